@@ -1,33 +1,41 @@
-use crate::utils::{i18n, Language};
+use crate::utils::{available_languages, i18n, Language};
 use std::io::Write;
 
-pub fn get_language_choice() -> Language {
-    println!("{}", i18n("language.choose", Language::Chinese));
-    println!("{}", i18n("language.chinese", Language::Chinese));
-    println!("{}", i18n("language.english", Language::Chinese));
+/// Asks on stdin which language to use; `None` when stdin ends or cannot be read.
+pub fn get_language_choice() -> Option<Language> {
+    let languages = available_languages();
+    println!("{}", i18n("language.choose", Language::CHINESE));
+    for (index, lang) in languages.iter().enumerate() {
+        println!("{}. {} ({})", index + 1, i18n("language.name", *lang), lang);
+    }
     println!();
 
     loop {
-        print!("{}", i18n("language.enter_choice", Language::Chinese));
-        std::io::stdout().flush().unwrap();
+        print!("{}", i18n("language.enter_choice", Language::CHINESE));
+        let _ = std::io::stdout().flush();
 
         let mut input = String::new();
-        std::io::stdin().read_line(&mut input).unwrap();
+        match std::io::stdin().read_line(&mut input) {
+            Ok(0) | Err(_) => return None,
+            Ok(_) => {}
+        }
+        let input = input.trim();
 
-        match input.trim() {
-            "1" | "chinese" => return Language::Chinese,
-            "2" | "english" | "English" => return Language::English,
-            _ => {
-                // Check against i18n translations
-                if input.trim() == i18n("lang.chinese", Language::Chinese) {
-                    return Language::Chinese;
-                } else if input.trim() == i18n("lang.english", Language::Chinese) {
-                    return Language::English;
-                } else {
-                    println!("{}", i18n("language.invalid_choice", Language::Chinese));
-                    continue;
-                }
-            }
+        // A number from the list, a tag such as `zh`, or a name such as `中文`.
+        let choice = input
+            .parse::<usize>()
+            .ok()
+            .and_then(|number| languages.get(number.checked_sub(1)?).copied())
+            .or_else(|| Language::from_code(input).filter(|lang| languages.contains(lang)))
+            .or_else(|| {
+                languages
+                    .iter()
+                    .copied()
+                    .find(|lang| i18n("language.name", *lang) == input)
+            });
+        match choice {
+            Some(lang) => return Some(lang),
+            None => println!("{}", i18n("language.invalid_choice", Language::CHINESE)),
         }
     }
 }
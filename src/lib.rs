@@ -4,11 +4,63 @@ pub mod utils;
 pub mod views;
 
 pub use controllers::language_controller::get_language_choice;
+pub use models::build_timings::{
+    measure_build_timings, parse_build_messages, parse_timing_report, summarize_builds, BuildKind,
+    BuildRecord, BuildRun, BuildTimings,
+};
 pub use models::daily_fortune::{
-    generate_daily_fortune, generate_daily_fortune_with_birthday, Fortune, WorkScenario,
+    generate_daily_fortune, generate_daily_fortune_with_birthday, generate_daily_fortune_with_pack,
+    generate_fortune_for_date, generate_pressure_adjusted_fortune, pressure_adjustments,
+    AdjustmentReason, DimensionAdjustment, Fortune, TechDimensions, WorkScenario,
+};
+pub use models::dev_pressure::{
+    analyze_dev_pressure, analyze_dev_pressure_in, analyze_dev_pressure_with_options, DevPressure,
+    PressureLevel, PressureOptions, ProbeKind,
+};
+pub use models::fortune_pack::{
+    discover_packs, find_pack, FortunePack, FortuneTemplate, DEFAULT_PACK_NAME,
+};
+pub use models::git_repo::{inspect_repository, measure_git_churn, GitChurn, RepoSignals};
+pub use models::history::{history_stats, sparkline, History, HistoryStats};
+pub use models::pressure_cache::PressureCache;
+pub use models::pressure_error::{PatternProblem, PressureError};
+pub use models::pressure_history::{
+    bar_chart, daily_values, detect_regressions, pressure_history_path, PressureHistory,
+    PressureSnapshot, Regression, TrendMetric, MAX_PRESSURE_SNAPSHOTS,
+};
+pub use models::pressure_score::{
+    MetricRule, PressureScore, ScoreContribution, ScoreInputs, ScoreMetric, ScoringModel,
+    ScoringProblem, REPO_CONFIG_FILE_NAME,
 };
-pub use models::dev_pressure::{analyze_dev_pressure, DevPressure, PressureLevel};
-pub use utils::i18n::i18n;
+pub use models::probe_command::{
+    run_command, ProbeControl, RunningProbe, DEFAULT_PROBE_TIMEOUT_SECONDS,
+};
+pub use models::probes::{
+    builtin_probes, select_probes, CustomProbe, Probe, ProbeResult, BUILD_FAILED, BUILD_SECONDS,
+    TESTS_BROKEN, TESTS_FAILED, TESTS_IGNORED, TESTS_PASSED,
+};
+pub use models::repo_dashboard::{analyze_repos, expand_repo_patterns, RepoPressure};
+pub use models::team::{generate_team_fortune, Team, TeamFortune, TeamMember};
+pub use models::test_results::{
+    parse_cargo_test_output, parse_go_test_json, parse_pytest_output, parse_test_counts,
+    run_cargo_tests, TestBinaryResult, TestRunSummary,
+};
+pub use utils::i18n::{
+    available_languages, i18n, i18n_args, is_supported, I18n, LocalizedError, Message,
+};
+pub use utils::i18n_check::{check_translations, TranslationReport};
 pub use utils::Language;
-pub use views::daily_fortune_view::display_fortune;
+pub use views::calendar_view::{display_calendar, CalendarSpan};
+pub use views::daily_fortune_view::{display_fortune, display_fortune_rows};
+pub use views::dashboard_view::display_pressure_dashboard;
 pub use views::dev_pressure_view::display_dev_pressure;
+pub use views::history_view::display_history;
+pub use views::i18n_check_view::display_translation_report;
+pub use views::pressure_history_view::display_pressure_history;
+pub use views::progress_view::{spawn_probe_spinner, ProbeSpinner};
+pub use views::report_view::{
+    render_report, DashboardEntry, DashboardReport, FortuneRangeReport, FortuneReport,
+    HistoryReport, OutputFormat, PressureHistoryReport, PressureReport, TeamReport,
+    REPORT_SCHEMA_VERSION,
+};
+pub use views::team_view::display_team_fortune;
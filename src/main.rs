@@ -1,10 +1,24 @@
 use anyhow::Result;
-use clap::Parser;
+use chrono::NaiveDate;
+use clap::{Parser, Subcommand};
+use findme::utils::config::{config_dir, UserConfig, CONFIG_FILE_NAME};
+use findme::utils::i18n::{translation_problems, translations};
+use findme::utils::i18n_check::{check_translations, read_sources};
+use findme::utils::{available_languages, detect_system_language, is_supported};
 use findme::{
-    analyze_dev_pressure, display_dev_pressure, display_fortune, get_language_choice, i18n,
-    Language,
+    analyze_dev_pressure_with_options, analyze_repos, detect_regressions, discover_packs,
+    display_calendar, display_dev_pressure, display_fortune, display_fortune_rows, display_history,
+    display_pressure_dashboard, display_pressure_history, display_team_fortune,
+    display_translation_report, expand_repo_patterns, find_pack, generate_fortune_for_date,
+    generate_pressure_adjusted_fortune, generate_team_fortune, get_language_choice, history_stats,
+    i18n, i18n_args, render_report, spawn_probe_spinner, CalendarSpan, DashboardReport,
+    DevPressure, Fortune, FortunePack, FortuneRangeReport, FortuneReport, History, HistoryReport,
+    Language, LocalizedError, OutputFormat, PressureHistory, PressureHistoryReport,
+    PressureOptions, PressureReport, PressureSnapshot, ProbeControl, ProbeKind, Team, TeamReport,
 };
-use std::fs;
+use serde::Serialize;
+use std::io::IsTerminal;
+use std::path::{Path, PathBuf};
 
 #[derive(Parser)]
 #[command(name = "findme")]
@@ -13,238 +27,874 @@ use std::fs;
 )]
 #[command(version)]
 struct Cli {
-    #[arg(short, long)]
+    #[command(subcommand)]
+    command: Option<Commands>,
+
+    #[arg(short, long, global = true)]
     verbose: bool,
 
-    #[arg(short, long)]
+    #[arg(short, long, global = true)]
     language: Option<String>,
 
     #[arg(long)]
     set_language: bool,
 
+    /// Never prompt on stdin; also set by FINDME_NO_INTERACTIVE=1
+    #[arg(long, global = true)]
+    no_interactive: bool,
+
     #[arg(short, long)]
     birthday: Option<String>,
 
     #[arg(long)]
     pressure: bool,
+
+    /// Pressure probes to run, comma-separated: git, tests, build (implies --pressure)
+    #[arg(long, value_delimiter = ',')]
+    probe: Option<Vec<ProbeKind>>,
+
+    /// Run pressure probes again instead of using cached results
+    #[arg(long)]
+    refresh: bool,
+
+    /// Show how many points each metric added to the pressure score (implies --pressure)
+    #[arg(long)]
+    explain: bool,
+
+    /// Let the repository state adjust today's dimensions (implies --pressure)
+    #[arg(long)]
+    pressure_adjusted: bool,
+
+    #[arg(long, global = true)]
+    pack: Option<String>,
+
+    #[arg(long)]
+    list_packs: bool,
+
+    #[arg(long, global = true, default_value_t = OutputFormat::Text)]
+    format: OutputFormat,
+
+    #[arg(long, global = true)]
+    date: Option<String>,
+
+    #[arg(long, requires = "to", conflicts_with_all = ["date", "pressure", "probe", "explain", "pressure_adjusted"])]
+    from: Option<String>,
+
+    #[arg(long, requires = "from")]
+    to: Option<String>,
+
+    #[arg(long, conflicts_with_all = ["from", "to", "pressure", "probe", "explain", "pressure_adjusted"])]
+    calendar: Option<CalendarSpan>,
 }
 
-#[derive(Default, Debug)]
-struct UserConfig {
-    language: Option<Language>,
-    birthday: Option<String>,
+#[derive(Subcommand)]
+enum Commands {
+    /// Combined fortune, best pairings and pair-programming pick for a team
+    Team {
+        /// Team file listing members and birthdays (defaults to ~/.findme/team.toml)
+        #[arg(long)]
+        file: Option<PathBuf>,
+    },
+    /// Past scores, high-score streaks and dimension averages
+    History {
+        /// Number of days to show, ending today (or on --date)
+        #[arg(long, default_value_t = 30)]
+        days: u32,
+    },
+    /// Read or change settings in ~/.findme/config.toml
+    Config {
+        #[command(subcommand)]
+        action: ConfigAction,
+    },
+    /// Pressure of several repositories at once, most pressured first
+    Pressure {
+        #[command(subcommand)]
+        action: Option<PressureAction>,
+        /// Repository paths or globs, comma-separated (defaults to pressure.repos)
+        #[arg(long, value_delimiter = ',')]
+        repos: Option<Vec<String>>,
+        /// Pressure probes to run in each repository: git, tests, build
+        #[arg(long, value_delimiter = ',')]
+        probe: Option<Vec<ProbeKind>>,
+        /// Run pressure probes again instead of using cached results
+        #[arg(long)]
+        refresh: bool,
+    },
+    /// Check the translations for missing, unused and mismatched messages
+    I18n {
+        #[command(subcommand)]
+        action: I18nAction,
+    },
 }
 
-fn get_config_dir() -> std::path::PathBuf {
-    if let Ok(dir) = std::env::var("FINDME_CONFIG_DIR") {
-        return std::path::PathBuf::from(dir);
-    }
+#[derive(Subcommand)]
+enum I18nAction {
+    /// List keys missing from a locale, keys no code uses and placeholder mismatches; exits 1 if any
+    Check {
+        /// Source directory scanned for used keys (defaults to ./src when it exists)
+        #[arg(long)]
+        src: Option<PathBuf>,
+    },
+}
 
-    let mut config_dir = dirs::home_dir().unwrap_or_else(|| std::path::PathBuf::from("."));
-    config_dir.push(".findme");
-    config_dir
+#[derive(Subcommand)]
+enum PressureAction {
+    /// Recorded runs of the repository in the current directory: chart of pressure score, churn, pass rate and build time, with regressions flagged
+    History {
+        /// Number of days to show, ending today (or on --date)
+        #[arg(long, default_value_t = 14)]
+        days: u32,
+    },
 }
 
-fn get_config_file() -> std::path::PathBuf {
-    let mut config_file = get_config_dir();
-    config_file.push("config.txt");
-    config_file
+#[derive(Subcommand)]
+enum ConfigAction {
+    /// Print the value of a key
+    Get { key: String },
+    /// Set a key (known keys are validated)
+    Set { key: String, value: String },
+    /// Remove a key
+    Unset { key: String },
+    /// Print every key and value
+    List,
+    /// Print the location of the config file
+    Path,
 }
 
-fn load_config() -> UserConfig {
-    let config_file = get_config_file();
-    if let Ok(content) = fs::read_to_string(config_file) {
-        parse_config(&content)
-    } else {
-        UserConfig::default()
-    }
+/// Longest `--from/--to` range accepted, to keep accidental decades off the terminal.
+const MAX_RANGE_DAYS: i64 = 366;
+
+fn get_packs_dir() -> PathBuf {
+    let mut packs_dir = config_dir();
+    packs_dir.push("packs");
+    packs_dir
+}
+
+fn get_team_file() -> PathBuf {
+    let mut team_file = config_dir();
+    team_file.push("team.toml");
+    team_file
+}
+
+fn load_config(language: Language) -> UserConfig {
+    UserConfig::load(&config_dir()).unwrap_or_else(|e| {
+        eprintln!(
+            "{}",
+            i18n_args(
+                "config.invalid",
+                language,
+                &[("error", &e.message(language))]
+            )
+        );
+        eprintln!("{}", i18n("config.fix_hint", language));
+        std::process::exit(1);
+    })
 }
 
-fn parse_config(content: &str) -> UserConfig {
-    let trimmed = content.trim();
-    if trimmed.is_empty() {
-        return UserConfig::default();
+fn save_config(config: &UserConfig) -> Result<(), LocalizedError> {
+    config.save(&config_dir())
+}
+
+fn save_config_or_exit(config: &UserConfig, language: Language) {
+    if let Err(e) = save_config(config) {
+        eprintln!(
+            "{}",
+            i18n_args(
+                "config.save_failed",
+                language,
+                &[("error", &e.message(language))]
+            )
+        );
+        std::process::exit(1);
     }
+}
 
-    match trimmed {
-        "zh" | "chinese" => UserConfig {
-            language: Some(Language::Chinese),
-            birthday: None,
-        },
-        "en" | "english" => UserConfig {
-            language: Some(Language::English),
-            birthday: None,
+/// Whether findme may prompt on stdin: it is a terminal and neither `--no-interactive` nor
+/// `FINDME_NO_INTERACTIVE` says otherwise.
+fn is_interactive(no_interactive: bool) -> bool {
+    let disabled_by_env = std::env::var("FINDME_NO_INTERACTIVE").is_ok_and(|value| {
+        !matches!(
+            value.trim().to_lowercase().as_str(),
+            "" | "0" | "false" | "no"
+        )
+    });
+    !no_interactive && !disabled_by_env && std::io::stdin().is_terminal()
+}
+
+/// A language tag or a language's own name such as `中文`, if findme has translations for it.
+fn parse_language(lang_str: &str) -> Option<Language> {
+    Language::from_code(lang_str)
+        .or_else(|| {
+            available_languages()
+                .into_iter()
+                .find(|lang| i18n("language.name", *lang) == lang_str.trim())
+        })
+        .filter(|lang| is_supported(*lang))
+}
+
+fn run_config(action: ConfigAction, language: Option<Language>) -> Result<()> {
+    let dir = config_dir();
+    let mut config = UserConfig::load_unvalidated(&dir).unwrap_or_else(|e| {
+        let language = language.unwrap_or(Language::ENGLISH);
+        eprintln!(
+            "{}",
+            i18n_args(
+                "config.invalid",
+                language,
+                &[("error", &e.message(language))]
+            )
+        );
+        std::process::exit(1);
+    });
+    let language = language
+        .or_else(|| config.language())
+        .or_else(detect_system_language)
+        .unwrap_or(Language::ENGLISH);
+
+    match action {
+        ConfigAction::Path => println!("{}", dir.join(CONFIG_FILE_NAME).display()),
+        ConfigAction::List => {
+            for (key, value) in config.entries() {
+                println!("{} = {}", key, value);
+            }
+        }
+        ConfigAction::Get { key } => match config.get(&key) {
+            Some(value) => match value.as_str() {
+                Some(text) => println!("{}", text),
+                None => println!("{}", value),
+            },
+            None => {
+                eprintln!(
+                    "{}",
+                    i18n_args("config.key_not_set", language, &[("key", &key)])
+                );
+                std::process::exit(1);
+            }
         },
-        _ => {
-            let mut config = UserConfig::default();
-            for line in content.lines() {
-                let line = line.trim();
-                if line.is_empty() || line.starts_with('#') {
-                    continue;
-                }
-
-                if let Some((key, value)) = line.split_once('=') {
-                    let key = key.trim();
-                    let value = value.trim();
-                    match key {
-                        "language" => match value.to_lowercase().as_str() {
-                            "zh" | "chinese" => config.language = Some(Language::Chinese),
-                            "en" | "english" => config.language = Some(Language::English),
-                            _ => {}
-                        },
-                        "birthday" => {
-                            if !value.is_empty()
-                                && chrono::NaiveDate::parse_from_str(value, "%Y-%m-%d").is_ok()
-                            {
-                                config.birthday = Some(value.to_string());
-                            }
-                        }
-                        _ => {}
-                    }
-                }
+        ConfigAction::Set { key, value } => {
+            if let Err(e) = config.set(&key, &value) {
+                eprintln!(
+                    "{}",
+                    i18n_args(
+                        "config.invalid_value",
+                        language,
+                        &[("error", &e.message(language))]
+                    )
+                );
+                std::process::exit(1);
+            }
+            save_config_or_exit(&config, language);
+            println!(
+                "{}",
+                i18n_args("config.updated", language, &[("key", &key)])
+            );
+        }
+        ConfigAction::Unset { key } => {
+            if !config.unset(&key) {
+                eprintln!(
+                    "{}",
+                    i18n_args("config.key_not_set", language, &[("key", &key)])
+                );
+                std::process::exit(1);
             }
-            config
+            save_config_or_exit(&config, language);
+            println!(
+                "{}",
+                i18n_args("config.removed", language, &[("key", &key)])
+            );
         }
     }
+    Ok(())
 }
 
-fn save_config(config: &UserConfig) -> Result<()> {
-    let config_dir = get_config_dir();
-    if !config_dir.exists() {
-        fs::create_dir_all(&config_dir)?;
-    }
+fn parse_date_arg(value: &str, language: Language) -> NaiveDate {
+    NaiveDate::parse_from_str(value, "%Y-%m-%d").unwrap_or_else(|_| {
+        eprintln!(
+            "{}",
+            i18n_args("date.invalid", language, &[("date", &value)])
+        );
+        std::process::exit(1);
+    })
+}
 
-    let config_file = get_config_file();
-    let mut lines = Vec::new();
+fn render_failed(error: LocalizedError, language: Language) -> ! {
+    eprintln!(
+        "{}",
+        i18n_args(
+            "pack.render_failed",
+            language,
+            &[("error", &error.message(language))]
+        )
+    );
+    std::process::exit(1);
+}
 
-    if let Some(lang) = config.language {
-        let lang_value = match lang {
-            Language::Chinese => "zh",
-            Language::English => "en",
-        };
-        lines.push(format!("language={}", lang_value));
+/// Prints `report` in a structured `format`.
+fn print_report<T: Serialize>(report: &T, format: OutputFormat, language: Language) {
+    match render_report(report, format) {
+        Ok(document) => print_document(&document),
+        Err(e) => {
+            eprintln!(
+                "{}",
+                i18n_args(
+                    "report.render_failed",
+                    language,
+                    &[("error", &e.message(language))]
+                )
+            );
+            std::process::exit(1);
+        }
     }
+}
 
-    if let Some(birthday) = &config.birthday {
-        lines.push(format!("birthday={}", birthday));
+fn print_document(document: &str) {
+    print!("{}", document);
+    if !document.ends_with('\n') {
+        println!();
     }
+}
 
-    let content = lines.join("\n");
-    fs::write(config_file, content)?;
+fn run_team(
+    file: Option<PathBuf>,
+    date: NaiveDate,
+    pack: &FortunePack,
+    format: OutputFormat,
+    language: Language,
+) -> Result<()> {
+    let path = file.unwrap_or_else(get_team_file);
+    let team = Team::load(&path).unwrap_or_else(|e| {
+        eprintln!(
+            "{}",
+            i18n_args(
+                "team.load_failed",
+                language,
+                &[("error", &e.message(language))]
+            )
+        );
+        std::process::exit(1);
+    });
+
+    let team_fortune = generate_team_fortune(&team, date, pack, language)
+        .unwrap_or_else(|e| render_failed(e, language));
+
+    if format == OutputFormat::Text {
+        display_team_fortune(&team_fortune, language);
+    } else {
+        let report = TeamReport::new(&team_fortune);
+        print_report(&report, format, language);
+    }
     Ok(())
 }
 
-fn load_language() -> Option<Language> {
-    load_config().language
+fn run_history(days: u32, date: NaiveDate, format: OutputFormat, language: Language) -> Result<()> {
+    let history = History::load(&config_dir()).unwrap_or_else(|e| {
+        eprintln!(
+            "{}",
+            i18n_args(
+                "history.load_failed",
+                language,
+                &[("error", &e.message(language))]
+            )
+        );
+        std::process::exit(1);
+    });
+    let entries = history.recent(date, days);
+    let stats = history_stats(entries);
+
+    if format == OutputFormat::Text {
+        display_history(entries, &stats, language);
+    } else {
+        let report = HistoryReport::new(&stats, entries);
+        print_report(&report, format, language);
+    }
+    Ok(())
 }
 
-fn save_language(lang: Language) -> Result<()> {
-    let mut config = load_config();
-    config.language = Some(lang);
-    save_config(&config)
+/// The `[pressure]` settings with command-line overrides and findme's own directories.
+fn pressure_options(
+    config: &UserConfig,
+    probes: Option<Vec<ProbeKind>>,
+    refresh: bool,
+) -> PressureOptions {
+    let mut options = PressureOptions::from_config(config);
+    if let Some(probes) = probes {
+        options.probes = probes;
+    }
+    options.cache_dir = Some(config_dir().join("cache"));
+    options.history_dir = Some(config_dir());
+    options.refresh = refresh;
+    cancel_on_ctrl_c(&options.control);
+    options
 }
 
-fn parse_language(lang_str: &str) -> Option<Language> {
-    match lang_str.to_lowercase().as_str() {
-        "zh" | "chinese" => Some(Language::Chinese),
-        "en" | "english" => Some(Language::English),
-        _ => {
-            // Check against i18n translations
-            if lang_str == i18n("lang.chinese", Language::Chinese) {
-                Some(Language::Chinese)
-            } else if lang_str == i18n("lang.english", Language::Chinese) {
-                Some(Language::English)
-            } else {
-                None
-            }
+/// The first Ctrl-C stops the running probes; a second one exits right away.
+fn cancel_on_ctrl_c(control: &ProbeControl) {
+    let control = control.clone();
+    let _ = ctrlc::set_handler(move || {
+        if control.is_cancelled() {
+            std::process::exit(130);
         }
+        control.cancel();
+    });
+}
+
+/// Exits after a cancelled run instead of showing what the probes managed to measure.
+fn exit_if_cancelled(options: &PressureOptions, language: Language) {
+    if options.control.is_cancelled() {
+        eprintln!("{}", i18n("pressure.cancelled", language));
+        std::process::exit(130);
+    }
+}
+
+fn run_i18n_check(src: Option<PathBuf>, format: OutputFormat, language: Language) -> Result<()> {
+    let src = src.or_else(|| Some(PathBuf::from("src")).filter(|dir| dir.is_dir()));
+    let sources = src.map(|dir| {
+        read_sources(&dir).unwrap_or_else(|e| {
+            eprintln!(
+                "{}",
+                i18n_args(
+                    "i18n_check.sources_failed",
+                    language,
+                    &[("error", &e.message(language))]
+                )
+            );
+            std::process::exit(1);
+        })
+    });
+    let report = check_translations(translations(), sources.as_deref());
+
+    if format == OutputFormat::Text {
+        display_translation_report(&report, language);
+    } else {
+        print_report(&report, format, language);
     }
+    if !report.is_clean() {
+        std::process::exit(1);
+    }
+    Ok(())
 }
 
-fn load_birthday() -> Option<String> {
-    load_config().birthday
+fn run_pressure_dashboard(
+    repos: Option<Vec<String>>,
+    options: &PressureOptions,
+    format: OutputFormat,
+    language: Language,
+) -> Result<()> {
+    let patterns = repos.unwrap_or_else(|| options.repos.clone());
+    let base = std::env::current_dir().unwrap_or_else(|_| PathBuf::from("."));
+    let repos = expand_repo_patterns(&patterns, &base).unwrap_or_else(|e| {
+        eprintln!(
+            "{}",
+            i18n_args(
+                "dashboard.error",
+                language,
+                &[("error", &e.message(language))]
+            )
+        );
+        std::process::exit(1);
+    });
+
+    let results = {
+        let _spinner = spawn_probe_spinner(&options.control, language);
+        analyze_repos(&repos, options, language)
+    };
+    exit_if_cancelled(options, language);
+    for pressure in results.iter().filter_map(|repo| repo.result.as_ref().ok()) {
+        record_pressure(pressure, language);
+    }
+
+    if format == OutputFormat::Text {
+        display_pressure_dashboard(&results, language);
+    } else {
+        let report = DashboardReport::new(&results);
+        print_report(&report, format, language);
+    }
+    Ok(())
 }
 
-fn save_birthday(birthday: &str) -> Result<()> {
-    let mut config = load_config();
-    config.birthday = Some(birthday.trim().to_string());
-    save_config(&config)
+fn run_pressure_history(
+    days: u32,
+    date: NaiveDate,
+    format: OutputFormat,
+    language: Language,
+) -> Result<()> {
+    let repo = std::fs::canonicalize(".").unwrap_or_else(|_| PathBuf::from("."));
+    let history = PressureHistory::load(&config_dir(), &repo).unwrap_or_else(|e| {
+        eprintln!(
+            "{}",
+            i18n_args(
+                "pressure_history.load_failed",
+                language,
+                &[("error", &e.message(language))]
+            )
+        );
+        std::process::exit(1);
+    });
+    let snapshots = history.recent(date, days);
+    let regressions = detect_regressions(snapshots);
+
+    if format == OutputFormat::Text {
+        display_pressure_history(&repo, snapshots, &regressions, date, days, language);
+    } else {
+        let report = PressureHistoryReport::new(&repo, snapshots, &regressions);
+        print_report(&report, format, language);
+    }
+    Ok(())
 }
 
-fn main() -> Result<()> {
-    let args = Cli::parse();
+/// Adds this run to the repository's pressure history; failures only warn.
+fn record_pressure(pressure: &DevPressure, language: Language) {
+    let dir = config_dir();
+    let repo: &Path = &pressure.root;
+    let result = PressureHistory::load(&dir, repo).and_then(|mut history| {
+        history.record(PressureSnapshot::new(pressure, chrono::Local::now()));
+        history.save(&dir, repo)
+    });
+    if let Err(e) = result {
+        eprintln!(
+            "{}",
+            i18n_args(
+                "pressure_history.save_failed",
+                language,
+                &[("error", &e.message(language))]
+            )
+        );
+    }
+}
 
-    if args.set_language {
-        let lang = get_language_choice();
-        save_language(lang)?;
-        match lang {
-            Language::Chinese => println!("{}", i18n("language.set_chinese", lang)),
-            Language::English => println!("{}", i18n("language.set_english", lang)),
+/// Adds today's fortune to the journal; failures only warn.
+fn record_history(fortune: &Fortune, language: Language) {
+    let dir = config_dir();
+    let result = History::load(&dir).and_then(|mut history| {
+        history.record(fortune);
+        history.save(&dir)
+    });
+    if let Err(e) = result {
+        eprintln!(
+            "{}",
+            i18n_args(
+                "history.save_failed",
+                language,
+                &[("error", &e.message(language))]
+            )
+        );
+    }
+}
+
+fn list_packs(language: Language) {
+    let packs_dir = get_packs_dir();
+    println!(
+        "{}",
+        i18n_args(
+            "pack.list_title",
+            language,
+            &[("path", &packs_dir.display())]
+        )
+    );
+    println!(
+        "  {} ({})",
+        findme::DEFAULT_PACK_NAME,
+        i18n("pack.builtin", language)
+    );
+
+    for (path, pack) in discover_packs(&packs_dir) {
+        match pack {
+            Ok(pack) => println!("  {} {} - {}", pack.name, pack.version, path.display()),
+            Err(e) => println!(
+                "  {}",
+                i18n_args("pack.invalid", language, &[("error", &e.message(language))])
+            ),
         }
-        return Ok(());
     }
+}
 
-    let language = if let Some(lang_str) = args.language {
-        parse_language(&lang_str).unwrap_or_else(|| {
+fn main() -> Result<()> {
+    let args = Cli::parse();
+
+    let requested_language = args.language.as_deref().map(|lang_str| {
+        parse_language(lang_str).unwrap_or_else(|| {
+            eprintln!(
+                "{}",
+                i18n_args(
+                    "language.invalid_option",
+                    Language::CHINESE,
+                    &[("option", &lang_str)]
+                )
+            );
+            let codes: Vec<&str> = available_languages()
+                .iter()
+                .map(|lang| lang.code())
+                .collect();
             eprintln!(
                 "{}",
-                i18n("language.invalid_option", Language::Chinese).replace("{}", &lang_str)
+                i18n_args(
+                    "language.available",
+                    Language::CHINESE,
+                    &[("languages", &codes.join(", "))]
+                )
             );
-            eprintln!("{}", i18n("language.use_zh_en", Language::Chinese));
             std::process::exit(1);
         })
-    } else {
-        load_language().unwrap_or_else(|| {
-            println!("{}", i18n("language.first_time", Language::Chinese));
-            let lang = get_language_choice();
-            if let Err(e) = save_language(lang) {
+    });
+
+    if let Some(Commands::Config { action }) = args.command {
+        return run_config(action, requested_language);
+    }
+
+    let mut config = load_config(
+        requested_language
+            .or_else(detect_system_language)
+            .unwrap_or(Language::ENGLISH),
+    );
+    let interactive = is_interactive(args.no_interactive);
+
+    if args.set_language {
+        let Some(lang) = interactive.then(get_language_choice).flatten() else {
+            eprintln!(
+                "{}",
+                i18n(
+                    "language.prompt_unavailable",
+                    requested_language
+                        .or_else(|| config.language())
+                        .or_else(detect_system_language)
+                        .unwrap_or(Language::ENGLISH)
+                )
+            );
+            std::process::exit(1);
+        };
+        config.set_language(lang);
+        save_config_or_exit(&config, lang);
+        println!(
+            "{}",
+            i18n_args(
+                "language.set",
+                lang,
+                &[("language", &i18n("language.name", lang))]
+            )
+        );
+        return Ok(());
+    }
+
+    let language = requested_language
+        .or_else(|| config.language())
+        .or_else(detect_system_language)
+        .or_else(|| {
+            if !interactive {
+                return None;
+            }
+            println!("{}", i18n("language.first_time", Language::CHINESE));
+            let lang = get_language_choice()?;
+            config.set_language(lang);
+            if let Err(e) = save_config(&config) {
                 eprintln!(
                     "{}",
-                    i18n("language.cannot_save", Language::Chinese).replace("{}", &e.to_string())
+                    i18n_args(
+                        "language.cannot_save",
+                        Language::CHINESE,
+                        &[("error", &e.message(lang))]
+                    )
                 );
             }
-            lang
+            Some(lang)
         })
-    };
+        .unwrap_or(Language::ENGLISH);
+
+    for problem in translation_problems() {
+        eprintln!(
+            "{}",
+            i18n_args(
+                "i18n.override_ignored",
+                language,
+                &[("problem", &problem.message(language))]
+            )
+        );
+    }
 
     if args.verbose {
         println!("🎯 Findme - {}", i18n("app.title", language));
-        println!("Version: {}", env!("CARGO_PKG_VERSION"));
-        println!("Author: {}", env!("CARGO_PKG_AUTHORS"));
+        println!(
+            "{}",
+            i18n_args(
+                "app.version",
+                language,
+                &[("version", &env!("CARGO_PKG_VERSION"))]
+            )
+        );
+        println!(
+            "{}",
+            i18n_args(
+                "app.author",
+                language,
+                &[("author", &env!("CARGO_PKG_AUTHORS"))]
+            )
+        );
         println!();
     }
 
+    if args.list_packs {
+        list_packs(language);
+        return Ok(());
+    }
+
+    let pack = match args.pack.as_deref() {
+        Some(name) => find_pack(&get_packs_dir(), name, language).unwrap_or_else(|e| {
+            eprintln!(
+                "{}",
+                i18n_args(
+                    "pack.load_failed",
+                    language,
+                    &[("error", &e.message(language))]
+                )
+            );
+            std::process::exit(1);
+        }),
+        None => findme::FortunePack::builtin(language),
+    };
+
     let birthday = if let Some(birthday_str) = args.birthday.as_deref() {
-        if chrono::NaiveDate::parse_from_str(birthday_str, "%Y-%m-%d").is_err() {
+        if config.set_birthday(birthday_str).is_err() {
             eprintln!(
-                "Invalid birthday format: {}. Please use YYYY-MM-DD format",
-                birthday_str
+                "{}",
+                i18n_args("birthday.invalid", language, &[("birthday", &birthday_str)])
             );
             std::process::exit(1);
         }
-        if let Err(e) = save_birthday(birthday_str) {
-            eprintln!("⚠️ Could not save birthday: {}", e);
+        if let Err(e) = save_config(&config) {
+            eprintln!(
+                "{}",
+                i18n_args(
+                    "birthday.save_failed",
+                    language,
+                    &[("error", &e.message(language))]
+                )
+            );
         }
-        Some(birthday_str.to_string())
+        Some(birthday_str.trim().to_string())
     } else {
-        load_birthday()
+        config.birthday().map(str::to_string)
     };
 
-    let fortune = if let Some(birthday_str) = birthday.as_deref() {
-        findme::generate_daily_fortune_with_birthday(birthday_str, language)
-    } else {
-        findme::generate_daily_fortune(language)
+    let date = match args.date.as_deref() {
+        Some(value) => parse_date_arg(value, language),
+        None => chrono::Local::now().date_naive(),
     };
 
+    match args.command {
+        Some(Commands::Team { file }) => return run_team(file, date, &pack, args.format, language),
+        Some(Commands::History { days }) => return run_history(days, date, args.format, language),
+        Some(Commands::I18n {
+            action: I18nAction::Check { src },
+        }) => return run_i18n_check(src, args.format, language),
+        Some(Commands::Pressure {
+            action: Some(PressureAction::History { days }),
+            ..
+        }) => return run_pressure_history(days, date, args.format, language),
+        Some(Commands::Pressure {
+            action: None,
+            repos,
+            probe,
+            refresh,
+        }) => {
+            let options = pressure_options(&config, probe, refresh);
+            return run_pressure_dashboard(repos, &options, args.format, language);
+        }
+        _ => {}
+    }
+
+    let range = match (args.from.as_deref(), args.to.as_deref(), args.calendar) {
+        (Some(from), Some(to), _) => {
+            Some((parse_date_arg(from, language), parse_date_arg(to, language)))
+        }
+        (_, _, Some(span)) => Some(span.range(date)),
+        _ => None,
+    };
+
+    if let Some((from, to)) = range {
+        if from > to {
+            eprintln!("{}", i18n("date.range_reversed", language));
+            std::process::exit(1);
+        }
+        if (to - from).num_days() >= MAX_RANGE_DAYS {
+            eprintln!(
+                "{}",
+                i18n_args(
+                    "date.range_too_long",
+                    language,
+                    &[("days", &MAX_RANGE_DAYS)]
+                )
+            );
+            std::process::exit(1);
+        }
+
+        let fortunes = from
+            .iter_days()
+            .take_while(|date| *date <= to)
+            .map(|date| generate_fortune_for_date(date, birthday.as_deref(), &pack, None, language))
+            .collect::<Result<Vec<_>, _>>()
+            .unwrap_or_else(|e| render_failed(e, language));
+
+        if args.format != OutputFormat::Text {
+            let report = FortuneRangeReport::new(&fortunes);
+            print_report(&report, args.format, language);
+        } else if args.calendar.is_some() {
+            display_calendar(&fortunes, language);
+        } else {
+            display_fortune_rows(&fortunes, language);
+        }
+        return Ok(());
+    }
+
+    let wants_pressure =
+        args.pressure || args.probe.is_some() || args.explain || args.pressure_adjusted;
+    let pressure = wants_pressure.then(|| {
+        let options = pressure_options(&config, args.probe.clone(), args.refresh);
+        let pressure = {
+            let _spinner = spawn_probe_spinner(&options.control, language);
+            analyze_dev_pressure_with_options(&options, language)
+        };
+        exit_if_cancelled(&options, language);
+        pressure
+    });
+    if let Some(Ok(pressure)) = &pressure {
+        record_pressure(pressure, language);
+    }
+
+    let fortune = match pressure.as_ref().and_then(|result| result.as_ref().ok()) {
+        Some(pressure) if args.pressure_adjusted => {
+            generate_pressure_adjusted_fortune(date, birthday.as_deref(), &pack, pressure, language)
+        }
+        pressure => generate_fortune_for_date(date, birthday.as_deref(), &pack, pressure, language),
+    }
+    .unwrap_or_else(|e| render_failed(e, language));
+
+    if date == chrono::Local::now().date_naive() {
+        record_history(&fortune, language);
+    }
+
+    if args.format != OutputFormat::Text {
+        let mut report = FortuneReport::new(&fortune);
+        match &pressure {
+            Some(Ok(pressure)) => report.pressure = Some(PressureReport::new(pressure)),
+            Some(Err(e)) => report.pressure_error = Some(e),
+            None => {}
+        }
+        print_report(&report, args.format, language);
+        return Ok(());
+    }
+
     display_fortune(&fortune, language);
 
-    if args.pressure {
-        match analyze_dev_pressure(language) {
-            Ok(pressure) => display_dev_pressure(&pressure, language),
-            Err(e) => {
-                eprintln!("⚠️ Failed to analyze developer pressure: {}", e);
-                eprintln!("💡 Make sure you're in a git repository and have cargo available");
-            }
+    if let Some(pressure) = pressure {
+        match pressure {
+            Ok(pressure) => display_dev_pressure(&pressure, args.explain, language),
+            Err(e) => eprintln!(
+                "{}",
+                i18n_args(
+                    "pressure.analysis_failed",
+                    language,
+                    &[("error", &e.message(language))]
+                )
+            ),
         }
     }
 
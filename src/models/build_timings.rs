@@ -0,0 +1,339 @@
+use crate::models::dev_pressure::PressureOptions;
+use crate::models::pressure_cache::repo_key;
+use crate::models::pressure_error::PressureError;
+use crate::models::probe_command::run_command;
+use chrono::NaiveDateTime;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::{LazyLock, Mutex};
+use std::time::{Instant, SystemTime, UNIX_EPOCH};
+
+/// Version written to build history files; bump it when [`BuildRecord`] changes shape.
+const BUILD_HISTORY_SCHEMA_VERSION: u32 = 1;
+/// Builds kept per repository; older ones are dropped.
+pub const MAX_RECORDED_BUILDS: usize = 50;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum BuildKind {
+    /// Every unit was compiled.
+    Clean,
+    /// Some units were reused from an earlier build.
+    Incremental,
+}
+
+/// One build that actually compiled something.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BuildRecord {
+    /// Timing report file name, or `findme-<unix seconds>` for builds findme ran.
+    pub id: String,
+    pub finished_at: i64,
+    pub seconds: f32,
+    pub kind: BuildKind,
+    pub dirty_units: u32,
+    pub total_units: u32,
+}
+
+/// Latest and median build durations, split by kind.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BuildTimings {
+    pub latest: BuildRecord,
+    pub median_seconds: f32,
+    pub clean_median_seconds: Option<f32>,
+    pub incremental_median_seconds: Option<f32>,
+    pub recorded_builds: usize,
+}
+
+/// The latest build, with the builds recorded so far.
+#[derive(Debug, Clone, PartialEq)]
+pub struct BuildRun {
+    /// Whether the build succeeded; recorded builds always did.
+    pub success: bool,
+    /// How long the build took, including a build findme ran that had nothing to compile.
+    pub seconds: f32,
+    /// Summary of the recorded builds; `None` before any real build was seen.
+    pub timings: Option<BuildTimings>,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct BuildHistory {
+    schema_version: u32,
+    builds: Vec<BuildRecord>,
+}
+
+/// Summarizes the recorded builds of the project containing `repo`.
+///
+/// Cargo `--timings` reports found in the target directory are imported first, so builds
+/// run outside findme count. Only when no build is recorded yet, or `options.refresh` is
+/// set, does findme build the project itself and time it. Failed builds and builds with
+/// nothing to compile are not recorded, but the returned run says whether this one
+/// succeeded. Returns `None` outside a cargo project. Builds are recorded under
+/// `options.history_dir`.
+pub fn measure_build_timings(
+    repo: &Path,
+    options: &PressureOptions,
+) -> Result<Option<BuildRun>, PressureError> {
+    if !repo
+        .ancestors()
+        .any(|ancestor| ancestor.join("Cargo.toml").is_file())
+    {
+        return Ok(None);
+    }
+
+    let root = fs::canonicalize(repo).unwrap_or_else(|_| repo.to_path_buf());
+    let history_path = options
+        .history_dir
+        .as_ref()
+        .map(|dir| dir.join("builds").join(format!("{}.json", repo_key(&root))));
+    let mut history = history_path
+        .as_ref()
+        .and_then(|path| fs::read_to_string(path).ok())
+        .and_then(|content| serde_json::from_str::<BuildHistory>(&content).ok())
+        .filter(|history| history.schema_version == BUILD_HISTORY_SCHEMA_VERSION)
+        .unwrap_or_default();
+
+    let timings_dir = target_dir(&root, options)?.join("cargo-timings");
+    let mut imported = false;
+    if let Ok(entries) = fs::read_dir(&timings_dir) {
+        for entry in entries.flatten() {
+            let name = entry.file_name().to_string_lossy().into_owned();
+            if history.builds.iter().any(|build| build.id == name) {
+                continue;
+            }
+            if let Some(build) = fs::read_to_string(entry.path())
+                .ok()
+                .and_then(|html| parse_timing_report(&name, &html))
+            {
+                history.builds.push(build);
+                imported = true;
+            }
+        }
+    }
+
+    // Building again would only repeat what is recorded; cargo runs for the first
+    // measurement and when asked to refresh.
+    if !options.refresh && !history.builds.is_empty() {
+        if imported {
+            save_history(&mut history, history_path.as_deref())?;
+        }
+        history.builds.sort_by_key(|build| build.finished_at);
+        let timings = summarize_builds(&history.builds);
+        return Ok(Some(BuildRun {
+            success: true,
+            seconds: timings
+                .as_ref()
+                .map_or(0.0, |timings| timings.latest.seconds),
+            timings,
+        }));
+    }
+
+    let args = ["cargo", "build", "--message-format=json"];
+    let start = Instant::now();
+    let output = run_command(
+        repo,
+        "cargo",
+        &args,
+        options.timeout_seconds,
+        &options.control,
+    )?;
+    let seconds = start.elapsed().as_secs_f32();
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    // Cargo gave up before building anything, e.g. on a broken manifest.
+    if !output.status.success() && stdout.trim().is_empty() {
+        return Err(PressureError::command_failed(
+            "cargo",
+            &args,
+            output.status.code(),
+            &String::from_utf8_lossy(&output.stderr),
+        ));
+    }
+    let (dirty_units, total_units, success) = parse_build_messages(&stdout);
+    if success && dirty_units > 0 {
+        let finished_at = now_seconds();
+        history.builds.push(BuildRecord {
+            id: format!("findme-{}", finished_at),
+            finished_at,
+            seconds,
+            kind: build_kind(dirty_units, total_units),
+            dirty_units,
+            total_units,
+        });
+    }
+
+    save_history(&mut history, history_path.as_deref())?;
+    Ok(Some(BuildRun {
+        success,
+        seconds,
+        timings: summarize_builds(&history.builds),
+    }))
+}
+
+/// Keeps the newest [`MAX_RECORDED_BUILDS`] builds and writes them to `path`, if any.
+fn save_history(history: &mut BuildHistory, path: Option<&Path>) -> Result<(), PressureError> {
+    history.builds.sort_by_key(|build| build.finished_at);
+    let excess = history.builds.len().saturating_sub(MAX_RECORDED_BUILDS);
+    history.builds.drain(..excess);
+
+    let Some(path) = path else {
+        return Ok(());
+    };
+    history.schema_version = BUILD_HISTORY_SCHEMA_VERSION;
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir).map_err(|e| PressureError::io(dir, e))?;
+    }
+    let content = serde_json::to_string(&history).map_err(|e| PressureError::io(path, e))?;
+    fs::write(path, content).map_err(|e| PressureError::io(path, e))
+}
+
+/// Latest build plus medians over `builds` (oldest first).
+pub fn summarize_builds(builds: &[BuildRecord]) -> Option<BuildTimings> {
+    let latest = builds.last()?.clone();
+    let median_of = |kind: Option<BuildKind>| {
+        median(
+            builds
+                .iter()
+                .filter(|build| kind.is_none_or(|kind| build.kind == kind))
+                .map(|build| build.seconds)
+                .collect(),
+        )
+    };
+
+    Some(BuildTimings {
+        latest,
+        median_seconds: median_of(None).unwrap_or(0.0),
+        clean_median_seconds: median_of(Some(BuildKind::Clean)),
+        incremental_median_seconds: median_of(Some(BuildKind::Incremental)),
+        recorded_builds: builds.len(),
+    })
+}
+
+/// Reads a `cargo build --timings` HTML report named like `cargo-timing-20240603T101530123Z-<hash>.html`.
+///
+/// Returns `None` for the `cargo-timing.html` copy of the latest report and for builds that compiled nothing.
+pub fn parse_timing_report(name: &str, html: &str) -> Option<BuildRecord> {
+    let stamp = name
+        .strip_prefix("cargo-timing-")?
+        .split(['-', '.'])
+        .next()?;
+    let finished_at = NaiveDateTime::parse_from_str(stamp, "%Y%m%dT%H%M%S%3fZ")
+        .ok()?
+        .and_utc()
+        .timestamp();
+
+    let dirty_units: u32 = timing_field(html, "Dirty units:")?.parse().ok()?;
+    let total_units: u32 = timing_field(html, "Total units:")?.parse().ok()?;
+    if dirty_units == 0 {
+        return None;
+    }
+    // `12.3s` or `75.3s (1m 15.3s)`.
+    let seconds = timing_field(html, "Total time:")?
+        .split('s')
+        .next()?
+        .trim()
+        .parse()
+        .ok()?;
+
+    Some(BuildRecord {
+        id: name.to_string(),
+        finished_at,
+        seconds,
+        kind: build_kind(dirty_units, total_units),
+        dirty_units,
+        total_units,
+    })
+}
+
+/// Counts `(dirty units, total units, success)` in `cargo build --message-format=json` output.
+pub fn parse_build_messages(stdout: &str) -> (u32, u32, bool) {
+    let mut dirty = 0;
+    let mut total = 0;
+    let mut success = false;
+    for message in stdout
+        .lines()
+        .filter_map(|line| serde_json::from_str::<serde_json::Value>(line).ok())
+    {
+        match message["reason"].as_str() {
+            Some("compiler-artifact") => {
+                total += 1;
+                if message["fresh"] == false {
+                    dirty += 1;
+                }
+            }
+            Some("build-finished") => success = message["success"] == true,
+            _ => {}
+        }
+    }
+    (dirty, total, success)
+}
+
+fn build_kind(dirty_units: u32, total_units: u32) -> BuildKind {
+    if dirty_units >= total_units {
+        BuildKind::Clean
+    } else {
+        BuildKind::Incremental
+    }
+}
+
+/// The value cell following `label` in a timing report's summary table.
+fn timing_field<'a>(html: &'a str, label: &str) -> Option<&'a str> {
+    let rest = &html[html.find(label)? + label.len()..];
+    let rest = rest.strip_prefix("</td><td>")?;
+    Some(rest[..rest.find('<')?].trim())
+}
+
+/// Where cargo puts build output for `repo`, asked with the probe's timeout and control.
+///
+/// Cargo is asked once per repository and run of findme. Falls back to `repo/target` when
+/// cargo cannot describe the project.
+fn target_dir(repo: &Path, options: &PressureOptions) -> Result<PathBuf, PressureError> {
+    static TARGET_DIRS: LazyLock<Mutex<HashMap<PathBuf, PathBuf>>> =
+        LazyLock::new(Default::default);
+    if let Some(dir) = TARGET_DIRS
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .get(repo)
+    {
+        return Ok(dir.clone());
+    }
+
+    let output = run_command(
+        repo,
+        "cargo",
+        &["cargo", "metadata", "--no-deps", "--format-version", "1"],
+        options.timeout_seconds,
+        &options.control,
+    )?;
+    let dir = Some(output)
+        .filter(|output| output.status.success())
+        .and_then(|output| serde_json::from_slice::<serde_json::Value>(&output.stdout).ok())
+        .and_then(|metadata| metadata["target_directory"].as_str().map(PathBuf::from))
+        .unwrap_or_else(|| repo.join("target"));
+    TARGET_DIRS
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .insert(repo.to_path_buf(), dir.clone());
+    Ok(dir)
+}
+
+fn median(mut values: Vec<f32>) -> Option<f32> {
+    if values.is_empty() {
+        return None;
+    }
+    values.sort_by(f32::total_cmp);
+    let middle = values.len() / 2;
+    Some(if values.len().is_multiple_of(2) {
+        (values[middle - 1] + values[middle]) / 2.0
+    } else {
+        values[middle]
+    })
+}
+
+fn now_seconds() -> i64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|duration| duration.as_secs() as i64)
+        .unwrap_or(0)
+}
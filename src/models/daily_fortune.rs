@@ -1,18 +1,27 @@
-use crate::utils::{i18n, Language};
-use chrono::{Datelike, Local, Weekday};
+use crate::models::dev_pressure::DevPressure;
+use crate::models::fortune_pack::{
+    render_template, scenario_name, FortuneFacts, FortunePack, PressureContext, TemplateContext,
+    TemplateField,
+};
+use crate::models::pressure_score::ScoreMetric;
+use crate::utils::{detect_user_name, Language, LocalizedError};
+use chrono::{Datelike, Local, NaiveDate, Weekday};
 use rand::{Rng, SeedableRng};
 use rand_chacha::ChaCha20Rng;
 use serde::{Deserialize, Serialize};
 use sha2::{Digest, Sha256};
-use std::collections::HashMap;
 
-#[derive(Debug, Serialize, Deserialize)]
+/// Written in snake_case; the capitalized names of older history files are still read.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
 pub enum WorkScenario {
+    #[serde(alias = "Workday")]
     Workday,
+    #[serde(alias = "Weekend")]
     Weekend,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct TechDimensions {
     pub focus: u8,
     pub creativity: u8,
@@ -21,25 +30,75 @@ pub struct TechDimensions {
     pub risk_tolerance: u8,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
-pub struct FortuneTemplate {
-    pub condition: String,
-    pub message: String,
-    pub advice: String,
-    pub color: String,
-    pub time: String,
+impl TechDimensions {
+    /// Looks up a dimension by its name, e.g. `"risk_tolerance"`.
+    pub fn value(&self, dimension: &str) -> u8 {
+        match dimension {
+            "focus" => self.focus,
+            "creativity" => self.creativity,
+            "debugging" => self.debugging,
+            "collaboration" => self.collaboration,
+            _ => self.risk_tolerance,
+        }
+    }
+
+    /// Moves a dimension by `delta`, staying within 0–100.
+    pub fn adjust(&mut self, dimension: &str, delta: i16) {
+        let value = match dimension {
+            "focus" => &mut self.focus,
+            "creativity" => &mut self.creativity,
+            "debugging" => &mut self.debugging,
+            "collaboration" => &mut self.collaboration,
+            _ => &mut self.risk_tolerance,
+        };
+        *value = (*value as i16 + delta).clamp(0, 100) as u8;
+    }
+
+    /// The strongest dimension, preferring earlier dimensions on ties.
+    pub fn primary_dimension(&self) -> &'static str {
+        if self.focus >= self.creativity
+            && self.focus >= self.debugging
+            && self.focus >= self.collaboration
+            && self.focus >= self.risk_tolerance
+        {
+            "focus"
+        } else if self.creativity >= self.debugging
+            && self.creativity >= self.collaboration
+            && self.creativity >= self.risk_tolerance
+        {
+            "creativity"
+        } else if self.debugging >= self.collaboration && self.debugging >= self.risk_tolerance {
+            "debugging"
+        } else if self.collaboration >= self.risk_tolerance {
+            "collaboration"
+        } else {
+            "risk_tolerance"
+        }
+    }
 }
 
-#[derive(Debug, Serialize, Deserialize)]
-pub struct FortunePack {
-    pub name: String,
-    pub version: String,
-    pub templates: HashMap<String, FortuneTemplate>,
-    pub dimension_weights: HashMap<String, f32>,
+/// What in the repository moved a dimension.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum AdjustmentReason {
+    Churn,
+    Tests,
+    Build,
+    Conflicts,
 }
 
-#[derive(Debug)]
+/// One change the pressure-adjusted mode made to the day's dimensions.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DimensionAdjustment {
+    /// Dimension name as accepted by [`TechDimensions::value`].
+    pub dimension: String,
+    pub delta: i16,
+    pub reason: AdjustmentReason,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Fortune {
+    pub date: NaiveDate,
     pub scenario: WorkScenario,
     pub dimensions: TechDimensions,
     pub overall_score: u8,
@@ -47,22 +106,119 @@ pub struct Fortune {
     pub advice: String,
     pub lucky_color: String,
     pub lucky_time: String,
+    /// Changes made by [`generate_pressure_adjusted_fortune`]; empty otherwise.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub adjustments: Vec<DimensionAdjustment>,
 }
 
 pub fn generate_daily_fortune(lang: Language) -> Fortune {
-    let today = Local::now();
-    let today_str = format!("{:04}{:02}{:02}", today.year(), today.month(), today.day());
-    generate_fortune_with_seed(&today_str, None, lang)
+    generate_daily_fortune_with_pack(None, &FortunePack::builtin(lang), None, lang)
+        .expect("built-in templates always render")
 }
 
 pub fn generate_daily_fortune_with_birthday(birthday: &str, lang: Language) -> Fortune {
-    let today = Local::now();
-    let today_str = format!("{:04}{:02}{:02}", today.year(), today.month(), today.day());
-    generate_fortune_with_seed(&today_str, Some(birthday), lang)
+    generate_daily_fortune_with_pack(Some(birthday), &FortunePack::builtin(lang), None, lang)
+        .expect("built-in templates always render")
+}
+
+/// Generates today's fortune with content taken from `pack`.
+///
+/// Fields the pack has no matching template for fall back to the built-in pack.
+/// `pressure`, when given, is exposed to templates as `{pressure.level}` etc.
+pub fn generate_daily_fortune_with_pack(
+    birthday: Option<&str>,
+    pack: &FortunePack,
+    pressure: Option<&DevPressure>,
+    lang: Language,
+) -> Result<Fortune, LocalizedError> {
+    generate_fortune_for_date(Local::now().date_naive(), birthday, pack, pressure, lang)
+}
+
+/// Generates the fortune for any `date`; the same inputs always give the same fortune.
+pub fn generate_fortune_for_date(
+    date: NaiveDate,
+    birthday: Option<&str>,
+    pack: &FortunePack,
+    pressure: Option<&DevPressure>,
+    lang: Language,
+) -> Result<Fortune, LocalizedError> {
+    let date_str = format!("{:04}{:02}{:02}", date.year(), date.month(), date.day());
+    generate_fortune_with_seed(&date_str, birthday, pack, pressure, false, lang)
+}
+
+/// Like [`generate_fortune_for_date`], but the state of the repository moves the day's
+/// dimensions before the score, message and advice are chosen.
+///
+/// Failing tests lower debugging, heavy churn lowers risk tolerance and focus, slow or
+/// failing builds lower focus and merge conflicts lower collaboration. The same date and
+/// repository state always give the same fortune.
+pub fn generate_pressure_adjusted_fortune(
+    date: NaiveDate,
+    birthday: Option<&str>,
+    pack: &FortunePack,
+    pressure: &DevPressure,
+    lang: Language,
+) -> Result<Fortune, LocalizedError> {
+    let date_str = format!("{:04}{:02}{:02}", date.year(), date.month(), date.day());
+    generate_fortune_with_seed(&date_str, birthday, pack, Some(pressure), true, lang)
+}
+
+/// How the pressure-adjusted mode moves the dimensions for `pressure`.
+///
+/// Each score metric moves its dimensions in proportion to the share of its points it
+/// scored, so the thresholds in `.findme.toml` apply here too.
+pub fn pressure_adjustments(pressure: &DevPressure) -> Vec<DimensionAdjustment> {
+    let mut adjustments = Vec::new();
+    let mut push = |dimension: &str, delta: f64, reason: AdjustmentReason| {
+        let delta = delta.round() as i16;
+        if delta != 0 {
+            adjustments.push(DimensionAdjustment {
+                dimension: dimension.to_string(),
+                delta,
+                reason,
+            });
+        }
+    };
+
+    for contribution in &pressure.score_breakdown {
+        let share = if contribution.max_points > 0.0 {
+            contribution.points / contribution.max_points
+        } else {
+            0.0
+        };
+        match contribution.metric {
+            ScoreMetric::Churn => {
+                push("risk_tolerance", -30.0 * share, AdjustmentReason::Churn);
+                push("focus", -10.0 * share, AdjustmentReason::Churn);
+            }
+            // A fully green suite is worth a little confidence.
+            ScoreMetric::Tests if contribution.value >= 1.0 => {
+                push("debugging", 5.0, AdjustmentReason::Tests)
+            }
+            ScoreMetric::Tests => push("debugging", -30.0 * share, AdjustmentReason::Tests),
+            ScoreMetric::Build => push("focus", -20.0 * share, AdjustmentReason::Build),
+        }
+    }
+
+    if pressure
+        .repo
+        .as_ref()
+        .is_some_and(|repo| repo.conflicted_files > 0)
+    {
+        push("collaboration", -15.0, AdjustmentReason::Conflicts);
+    }
+    adjustments
 }
 
-fn generate_fortune_with_seed(today: &str, birthday: Option<&str>, lang: Language) -> Fortune {
-    let today_date = chrono::NaiveDate::parse_from_str(today, "%Y%m%d").unwrap();
+fn generate_fortune_with_seed(
+    today: &str,
+    birthday: Option<&str>,
+    pack: &FortunePack,
+    pressure: Option<&DevPressure>,
+    adjust: bool,
+    lang: Language,
+) -> Result<Fortune, LocalizedError> {
+    let today_date = NaiveDate::parse_from_str(today, "%Y%m%d").unwrap();
     let scenario = match today_date.weekday() {
         Weekday::Sat | Weekday::Sun => WorkScenario::Weekend,
         _ => WorkScenario::Workday,
@@ -83,14 +239,31 @@ fn generate_fortune_with_seed(today: &str, birthday: Option<&str>, lang: Languag
 
     let mut rng = ChaCha20Rng::seed_from_u64(seed);
 
-    let dimensions = generate_scenario_biased_dimensions(&scenario, &mut rng);
+    let mut dimensions = generate_scenario_biased_dimensions(&scenario, &mut rng);
+
+    // Applied after the draw so the rest of the day's randomness is unchanged.
+    let adjustments = match pressure {
+        Some(pressure) if adjust => pressure_adjustments(pressure),
+        _ => Vec::new(),
+    };
+    for adjustment in &adjustments {
+        dimensions.adjust(&adjustment.dimension, adjustment.delta);
+    }
 
-    let overall_score = calculate_scenario_weighted_score(&dimensions, &scenario);
+    let overall_score = calculate_scenario_weighted_score(&dimensions, &scenario, pack);
 
-    let (message, advice, color, time) =
-        generate_fortune_content(&dimensions, &scenario, &mut rng, lang);
+    let (message, advice, color, time) = generate_fortune_content(
+        &dimensions,
+        &scenario,
+        overall_score,
+        &mut rng,
+        pack,
+        pressure,
+        lang,
+    )?;
 
-    Fortune {
+    Ok(Fortune {
+        date: today_date,
         scenario,
         dimensions,
         overall_score,
@@ -98,7 +271,8 @@ fn generate_fortune_with_seed(today: &str, birthday: Option<&str>, lang: Languag
         advice,
         lucky_color: color,
         lucky_time: time,
-    }
+        adjustments,
+    })
 }
 
 fn generate_scenario_biased_dimensions(
@@ -123,7 +297,39 @@ fn generate_scenario_biased_dimensions(
     }
 }
 
-fn calculate_scenario_weighted_score(dimensions: &TechDimensions, scenario: &WorkScenario) -> u8 {
+fn calculate_scenario_weighted_score(
+    dimensions: &TechDimensions,
+    scenario: &WorkScenario,
+    pack: &FortunePack,
+) -> u8 {
+    let dimension_order = match scenario {
+        WorkScenario::Workday => [
+            ("focus", dimensions.focus),
+            ("debugging", dimensions.debugging),
+            ("collaboration", dimensions.collaboration),
+            ("creativity", dimensions.creativity),
+            ("risk_tolerance", dimensions.risk_tolerance),
+        ],
+        WorkScenario::Weekend => [
+            ("creativity", dimensions.creativity),
+            ("risk_tolerance", dimensions.risk_tolerance),
+            ("focus", dimensions.focus),
+            ("debugging", dimensions.debugging),
+            ("collaboration", dimensions.collaboration),
+        ],
+    };
+
+    let mut weighted_sum = 0.0;
+    for (name, value) in dimension_order {
+        match pack.weight(scenario, name) {
+            Some(weight) => weighted_sum += value as f32 * weight,
+            None => return calculate_default_weighted_score(dimensions, scenario),
+        }
+    }
+    weighted_sum.round().clamp(0.0, 100.0) as u8
+}
+
+fn calculate_default_weighted_score(dimensions: &TechDimensions, scenario: &WorkScenario) -> u8 {
     match scenario {
         WorkScenario::Workday => {
             let weighted_sum = (dimensions.focus as f32 * 0.35)
@@ -147,170 +353,50 @@ fn calculate_scenario_weighted_score(dimensions: &TechDimensions, scenario: &Wor
 fn generate_fortune_content(
     dimensions: &TechDimensions,
     scenario: &WorkScenario,
+    score: u8,
     rng: &mut ChaCha20Rng,
+    pack: &FortunePack,
+    pressure: Option<&DevPressure>,
     lang: Language,
-) -> (String, String, String, String) {
-    let primary_dim = if dimensions.focus >= dimensions.creativity
-        && dimensions.focus >= dimensions.debugging
-        && dimensions.focus >= dimensions.collaboration
-        && dimensions.focus >= dimensions.risk_tolerance
-    {
-        "focus"
-    } else if dimensions.creativity >= dimensions.debugging
-        && dimensions.creativity >= dimensions.collaboration
-        && dimensions.creativity >= dimensions.risk_tolerance
-    {
-        "creativity"
-    } else if dimensions.debugging >= dimensions.collaboration
-        && dimensions.debugging >= dimensions.risk_tolerance
-    {
-        "debugging"
-    } else if dimensions.collaboration >= dimensions.risk_tolerance {
-        "collaboration"
-    } else {
-        "risk_tolerance"
+) -> Result<(String, String, String, String), LocalizedError> {
+    let primary = dimensions.primary_dimension();
+    let facts = FortuneFacts {
+        dimensions,
+        scenario,
+        score,
+        primary,
     };
 
-    let message = generate_message(dimensions, primary_dim, scenario, lang);
-    let advice = generate_advice(dimensions, primary_dim, scenario, lang);
-    let color = generate_color(dimensions, scenario, rng, lang);
-    let time = generate_time(dimensions, scenario, rng, lang);
-
-    (message, advice, color, time)
-}
-
-fn generate_message(
-    dimensions: &TechDimensions,
-    primary_dim: &str,
-    scenario: &WorkScenario,
-    lang: Language,
-) -> String {
-    let score = calculate_scenario_weighted_score(dimensions, scenario);
-
-    match score {
-        90..=100 => match primary_dim {
-            "focus" => i18n("fortune.excellent.focus", lang),
-            "creativity" => i18n("fortune.excellent.creativity", lang),
-            "debugging" => i18n("fortune.excellent.debugging", lang),
-            "collaboration" => i18n("fortune.excellent.collaboration", lang),
-            "risk_tolerance" => i18n("fortune.excellent.risk", lang),
-            _ => i18n("fortune.excellent", lang),
-        },
-        80..=89 => match primary_dim {
-            "focus" => i18n("fortune.great.focus", lang),
-            "creativity" => i18n("fortune.great.creativity", lang),
-            "debugging" => i18n("fortune.great.debugging", lang),
-            "collaboration" => i18n("fortune.great.collaboration", lang),
-            "risk_tolerance" => i18n("fortune.great.risk", lang),
-            _ => i18n("fortune.great", lang),
-        },
-        70..=79 => i18n("fortune.good", lang),
-        60..=69 => i18n("fortune.average", lang),
-        50..=59 => i18n("fortune.careful", lang),
-        40..=49 => i18n("fortune.poor", lang),
-        30..=39 => i18n("fortune.challenging", lang),
-        _ => i18n("fortune.unknown", lang),
-    }
-}
-
-fn generate_advice(
-    dimensions: &TechDimensions,
-    primary_dim: &str,
-    scenario: &WorkScenario,
-    lang: Language,
-) -> String {
-    let score = calculate_scenario_weighted_score(dimensions, scenario);
-
-    match score {
-        90..=100 => match primary_dim {
-            "focus" => i18n("advice.excellent.focus", lang),
-            "creativity" => i18n("advice.excellent.creativity", lang),
-            "debugging" => i18n("advice.excellent.debugging", lang),
-            "collaboration" => i18n("advice.excellent.collaboration", lang),
-            "risk_tolerance" => i18n("advice.excellent.risk", lang),
-            _ => i18n("advice.excellent", lang),
-        },
-        80..=89 => match primary_dim {
-            "focus" => i18n("advice.great.focus", lang),
-            "creativity" => i18n("advice.great.creativity", lang),
-            "debugging" => i18n("advice.great.debugging", lang),
-            "collaboration" => i18n("advice.great.collaboration", lang),
-            "risk_tolerance" => i18n("advice.great.risk", lang),
-            _ => i18n("advice.great", lang),
-        },
-        70..=79 => i18n("advice.good", lang),
-        60..=69 => i18n("advice.average", lang),
-        50..=59 => i18n("advice.careful", lang),
-        40..=49 => i18n("advice.poor", lang),
-        30..=39 => i18n("advice.challenging", lang),
-        _ => i18n("advice.unknown", lang),
-    }
-}
+    let builtin = FortunePack::builtin(lang);
+    let select = |field: TemplateField| {
+        pack.select(field, &facts)
+            .or_else(|| builtin.select(field, &facts))
+            .unwrap_or_default()
+    };
 
-fn generate_color(
-    dimensions: &TechDimensions,
-    scenario: &WorkScenario,
-    rng: &mut ChaCha20Rng,
-    lang: Language,
-) -> String {
-    let color_keys = match scenario {
-        WorkScenario::Workday => {
-            if dimensions.focus > 70 {
-                vec!["color.blue", "color.cyan", "color.green"]
-            } else if dimensions.debugging > 70 {
-                vec!["color.blue", "color.green", "color.cyan"]
-            } else {
-                vec!["color.blue", "color.green", "color.cyan", "color.yellow"]
-            }
-        }
-        WorkScenario::Weekend => {
-            if dimensions.creativity > 70 {
-                vec!["color.purple", "color.pink", "color.orange"]
-            } else if dimensions.risk_tolerance > 70 {
-                vec!["color.red", "color.orange", "color.pink"]
-            } else {
-                vec!["color.purple", "color.pink", "color.orange", "color.red"]
-            }
-        }
+    let context = TemplateContext {
+        user_name: detect_user_name(),
+        scenario: scenario_name(scenario),
+        overall_score: score,
+        primary,
+        dimensions,
+        pressure: pressure.map(PressureContext::from),
+    };
+    let render = |field: TemplateField| {
+        let (key, source) = select(field);
+        render_template(key, field, source, &context)
     };
 
-    let selected_key = color_keys[rng.gen_range(0..color_keys.len())];
-    i18n(selected_key, lang)
-}
+    let message = render(TemplateField::Message)?;
+    let advice = render(TemplateField::Advice)?;
+    let color = pick_choice(select(TemplateField::Color).1, rng);
+    let time = pick_choice(select(TemplateField::Time).1, rng);
 
-fn generate_time(
-    dimensions: &TechDimensions,
-    scenario: &WorkScenario,
-    rng: &mut ChaCha20Rng,
-    lang: Language,
-) -> String {
-    let time_keys = match scenario {
-        WorkScenario::Workday => {
-            if dimensions.focus > 80 {
-                vec!["time.morning_9_11", "time.afternoon_2_4"]
-            } else if dimensions.collaboration > 70 {
-                vec!["time.afternoon_2_4", "time.afternoon_5_6"]
-            } else if dimensions.debugging > 70 {
-                vec!["time.morning_9_11", "time.afternoon_2_4"]
-            } else {
-                vec![
-                    "time.morning_9_11",
-                    "time.afternoon_2_4",
-                    "time.afternoon_5_6",
-                ]
-            }
-        }
-        WorkScenario::Weekend => {
-            if dimensions.creativity > 70 {
-                vec!["time.evening_7_9", "time.night_1_3"]
-            } else if dimensions.risk_tolerance > 70 {
-                vec!["time.evening_10_11", "time.night_1_3"]
-            } else {
-                vec!["time.evening_7_9", "time.evening_10_11", "time.night_1_3"]
-            }
-        }
-    };
+    Ok((message, advice, color, time))
+}
 
-    let selected_key = time_keys[rng.gen_range(0..time_keys.len())];
-    i18n(selected_key, lang)
+/// Colors and times may list `|`-separated alternatives; one is drawn per day.
+fn pick_choice(choices: &str, rng: &mut ChaCha20Rng) -> String {
+    let options: Vec<&str> = choices.split('|').map(str::trim).collect();
+    options[rng.gen_range(0..options.len())].to_string()
 }
@@ -1,190 +1,531 @@
-use crate::utils::i18n::i18n;
-use crate::utils::Language;
+use crate::models::build_timings::BuildTimings;
+use crate::models::git_repo::{
+    head_commit_id, inspect_repository, measure_git_churn, GitChurn, RepoSignals,
+};
+use crate::models::pressure_cache::PressureCache;
+use crate::models::pressure_error::PressureError;
+use crate::models::pressure_score::{ScoreContribution, ScoreInputs, ScoreMetric, ScoringModel};
+use crate::models::probe_command::{ProbeControl, DEFAULT_PROBE_TIMEOUT_SECONDS};
+use crate::models::probes::{
+    select_probes, CustomProbe, ProbeResult, BUILD_FAILED, BUILD_SECONDS, TESTS_BROKEN,
+    TESTS_FAILED, TESTS_PASSED,
+};
+use crate::models::test_results::TestRunSummary;
+use crate::utils::config::UserConfig;
+use crate::utils::i18n::{i18n, i18n_args};
+use crate::utils::{Language, LocalizedError};
 use serde::{Deserialize, Serialize};
-use std::process::Command;
-use std::time::{SystemTime, UNIX_EPOCH};
+use std::fmt;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
 
-#[derive(Debug, Serialize, Deserialize)]
+/// Hours of commit history counted as recent churn.
+pub const DEFAULT_CHURN_WINDOW_HOURS: u32 = 24;
+/// Paths left out of churn unless `pressure.exclude` replaces the list.
+pub const DEFAULT_CHURN_EXCLUDES: &[&str] = &[
+    "vendor/**",
+    "**/vendor/**",
+    "node_modules/**",
+    "**/node_modules/**",
+    "target/**",
+    "dist/**",
+    "build/**",
+    "*.lock",
+    "**/*.lock",
+    "*.min.js",
+    "**/*.min.js",
+    "package-lock.json",
+    "**/package-lock.json",
+];
+
+/// How long cached test and build results stay valid for an unchanged working tree.
+pub const DEFAULT_CACHE_TTL_MINUTES: u32 = 24 * 60;
+
+/// One kind of measurement behind the pressure index; see [`crate::models::probes`] for the tools behind it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ProbeKind {
+    /// Churn and repository state; cheap, runs in-process.
+    Git,
+    /// The project's test runner; slow.
+    Tests,
+    /// The project's build; slow and writes build output.
+    Build,
+}
+
+impl ProbeKind {
+    pub const ALL: [ProbeKind; 3] = [ProbeKind::Git, ProbeKind::Tests, ProbeKind::Build];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            ProbeKind::Git => "git",
+            ProbeKind::Tests => "tests",
+            ProbeKind::Build => "build",
+        }
+    }
+}
+
+impl FromStr for ProbeKind {
+    type Err = LocalizedError;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        ProbeKind::ALL
+            .into_iter()
+            .find(|probe| probe.name() == value.trim().to_lowercase())
+            .ok_or_else(|| LocalizedError::new("probe.error.unknown").with("probe", value))
+    }
+}
+
+impl fmt::Display for ProbeKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+/// Settings for [`analyze_dev_pressure_with_options`], read from the `[pressure]` config table.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PressureOptions {
+    pub window_hours: u32,
+    /// Glob patterns, matched against repository-relative paths.
+    pub exclude: Vec<String>,
+    /// Only the cheap git probe runs unless tests or build are asked for.
+    pub probes: Vec<ProbeKind>,
+    /// Where test and build results are cached; `None` disables caching.
+    pub cache_dir: Option<PathBuf>,
+    pub cache_ttl_minutes: u32,
+    /// Commands from `[[pressure.custom_probes]]`; they replace the detected tools of their kind.
+    pub custom_probes: Vec<CustomProbe>,
+    /// Where build durations are recorded; `None` keeps no history.
+    pub history_dir: Option<PathBuf>,
+    /// Ignore cached results and measure again.
+    pub refresh: bool,
+    /// Paths or globs analyzed by `findme pressure` when `--repos` is not given.
+    pub repos: Vec<String>,
+    /// Seconds each test or build tool may run; 0 waits forever.
+    pub timeout_seconds: u64,
+    /// Cancels the probes of a run and tells a progress display which one is running.
+    pub control: ProbeControl,
+}
+
+impl Default for PressureOptions {
+    fn default() -> Self {
+        PressureOptions {
+            window_hours: DEFAULT_CHURN_WINDOW_HOURS,
+            exclude: DEFAULT_CHURN_EXCLUDES
+                .iter()
+                .map(|pattern| pattern.to_string())
+                .collect(),
+            probes: vec![ProbeKind::Git],
+            cache_dir: None,
+            cache_ttl_minutes: DEFAULT_CACHE_TTL_MINUTES,
+            custom_probes: Vec::new(),
+            history_dir: None,
+            refresh: false,
+            repos: Vec::new(),
+            timeout_seconds: DEFAULT_PROBE_TIMEOUT_SECONDS,
+            control: ProbeControl::default(),
+        }
+    }
+}
+
+impl PressureOptions {
+    /// The `[pressure]` keys from the user config, else the defaults.
+    pub fn from_config(config: &UserConfig) -> PressureOptions {
+        let defaults = PressureOptions::default();
+        PressureOptions {
+            probes: config
+                .get("pressure.probes")
+                .and_then(|value| value.as_array())
+                .map(|probes| {
+                    probes
+                        .iter()
+                        .filter_map(|probe| probe.as_str()?.parse().ok())
+                        .collect()
+                })
+                .unwrap_or(defaults.probes),
+            custom_probes: config
+                .get("pressure.custom_probes")
+                .and_then(|value| value.as_array())
+                .map(|probes| {
+                    probes
+                        .iter()
+                        .filter_map(|probe| probe.clone().try_into().ok())
+                        .collect()
+                })
+                .unwrap_or(defaults.custom_probes),
+            cache_ttl_minutes: config
+                .get("pressure.cache_ttl_minutes")
+                .and_then(|value| value.as_integer())
+                .and_then(|minutes| u32::try_from(minutes).ok())
+                .unwrap_or(defaults.cache_ttl_minutes),
+            window_hours: config
+                .get("pressure.window_hours")
+                .and_then(|value| value.as_integer())
+                .and_then(|hours| u32::try_from(hours).ok())
+                .unwrap_or(defaults.window_hours),
+            exclude: config
+                .get("pressure.exclude")
+                .and_then(|value| value.as_array())
+                .map(|patterns| {
+                    patterns
+                        .iter()
+                        .filter_map(|pattern| pattern.as_str().map(str::to_string))
+                        .collect()
+                })
+                .unwrap_or(defaults.exclude),
+            repos: config
+                .get("pressure.repos")
+                .and_then(|value| value.as_array())
+                .map(|repos| {
+                    repos
+                        .iter()
+                        .filter_map(|repo| repo.as_str().map(str::to_string))
+                        .collect()
+                })
+                .unwrap_or(defaults.repos),
+            timeout_seconds: config
+                .get("pressure.timeout_seconds")
+                .and_then(|value| value.as_integer())
+                .and_then(|seconds| u64::try_from(seconds).ok())
+                .unwrap_or(defaults.timeout_seconds),
+            ..defaults
+        }
+    }
+
+    pub fn runs(&self, probe: ProbeKind) -> bool {
+        self.probes.contains(&probe)
+    }
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
 pub struct DevPressure {
+    /// Directory the probes ran in.
+    pub root: PathBuf,
+    /// HEAD at the time of the run; `None` outside a git repository or before the first commit.
+    pub commit: Option<String>,
+    /// `git_churn.added + git_churn.deleted`.
     pub git_diff_lines: i32,
+    pub git_churn: GitChurn,
+    /// Branch, upstream and working-tree state; `None` outside a git repository.
+    pub repo: Option<RepoSignals>,
     pub test_success_rate: f32,
     pub has_tests: bool,
+    /// Per-binary test results of every test probe; `None` when no test runner was found.
+    pub tests: Option<TestRunSummary>,
+    /// Duration of the slowest build, rounded.
     pub build_time_seconds: u32,
+    /// Latest and median cargo builds; `None` outside a cargo project or before a real build.
+    pub builds: Option<BuildTimings>,
+    /// Metrics of every test and build probe that ran.
+    pub results: Vec<ProbeResult>,
+    /// Probes that ran or were answered from the cache.
+    pub probes: Vec<ProbeKind>,
+    /// Probes whose results came from the cache.
+    pub cached_probes: Vec<ProbeKind>,
     pub pressure_level: PressureLevel,
+    /// 0–100, from the scoring model.
+    pub pressure_score: u32,
+    /// Points each measured metric added to `pressure_score`.
+    pub score_breakdown: Vec<ScoreContribution>,
+    pub scoring_model: ScoringModel,
+    /// The `.findme.toml` the scoring model came from; `None` for the defaults.
+    pub scoring_file: Option<PathBuf>,
     pub risk_threshold: String,
     pub patience_advice: String,
+    /// Why probes failed; their metrics are left out of the score.
+    #[serde(default)]
+    pub errors: Vec<PressureError>,
+    /// Probes with at least one failed tool.
+    #[serde(default)]
+    pub failed_probes: Vec<ProbeKind>,
+    /// The part of `failed_probes` where a tool ran past `pressure.timeout_seconds`.
+    #[serde(default)]
+    pub timed_out_probes: Vec<ProbeKind>,
 }
 
-#[derive(Debug, Serialize, Deserialize, PartialEq)]
+/// Written in snake_case; the capitalized names of older caches and histories are still read.
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "snake_case")]
 pub enum PressureLevel {
+    #[default]
+    #[serde(alias = "Low")]
     Low,
+    #[serde(alias = "Medium")]
     Medium,
+    #[serde(alias = "High")]
     High,
+    #[serde(alias = "Critical")]
     Critical,
 }
 
-pub fn analyze_dev_pressure(lang: Language) -> Result<DevPressure, String> {
-    let git_diff_lines = get_git_diff_lines()?;
-    let (test_success_rate, has_tests) = get_test_success_rate()?;
-    let build_time_seconds = get_build_time()?;
-
-    let pressure_level =
-        calculate_pressure_level(git_diff_lines, test_success_rate, build_time_seconds);
-    let (risk_threshold, patience_advice) = generate_pressure_advice(
-        &pressure_level,
-        git_diff_lines,
-        test_success_rate,
-        build_time_seconds,
-        lang,
-    );
+pub fn analyze_dev_pressure(lang: Language) -> Result<DevPressure, PressureError> {
+    analyze_dev_pressure_with_options(&PressureOptions::default(), lang)
+}
 
-    Ok(DevPressure {
-        git_diff_lines,
-        test_success_rate,
-        has_tests,
-        build_time_seconds,
-        pressure_level,
-        risk_threshold,
-        patience_advice,
-    })
+pub fn analyze_dev_pressure_with_options(
+    options: &PressureOptions,
+    lang: Language,
+) -> Result<DevPressure, PressureError> {
+    analyze_dev_pressure_in(Path::new("."), options, lang)
 }
 
-fn get_git_diff_lines() -> Result<i32, String> {
-    let since = (SystemTime::now()
-        .duration_since(UNIX_EPOCH)
-        .unwrap()
-        .as_secs()
-        - 86400)
-        .to_string();
-
-    let output = Command::new("git")
-        .args(["log", "--since", &since, "--oneline", "--name-only"])
-        .output()
-        .map_err(|_| "Git not available or not in a git repository".to_string())?;
-
-    if !output.status.success() {
-        return Ok(0);
+/// Runs the selected probes in `dir` instead of the working directory.
+///
+/// A probe that fails is listed in [`DevPressure::errors`] and left out of the score;
+/// only a missing directory or invalid scoring settings fail the whole analysis.
+pub fn analyze_dev_pressure_in(
+    dir: &Path,
+    options: &PressureOptions,
+    lang: Language,
+) -> Result<DevPressure, PressureError> {
+    if !dir.is_dir() {
+        return Err(PressureError::NotADirectory {
+            path: dir.to_path_buf(),
+        });
     }
+    let dir = &fs::canonicalize(dir).unwrap_or_else(|_| dir.to_path_buf());
+    let mut errors = Vec::new();
+    let mut failed_probes = Vec::new();
+    let mut timed_out_probes = Vec::new();
 
-    let output_str = String::from_utf8_lossy(&output.stdout);
-    let file_count = output_str
-        .lines()
-        .filter(|line| !line.is_empty() && !line.starts_with("commit"))
-        .count();
-
-    Ok((file_count * 15) as i32)
-}
-
-fn get_test_success_rate() -> Result<(f32, bool), String> {
-    let output = Command::new("cargo")
-        .args(["test", "--quiet"])
-        .output()
-        .map_err(|_| "Cargo not available".to_string())?;
+    let empty_churn = GitChurn {
+        window_hours: options.window_hours,
+        ..GitChurn::default()
+    };
+    let (git_churn, repo) = if options.runs(ProbeKind::Git) {
+        let _running = options.control.start(ProbeKind::Git.name());
+        let measured =
+            inspect_repository(dir).and_then(|repo| Ok((measure_git_churn(dir, options)?, repo)));
+        match measured {
+            Ok((churn, Some(repo))) => (churn, Some(repo)),
+            Ok((_, None)) => {
+                errors.push(PressureError::NotARepository { path: dir.clone() });
+                failed_probes.push(ProbeKind::Git);
+                (empty_churn, None)
+            }
+            Err(e) => {
+                errors.push(e);
+                failed_probes.push(ProbeKind::Git);
+                (empty_churn, None)
+            }
+        }
+    } else {
+        (empty_churn, None)
+    };
+    let git_diff_lines = git_churn.total_lines() as i32;
 
-    let output_str = String::from_utf8_lossy(&output.stdout);
-    let stderr_str = String::from_utf8_lossy(&output.stderr);
+    let mut cache = match &options.cache_dir {
+        Some(cache_dir) if options.runs(ProbeKind::Tests) || options.runs(ProbeKind::Build) => {
+            // Without a cache the probes simply run again.
+            match PressureCache::open(cache_dir, dir, options.cache_ttl_minutes) {
+                Ok(cache) => Some(cache),
+                Err(e) => {
+                    errors.push(e);
+                    None
+                }
+            }
+        }
+        _ => None,
+    };
+    let mut cached_probes = Vec::new();
+    let mut results = Vec::new();
 
-    let total_tests = extract_test_count(&output_str, "test result: ");
-    let failed_tests = extract_test_count(&stderr_str, "test result: FAILED");
+    for kind in [ProbeKind::Tests, ProbeKind::Build] {
+        if !options.runs(kind) {
+            continue;
+        }
+        let probes = select_probes(dir, kind, &options.custom_probes);
+        let names: Vec<&str> = probes.iter().map(|probe| probe.name()).collect();
 
-    if total_tests == 0 {
-        return Ok((0.0, false));
+        // Results cached for a different set of tools are stale.
+        let cached = cache
+            .as_ref()
+            .filter(|_| !options.refresh)
+            .and_then(|cache| cache.results(kind))
+            .filter(|cached| {
+                cached
+                    .iter()
+                    .map(|result| result.probe.as_str())
+                    .eq(names.iter().copied())
+            });
+        match cached {
+            Some(cached) => {
+                cached_probes.push(kind);
+                results.extend(cached);
+            }
+            None => {
+                let mut measured = Vec::new();
+                for probe in &probes {
+                    let _running = options.control.start(&format!("{} {}", probe.name(), kind));
+                    match probe.run(dir, options) {
+                        Ok(result) => measured.push(result),
+                        Err(e) => {
+                            if matches!(e, PressureError::Timeout { .. })
+                                && !timed_out_probes.contains(&kind)
+                            {
+                                timed_out_probes.push(kind);
+                            }
+                            errors.push(e);
+                        }
+                    }
+                }
+                // Partial results are not cached, so the failed probe runs again next time.
+                if measured.len() == probes.len() {
+                    if let Some(cache) = cache.as_mut() {
+                        cache.set_results(kind, &measured);
+                    }
+                } else {
+                    failed_probes.push(kind);
+                }
+                results.extend(measured);
+            }
+        }
     }
 
-    let success_rate = (total_tests - failed_tests) as f32 / total_tests as f32;
-    Ok((success_rate.clamp(0.0, 1.0), true))
-}
+    if let Some(cache) = cache.as_mut() {
+        // A cache that cannot be written only costs the next run some time.
+        let _ = cache.save();
+    }
 
-fn get_build_time() -> Result<u32, String> {
-    let start = SystemTime::now();
+    let tests = merge_tests(&results);
+    let (test_success_rate, has_tests) = match tests.as_ref().and_then(TestRunSummary::success_rate)
+    {
+        Some(rate) => (rate.clamp(0.0, 1.0), true),
+        None => (0.0, false),
+    };
+    let builds = results.iter().find_map(|result| result.builds.clone());
+    let build_time_seconds = results
+        .iter()
+        .filter_map(|result| result.metric(BUILD_SECONDS))
+        .fold(0.0, f64::max)
+        .round() as u32;
 
-    let _output = Command::new("cargo")
-        .args(["build", "--quiet"])
-        .output()
-        .map_err(|_| "Cargo build failed".to_string())?;
+    let (model, scoring_file) = ScoringModel::load(dir)?;
+    let churn_measured = options.runs(ProbeKind::Git) && !failed_probes.contains(&ProbeKind::Git);
+    let score = model.score(&score_inputs(
+        churn_measured.then_some(git_diff_lines),
+        &results,
+    ));
+    let (risk_threshold, patience_advice) =
+        generate_pressure_advice(&score.level, score.top_contribution(), lang);
+    // A broken repository only costs the commit; the git probe may have reported it already.
+    let commit = head_commit_id(dir).unwrap_or_else(|e| {
+        if !errors.contains(&e) {
+            errors.push(e);
+        }
+        None
+    });
 
-    let duration = start.elapsed().unwrap_or_default();
-    Ok(duration.as_secs() as u32)
+    Ok(DevPressure {
+        root: dir.clone(),
+        commit,
+        git_diff_lines,
+        git_churn,
+        repo,
+        test_success_rate,
+        has_tests,
+        tests,
+        build_time_seconds,
+        builds,
+        results,
+        probes: ProbeKind::ALL
+            .into_iter()
+            .filter(|probe| options.runs(*probe))
+            .collect(),
+        cached_probes,
+        pressure_level: score.level,
+        pressure_score: score.score,
+        score_breakdown: score.contributions,
+        scoring_model: model,
+        scoring_file,
+        risk_threshold,
+        patience_advice,
+        errors,
+        failed_probes,
+        timed_out_probes,
+    })
 }
 
-fn extract_test_count(text: &str, pattern: &str) -> u32 {
-    if let Some(line) = text.lines().find(|line| line.contains(pattern)) {
-        for word in line.split_whitespace() {
-            if let Ok(num) = word.parse::<u32>() {
-                return num;
-            }
-        }
+/// Every test summary in `results` combined; `None` when no test probe ran.
+fn merge_tests(results: &[ProbeResult]) -> Option<TestRunSummary> {
+    let mut summaries = results.iter().filter_map(|result| result.tests.clone());
+    let mut merged = summaries.next()?;
+    for summary in summaries {
+        merged.binaries.extend(summary.binaries);
+        merged.compile_errors.extend(summary.compile_errors);
     }
-    0
+    Some(merged)
 }
 
-fn calculate_pressure_level(
-    git_diff_lines: i32,
-    test_success_rate: f32,
-    build_time_seconds: u32,
-) -> PressureLevel {
-    let mut pressure_score = 0.0;
-
-    if git_diff_lines > 200 {
-        pressure_score += 40.0;
-    } else if git_diff_lines > 100 {
-        pressure_score += 30.0;
-    } else if git_diff_lines > 50 {
-        pressure_score += 20.0;
-    } else if git_diff_lines > 20 {
-        pressure_score += 10.0;
-    }
+/// Metric values for the scoring model, summed over every probe that reported them.
+fn score_inputs(git_diff_lines: Option<i32>, results: &[ProbeResult]) -> ScoreInputs {
+    let sum = |name: &str| -> Option<f64> {
+        results
+            .iter()
+            .filter_map(|result| result.metric(name))
+            .reduce(|total, value| total + value)
+    };
+    let broken = sum(TESTS_BROKEN).is_some_and(|broken| broken > 0.0);
+    let passed = sum(TESTS_PASSED).unwrap_or(0.0);
+    let failed = sum(TESTS_FAILED).unwrap_or(0.0);
 
-    if test_success_rate < 0.5 {
-        pressure_score += 30.0;
-    } else if test_success_rate < 0.7 {
-        pressure_score += 20.0;
-    } else if test_success_rate < 0.9 {
-        pressure_score += 10.0;
-    }
-
-    if build_time_seconds > 60 {
-        pressure_score += 30.0;
-    } else if build_time_seconds > 30 {
-        pressure_score += 20.0;
-    } else if build_time_seconds > 15 {
-        pressure_score += 10.0;
-    }
-
-    if pressure_score >= 80.0 {
-        PressureLevel::Critical
-    } else if pressure_score >= 60.0 {
-        PressureLevel::High
-    } else if pressure_score >= 30.0 {
-        PressureLevel::Medium
-    } else {
-        PressureLevel::Low
+    ScoreInputs {
+        churn_lines: git_diff_lines.map(f64::from),
+        test_pass_rate: if broken {
+            Some(0.0)
+        } else if passed + failed > 0.0 {
+            Some(passed / (passed + failed))
+        } else {
+            None
+        },
+        build_seconds: results
+            .iter()
+            .filter_map(|result| result.metric(BUILD_SECONDS))
+            .reduce(f64::max),
+        build_failed: sum(BUILD_FAILED).is_some_and(|failed| failed > 0.0),
     }
 }
 
+/// Risk follows the level; the advice points at the metric that added the most points.
 fn generate_pressure_advice(
     pressure_level: &PressureLevel,
-    _git_diff_lines: i32,
-    _test_success_rate: f32,
-    _build_time_seconds: u32,
+    top: Option<&ScoreContribution>,
     lang: Language,
 ) -> (String, String) {
-    match pressure_level {
-        PressureLevel::Low => (
-            i18n("pressure.advice.low.risk", lang),
-            i18n("pressure.advice.low.patience", lang),
-        ),
-        PressureLevel::Medium => (
-            i18n("pressure.advice.medium.risk", lang),
-            i18n("pressure.advice.medium.patience", lang),
-        ),
-        PressureLevel::High => (
-            i18n("pressure.advice.high.risk", lang),
-            i18n("pressure.advice.high.patience", lang),
-        ),
-        PressureLevel::Critical => (
-            i18n("pressure.advice.critical.risk", lang),
-            i18n("pressure.advice.critical.patience", lang),
-        ),
-    }
+    let level = match pressure_level {
+        PressureLevel::Low => "low",
+        PressureLevel::Medium => "medium",
+        PressureLevel::High => "high",
+        PressureLevel::Critical => "critical",
+    };
+    let risk = i18n(&format!("pressure.advice.{}.risk", level), lang);
+
+    let patience = match top {
+        Some(top) if top.metric == ScoreMetric::Build && top.failed => {
+            i18n("pressure.advice.focus.build_failed", lang)
+        }
+        Some(top) => {
+            let (key, name, value) = match top.metric {
+                ScoreMetric::Churn => (
+                    "pressure.advice.focus.churn",
+                    "count",
+                    format!("{}", top.value),
+                ),
+                ScoreMetric::Tests => (
+                    "pressure.advice.focus.tests",
+                    "rate",
+                    format!("{:.0}%", top.value * 100.0),
+                ),
+                ScoreMetric::Build => (
+                    "pressure.advice.focus.build",
+                    "duration",
+                    format!("{:.0}s", top.value),
+                ),
+            };
+            i18n_args(key, lang, &[(name, &value)])
+        }
+        None => i18n(&format!("pressure.advice.{}.patience", level), lang),
+    };
+    (risk, patience)
 }
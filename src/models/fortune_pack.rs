@@ -0,0 +1,721 @@
+use crate::models::daily_fortune::{TechDimensions, WorkScenario};
+use crate::models::dev_pressure::{DevPressure, PressureLevel};
+use crate::utils::{i18n, Language, LocalizedError};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
+use tinytemplate::TinyTemplate;
+
+/// Name under which the built-in strings are exposed as a pack.
+pub const DEFAULT_PACK_NAME: &str = "default";
+
+pub const DIMENSION_NAMES: [&str; 5] = [
+    "focus",
+    "creativity",
+    "debugging",
+    "collaboration",
+    "risk_tolerance",
+];
+
+const SCENARIO_NAMES: [&str; 2] = ["workday", "weekend"];
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct FortuneTemplate {
+    #[serde(default)]
+    pub condition: String,
+    #[serde(default)]
+    pub message: String,
+    #[serde(default)]
+    pub advice: String,
+    #[serde(default)]
+    pub color: String,
+    #[serde(default)]
+    pub time: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct FortunePack {
+    pub name: String,
+    pub version: String,
+    pub templates: HashMap<String, FortuneTemplate>,
+    #[serde(default)]
+    pub dimension_weights: HashMap<String, f32>,
+    /// Template conditions by key, parsed by [`FortunePack::validate`] or the first
+    /// [`FortunePack::select`].
+    #[serde(skip)]
+    conditions: OnceLock<HashMap<String, Condition>>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TemplateField {
+    Message,
+    Advice,
+    Color,
+    Time,
+}
+
+/// Values available to `message` and `advice` templates, e.g. `{dimensions.focus}`.
+#[derive(Debug, Serialize)]
+pub struct TemplateContext<'a> {
+    pub user_name: String,
+    pub scenario: &'static str,
+    pub overall_score: u8,
+    pub primary: &'static str,
+    pub dimensions: &'a TechDimensions,
+    pub pressure: Option<PressureContext>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct PressureContext {
+    pub level: &'static str,
+    pub score: u32,
+    pub git_diff_lines: i32,
+    pub test_success_rate: u8,
+    pub has_tests: bool,
+    pub build_time_seconds: u32,
+}
+
+impl From<&DevPressure> for PressureContext {
+    fn from(pressure: &DevPressure) -> Self {
+        PressureContext {
+            level: match pressure.pressure_level {
+                PressureLevel::Low => "low",
+                PressureLevel::Medium => "medium",
+                PressureLevel::High => "high",
+                PressureLevel::Critical => "critical",
+            },
+            score: pressure.pressure_score,
+            git_diff_lines: pressure.git_diff_lines,
+            test_success_rate: (pressure.test_success_rate * 100.0).round() as u8,
+            has_tests: pressure.has_tests,
+            build_time_seconds: pressure.build_time_seconds,
+        }
+    }
+}
+
+/// The values a template condition can look at.
+pub struct FortuneFacts<'a> {
+    pub dimensions: &'a TechDimensions,
+    pub scenario: &'a WorkScenario,
+    pub score: u8,
+    pub primary: &'static str,
+}
+
+impl FortuneTemplate {
+    pub fn field(&self, field: TemplateField) -> &str {
+        match field {
+            TemplateField::Message => &self.message,
+            TemplateField::Advice => &self.advice,
+            TemplateField::Color => &self.color,
+            TemplateField::Time => &self.time,
+        }
+    }
+}
+
+impl FortunePack {
+    /// Builds the default pack from the built-in translations.
+    pub fn builtin(lang: Language) -> FortunePack {
+        let mut templates = HashMap::new();
+
+        let tiers = [
+            ("excellent", "score >= 90"),
+            ("great", "score >= 80 && score < 90"),
+        ];
+        let primaries = [
+            ("focus", "focus"),
+            ("creativity", "creativity"),
+            ("debugging", "debugging"),
+            ("collaboration", "collaboration"),
+            ("risk", "risk_tolerance"),
+        ];
+        for (tier, tier_condition) in tiers {
+            for (suffix, primary) in primaries {
+                templates.insert(
+                    format!("{}.{}", tier, suffix),
+                    FortuneTemplate {
+                        condition: format!("{} && primary == {}", tier_condition, primary),
+                        message: escape_template(&i18n(
+                            &format!("fortune.{}.{}", tier, suffix),
+                            lang,
+                        )),
+                        advice: escape_template(&i18n(
+                            &format!("advice.{}.{}", tier, suffix),
+                            lang,
+                        )),
+                        color: String::new(),
+                        time: String::new(),
+                    },
+                );
+            }
+        }
+
+        let plain_tiers = [
+            ("good", "score >= 70 && score < 80"),
+            ("average", "score >= 60 && score < 70"),
+            ("careful", "score >= 50 && score < 60"),
+            ("poor", "score >= 40 && score < 50"),
+            ("challenging", "score >= 30 && score < 40"),
+            ("unknown", "score < 30"),
+        ];
+        for (tier, condition) in plain_tiers {
+            templates.insert(
+                tier.to_string(),
+                FortuneTemplate {
+                    condition: condition.to_string(),
+                    message: escape_template(&i18n(&format!("fortune.{}", tier), lang)),
+                    advice: escape_template(&i18n(&format!("advice.{}", tier), lang)),
+                    color: String::new(),
+                    time: String::new(),
+                },
+            );
+        }
+
+        let choices = |keys: &[&str]| {
+            keys.iter()
+                .map(|key| i18n(key, lang))
+                .collect::<Vec<_>>()
+                .join("|")
+        };
+
+        let colors = [
+            (
+                "color.workday.focus",
+                "scenario == workday && focus > 70",
+                vec!["color.blue", "color.cyan", "color.green"],
+            ),
+            (
+                "color.workday.debugging",
+                "scenario == workday && focus <= 70 && debugging > 70",
+                vec!["color.blue", "color.green", "color.cyan"],
+            ),
+            (
+                "color.workday",
+                "scenario == workday",
+                vec!["color.blue", "color.green", "color.cyan", "color.yellow"],
+            ),
+            (
+                "color.weekend.creativity",
+                "scenario == weekend && creativity > 70",
+                vec!["color.purple", "color.pink", "color.orange"],
+            ),
+            (
+                "color.weekend.risk",
+                "scenario == weekend && creativity <= 70 && risk_tolerance > 70",
+                vec!["color.red", "color.orange", "color.pink"],
+            ),
+            (
+                "color.weekend",
+                "scenario == weekend",
+                vec!["color.purple", "color.pink", "color.orange", "color.red"],
+            ),
+        ];
+        for (key, condition, keys) in colors {
+            templates.insert(
+                key.to_string(),
+                FortuneTemplate {
+                    condition: condition.to_string(),
+                    message: String::new(),
+                    advice: String::new(),
+                    color: choices(&keys),
+                    time: String::new(),
+                },
+            );
+        }
+
+        let times = [
+            (
+                "time.workday.focus",
+                "scenario == workday && focus > 80",
+                vec!["time.morning_9_11", "time.afternoon_2_4"],
+            ),
+            (
+                "time.workday.collaboration",
+                "scenario == workday && focus <= 80 && collaboration > 70",
+                vec!["time.afternoon_2_4", "time.afternoon_5_6"],
+            ),
+            (
+                "time.workday.debugging",
+                "scenario == workday && focus <= 80 && collaboration <= 70 && debugging > 70",
+                vec!["time.morning_9_11", "time.afternoon_2_4"],
+            ),
+            (
+                "time.workday",
+                "scenario == workday",
+                vec![
+                    "time.morning_9_11",
+                    "time.afternoon_2_4",
+                    "time.afternoon_5_6",
+                ],
+            ),
+            (
+                "time.weekend.creativity",
+                "scenario == weekend && creativity > 70",
+                vec!["time.evening_7_9", "time.night_1_3"],
+            ),
+            (
+                "time.weekend.risk",
+                "scenario == weekend && creativity <= 70 && risk_tolerance > 70",
+                vec!["time.evening_10_11", "time.night_1_3"],
+            ),
+            (
+                "time.weekend",
+                "scenario == weekend",
+                vec!["time.evening_7_9", "time.evening_10_11", "time.night_1_3"],
+            ),
+        ];
+        for (key, condition, keys) in times {
+            templates.insert(
+                key.to_string(),
+                FortuneTemplate {
+                    condition: condition.to_string(),
+                    message: String::new(),
+                    advice: String::new(),
+                    color: String::new(),
+                    time: choices(&keys),
+                },
+            );
+        }
+
+        let dimension_weights = [
+            ("workday.focus", 0.35),
+            ("workday.debugging", 0.30),
+            ("workday.collaboration", 0.25),
+            ("workday.creativity", 0.07),
+            ("workday.risk_tolerance", 0.03),
+            ("weekend.creativity", 0.40),
+            ("weekend.risk_tolerance", 0.30),
+            ("weekend.focus", 0.20),
+            ("weekend.debugging", 0.07),
+            ("weekend.collaboration", 0.03),
+        ]
+        .into_iter()
+        .map(|(key, weight)| (key.to_string(), weight))
+        .collect();
+
+        FortunePack {
+            name: DEFAULT_PACK_NAME.to_string(),
+            version: env!("CARGO_PKG_VERSION").to_string(),
+            templates,
+            dimension_weights,
+            conditions: OnceLock::new(),
+        }
+    }
+
+    /// Parses a pack from JSON and validates it.
+    pub fn from_json(content: &str) -> Result<FortunePack, LocalizedError> {
+        let pack: FortunePack = serde_json::from_str(content)
+            .map_err(|e| LocalizedError::new("pack.error.json").with("error", e))?;
+        pack.validate()?;
+        Ok(pack)
+    }
+
+    pub fn load_file(path: &Path) -> Result<FortunePack, LocalizedError> {
+        let content = fs::read_to_string(path).map_err(|e| LocalizedError::read(path, e))?;
+        FortunePack::from_json(&content).map_err(|e| e.in_file(path))
+    }
+
+    /// Checks everything serde cannot: names, conditions, placeholders and weights.
+    pub fn validate(&self) -> Result<(), LocalizedError> {
+        if self.name.trim().is_empty() {
+            return Err(LocalizedError::new("pack.error.empty_name"));
+        }
+        if self.version.trim().is_empty() {
+            return Err(LocalizedError::new("pack.error.empty_version"));
+        }
+        if self.templates.is_empty() {
+            return Err(LocalizedError::new("pack.error.no_templates"));
+        }
+
+        let conditions = self
+            .templates
+            .iter()
+            .map(|(key, template)| {
+                Condition::parse(&template.condition)
+                    .map(|condition| (key.clone(), condition))
+                    .map_err(|e| {
+                        LocalizedError::new("pack.error.condition")
+                            .with("key", key)
+                            .caused_by(e)
+                    })
+            })
+            .collect::<Result<HashMap<_, _>, _>>()?;
+
+        let dimensions = TechDimensions {
+            focus: 50,
+            creativity: 50,
+            debugging: 50,
+            collaboration: 50,
+            risk_tolerance: 50,
+        };
+        // Pressure is present so `{{ if pressure }}` blocks are checked too.
+        let sample = TemplateContext {
+            user_name: String::new(),
+            scenario: "workday",
+            overall_score: 50,
+            primary: "focus",
+            dimensions: &dimensions,
+            pressure: Some(PressureContext {
+                level: "low",
+                score: 0,
+                git_diff_lines: 0,
+                test_success_rate: 100,
+                has_tests: true,
+                build_time_seconds: 0,
+            }),
+        };
+
+        for (key, template) in &self.templates {
+            let fields = [
+                TemplateField::Message,
+                TemplateField::Advice,
+                TemplateField::Color,
+                TemplateField::Time,
+            ];
+            if fields
+                .iter()
+                .all(|field| template.field(*field).trim().is_empty())
+            {
+                return Err(LocalizedError::new("pack.error.empty_template").with("key", key));
+            }
+            for field in [TemplateField::Message, TemplateField::Advice] {
+                render_template(key, field, template.field(field), &sample)?;
+            }
+            for field in [TemplateField::Color, TemplateField::Time] {
+                let value = template.field(field);
+                if !value.is_empty() && value.split('|').any(|choice| choice.trim().is_empty()) {
+                    return Err(LocalizedError::new("pack.error.empty_choice")
+                        .with("key", key)
+                        .with("field", field.name()));
+                }
+            }
+        }
+
+        for (key, weight) in &self.dimension_weights {
+            let dimension = match key.split_once('.') {
+                Some((scenario, dimension)) => {
+                    if !SCENARIO_NAMES.contains(&scenario) {
+                        return Err(
+                            LocalizedError::new("pack.error.unknown_scenario").with("key", key)
+                        );
+                    }
+                    dimension
+                }
+                None => key.as_str(),
+            };
+            if !DIMENSION_NAMES.contains(&dimension) {
+                return Err(LocalizedError::new("pack.error.unknown_dimension").with("key", key));
+            }
+            if !weight.is_finite() || *weight < 0.0 {
+                return Err(LocalizedError::new("pack.error.negative_weight").with("key", key));
+            }
+        }
+
+        if !self.dimension_weights.is_empty() {
+            for scenario in [WorkScenario::Workday, WorkScenario::Weekend] {
+                let total: f32 = DIMENSION_NAMES
+                    .iter()
+                    .map(|dimension| self.weight(&scenario, dimension).unwrap_or(0.0))
+                    .sum();
+                if (total - 1.0).abs() > 0.01 {
+                    return Err(LocalizedError::new("pack.error.weight_sum")
+                        .with("scenario", scenario_name(&scenario))
+                        .with("total", format!("{:.2}", total)));
+                }
+            }
+        }
+
+        let _ = self.conditions.set(conditions);
+        Ok(())
+    }
+
+    /// Weight of a dimension for a scenario, `None` when the pack keeps the defaults.
+    pub fn weight(&self, scenario: &WorkScenario, dimension: &str) -> Option<f32> {
+        if self.dimension_weights.is_empty() {
+            return None;
+        }
+        let scoped = format!("{}.{}", scenario_name(scenario), dimension);
+        Some(
+            self.dimension_weights
+                .get(&scoped)
+                .or_else(|| self.dimension_weights.get(dimension))
+                .copied()
+                .unwrap_or(0.0),
+        )
+    }
+
+    /// Picks the most specific matching template that provides `field`.
+    ///
+    /// Templates with more condition clauses win; ties are broken by key so
+    /// the choice never depends on `HashMap` ordering.
+    pub fn select(&self, field: TemplateField, facts: &FortuneFacts) -> Option<(&str, &str)> {
+        let conditions = self.conditions.get_or_init(|| {
+            self.templates
+                .iter()
+                .filter_map(|(key, template)| {
+                    Some((key.clone(), Condition::parse(&template.condition).ok()?))
+                })
+                .collect()
+        });
+        let mut candidates: Vec<(usize, &String, &FortuneTemplate)> = self
+            .templates
+            .iter()
+            .filter(|(_, template)| !template.field(field).trim().is_empty())
+            .filter_map(|(key, template)| {
+                let condition = conditions.get(key)?;
+                condition
+                    .matches(facts)
+                    .then_some((condition.clauses.len(), key, template))
+            })
+            .collect();
+        candidates.sort_by(|a, b| b.0.cmp(&a.0).then_with(|| a.1.cmp(b.1)));
+        candidates
+            .first()
+            .map(|(_, key, template)| (key.as_str(), template.field(field)))
+    }
+}
+
+/// Renders a `message` or `advice` template; errors name the template key.
+pub fn render_template(
+    key: &str,
+    field: TemplateField,
+    source: &str,
+    context: &TemplateContext,
+) -> Result<String, LocalizedError> {
+    let mut renderer = TinyTemplate::new();
+    renderer.set_default_formatter(&tinytemplate::format_unescaped);
+    renderer
+        .add_template(field.name(), source)
+        .and_then(|_| renderer.render(field.name(), context))
+        .map_err(|e| {
+            LocalizedError::new("pack.error.template_field")
+                .with("key", key)
+                .with("field", field.name())
+                .with("error", e)
+        })
+}
+
+/// Escapes plain text so it renders verbatim as a template.
+fn escape_template(text: &str) -> String {
+    text.replace('{', "\\{")
+}
+
+impl TemplateField {
+    pub fn name(&self) -> &'static str {
+        match self {
+            TemplateField::Message => "message",
+            TemplateField::Advice => "advice",
+            TemplateField::Color => "color",
+            TemplateField::Time => "time",
+        }
+    }
+}
+
+pub fn scenario_name(scenario: &WorkScenario) -> &'static str {
+    match scenario {
+        WorkScenario::Workday => "workday",
+        WorkScenario::Weekend => "weekend",
+    }
+}
+
+/// Lists every `*.json` file in `dir` together with its validation result.
+pub fn discover_packs(dir: &Path) -> Vec<(PathBuf, Result<FortunePack, LocalizedError>)> {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return Vec::new();
+    };
+
+    let mut paths: Vec<PathBuf> = entries
+        .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+        .filter(|path| path.extension().is_some_and(|ext| ext == "json"))
+        .collect();
+    paths.sort();
+
+    paths
+        .into_iter()
+        .map(|path| {
+            let pack = FortunePack::load_file(&path);
+            (path, pack)
+        })
+        .collect()
+}
+
+/// Finds a pack by file stem first, then by the `name` declared inside it.
+pub fn find_pack(dir: &Path, name: &str, lang: Language) -> Result<FortunePack, LocalizedError> {
+    if name == DEFAULT_PACK_NAME {
+        return Ok(FortunePack::builtin(lang));
+    }
+
+    let direct = dir.join(format!("{}.json", name));
+    if direct.is_file() {
+        return FortunePack::load_file(&direct);
+    }
+
+    discover_packs(dir)
+        .into_iter()
+        .filter_map(|(_, pack)| pack.ok())
+        .find(|pack| pack.name == name)
+        .ok_or_else(|| {
+            LocalizedError::new("pack.error.not_found")
+                .with("name", name)
+                .with("path", dir.display())
+        })
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Subject {
+    Score,
+    Primary,
+    Scenario,
+    Dimension(usize),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Op {
+    Eq,
+    Ne,
+    Ge,
+    Le,
+    Gt,
+    Lt,
+}
+
+#[derive(Debug, Clone)]
+enum Operand {
+    Number(u8),
+    Name(String),
+}
+
+#[derive(Debug, Clone)]
+struct Clause {
+    subject: Subject,
+    op: Op,
+    operand: Operand,
+}
+
+/// A template condition such as `score >= 80 && primary == focus`.
+///
+/// An empty condition (or `*`) matches every fortune.
+#[derive(Debug, Clone)]
+struct Condition {
+    clauses: Vec<Clause>,
+}
+
+impl Condition {
+    fn parse(source: &str) -> Result<Condition, LocalizedError> {
+        let source = source.trim();
+        if source.is_empty() || source == "*" {
+            return Ok(Condition {
+                clauses: Vec::new(),
+            });
+        }
+
+        let clauses = source
+            .split("&&")
+            .map(Clause::parse)
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(Condition { clauses })
+    }
+
+    fn matches(&self, facts: &FortuneFacts) -> bool {
+        self.clauses.iter().all(|clause| clause.matches(facts))
+    }
+}
+
+impl Clause {
+    fn parse(source: &str) -> Result<Clause, LocalizedError> {
+        const OPS: [(&str, Op); 6] = [
+            ("==", Op::Eq),
+            ("!=", Op::Ne),
+            (">=", Op::Ge),
+            ("<=", Op::Le),
+            (">", Op::Gt),
+            ("<", Op::Lt),
+        ];
+
+        let source = source.trim();
+        let (position, token, op) = OPS
+            .iter()
+            .filter_map(|(token, op)| source.find(token).map(|pos| (pos, *token, *op)))
+            .min_by_key(|(pos, token, _)| (*pos, std::cmp::Reverse(token.len())))
+            .ok_or_else(|| LocalizedError::new("pack.error.no_operator").with("clause", source))?;
+
+        let name = source[..position].trim();
+        let value = source[position + token.len()..].trim();
+
+        let subject = match name {
+            "score" => Subject::Score,
+            "primary" => Subject::Primary,
+            "scenario" => Subject::Scenario,
+            _ => DIMENSION_NAMES
+                .iter()
+                .position(|dimension| *dimension == name)
+                .map(Subject::Dimension)
+                .ok_or_else(|| {
+                    LocalizedError::new("pack.error.unknown_field").with("field", name)
+                })?,
+        };
+
+        let operand = match subject {
+            Subject::Score | Subject::Dimension(_) => {
+                Operand::Number(value.parse::<u8>().map_err(|_| {
+                    LocalizedError::new("pack.error.not_a_number").with("value", value)
+                })?)
+            }
+            Subject::Primary | Subject::Scenario => {
+                if !matches!(op, Op::Eq | Op::Ne) {
+                    return Err(LocalizedError::new("pack.error.equality_only").with("field", name));
+                }
+                let allowed: &[&str] = if subject == Subject::Primary {
+                    &DIMENSION_NAMES
+                } else {
+                    &SCENARIO_NAMES
+                };
+                if !allowed.contains(&value) {
+                    return Err(LocalizedError::new("pack.error.invalid_name")
+                        .with("value", value)
+                        .with("field", name)
+                        .with("allowed", allowed.join(", ")));
+                }
+                Operand::Name(value.to_string())
+            }
+        };
+
+        Ok(Clause {
+            subject,
+            op,
+            operand,
+        })
+    }
+
+    fn matches(&self, facts: &FortuneFacts) -> bool {
+        match (&self.subject, &self.operand) {
+            (Subject::Score, Operand::Number(value)) => compare(facts.score, self.op, *value),
+            (Subject::Dimension(index), Operand::Number(value)) => compare(
+                facts.dimensions.value(DIMENSION_NAMES[*index]),
+                self.op,
+                *value,
+            ),
+            (Subject::Primary, Operand::Name(value)) => {
+                (facts.primary == value) == (self.op == Op::Eq)
+            }
+            (Subject::Scenario, Operand::Name(value)) => {
+                (scenario_name(facts.scenario) == value) == (self.op == Op::Eq)
+            }
+            _ => false,
+        }
+    }
+}
+
+fn compare(left: u8, op: Op, right: u8) -> bool {
+    match op {
+        Op::Eq => left == right,
+        Op::Ne => left != right,
+        Op::Ge => left >= right,
+        Op::Le => left <= right,
+        Op::Gt => left > right,
+        Op::Lt => left < right,
+    }
+}
@@ -0,0 +1,334 @@
+use crate::models::dev_pressure::PressureOptions;
+use crate::models::pressure_error::{PatternProblem, PressureError};
+use git2::{
+    BranchType, Diff, DiffFormat, DiffOptions, ErrorCode, Patch, Repository, RepositoryState, Sort,
+    Status, StatusOptions,
+};
+use globset::{Glob, GlobSet, GlobSetBuilder};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::HashSet;
+use std::path::Path;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Lines added and deleted recently, from commits in the window plus uncommitted changes.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct GitChurn {
+    pub window_hours: u32,
+    pub added: u32,
+    pub deleted: u32,
+    /// Part of `added + deleted` that is staged or only in the working tree.
+    pub uncommitted: u32,
+    /// Distinct paths counted.
+    pub files: u32,
+    /// Distinct paths skipped by the exclude list.
+    pub excluded_files: u32,
+}
+
+impl GitChurn {
+    pub fn total_lines(&self) -> u32 {
+        self.added + self.deleted
+    }
+}
+
+/// State of the repository beyond line churn.
+///
+/// Fields are `None` when they do not apply, e.g. `ahead` without an upstream branch.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct RepoSignals {
+    /// `None` on a detached HEAD.
+    pub branch: Option<String>,
+    pub ahead: Option<u32>,
+    pub behind: Option<u32>,
+    pub stash_count: u32,
+    pub conflicted_files: u32,
+    /// Operation left unfinished, such as `merge` or `rebase`.
+    pub in_progress: Option<String>,
+    pub untracked_files: u32,
+    /// `None` before the first commit.
+    pub last_commit_age_seconds: Option<u64>,
+}
+
+/// Measures churn in the repository containing `repo`.
+///
+/// Outside a git repository every count is zero.
+pub fn measure_git_churn(
+    repo: &Path,
+    options: &PressureOptions,
+) -> Result<GitChurn, PressureError> {
+    let mut counter = ChurnCounter {
+        exclude: build_exclude_set(&options.exclude)?,
+        churn: GitChurn {
+            window_hours: options.window_hours,
+            ..GitChurn::default()
+        },
+        files: HashSet::new(),
+        excluded: HashSet::new(),
+    };
+
+    let Some(repository) = discover(repo)? else {
+        return Ok(counter.churn);
+    };
+
+    let since = now_seconds() - options.window_hours as i64 * 3600;
+    if let Some(head) = head_commit(&repository) {
+        let mut walk = repository.revwalk().map_err(git_error)?;
+        walk.push(head.id()).map_err(git_error)?;
+        walk.set_sorting(Sort::TIME).map_err(git_error)?;
+
+        for oid in walk {
+            let commit = repository
+                .find_commit(oid.map_err(git_error)?)
+                .map_err(git_error)?;
+            if commit.time().seconds() < since {
+                break;
+            }
+            // Like `git log --numstat`, merges contribute nothing of their own.
+            if commit.parent_count() > 1 {
+                continue;
+            }
+
+            let parent_tree = match commit.parent_count() {
+                0 => None,
+                _ => Some(
+                    commit
+                        .parent(0)
+                        .and_then(|parent| parent.tree())
+                        .map_err(git_error)?,
+                ),
+            };
+            let tree = commit.tree().map_err(git_error)?;
+            let diff = repository
+                .diff_tree_to_tree(parent_tree.as_ref(), Some(&tree), None)
+                .map_err(git_error)?;
+            counter.add_diff(diff, false)?;
+        }
+    }
+
+    if !repository.is_bare() {
+        let head_tree = head_commit(&repository).and_then(|commit| commit.tree().ok());
+        let diff = repository
+            .diff_tree_to_workdir_with_index(head_tree.as_ref(), None)
+            .map_err(git_error)?;
+        counter.add_diff(diff, true)?;
+    }
+
+    counter.churn.files = counter.files.len() as u32;
+    counter.churn.excluded_files = counter.excluded.len() as u32;
+    Ok(counter.churn)
+}
+
+/// Reads branch, upstream, stash and working-tree state; `None` outside a git repository.
+pub fn inspect_repository(repo: &Path) -> Result<Option<RepoSignals>, PressureError> {
+    let Some(mut repository) = discover(repo)? else {
+        return Ok(None);
+    };
+
+    let mut signals = RepoSignals {
+        branch: current_branch(&repository),
+        ..RepoSignals::default()
+    };
+
+    if let Some(branch) = &signals.branch {
+        if let Ok(local) = repository.find_branch(branch, BranchType::Local) {
+            if let (Ok(upstream), Some(local_oid)) = (local.upstream(), local.get().target()) {
+                if let Some(upstream_oid) = upstream.get().target() {
+                    let (ahead, behind) = repository
+                        .graph_ahead_behind(local_oid, upstream_oid)
+                        .map_err(git_error)?;
+                    signals.ahead = Some(ahead as u32);
+                    signals.behind = Some(behind as u32);
+                }
+            }
+        }
+    }
+
+    signals.last_commit_age_seconds = head_commit(&repository)
+        .map(|commit| (now_seconds() - commit.time().seconds()).max(0) as u64);
+
+    signals.in_progress = match repository.state() {
+        RepositoryState::Clean => None,
+        RepositoryState::Merge => Some("merge"),
+        RepositoryState::Revert | RepositoryState::RevertSequence => Some("revert"),
+        RepositoryState::CherryPick | RepositoryState::CherryPickSequence => Some("cherry-pick"),
+        RepositoryState::Bisect => Some("bisect"),
+        RepositoryState::Rebase
+        | RepositoryState::RebaseInteractive
+        | RepositoryState::RebaseMerge => Some("rebase"),
+        RepositoryState::ApplyMailbox | RepositoryState::ApplyMailboxOrRebase => Some("am"),
+    }
+    .map(str::to_string);
+
+    if !repository.is_bare() {
+        let index = repository.index().map_err(git_error)?;
+        signals.conflicted_files = index.conflicts().map_err(git_error)?.count() as u32;
+
+        let mut options = StatusOptions::new();
+        options
+            .include_untracked(true)
+            .recurse_untracked_dirs(true)
+            .include_ignored(false);
+        signals.untracked_files = repository
+            .statuses(Some(&mut options))
+            .map_err(git_error)?
+            .iter()
+            .filter(|entry| entry.status().contains(Status::WT_NEW))
+            .count() as u32;
+    }
+
+    let mut stash_count = 0;
+    // Repositories without a stash ref report an error here; that simply means no stashes.
+    let _ = repository.stash_foreach(|_, _, _| {
+        stash_count += 1;
+        true
+    });
+    signals.stash_count = stash_count;
+
+    Ok(Some(signals))
+}
+
+/// Hash of HEAD plus every staged, unstaged and untracked change; `None` outside a git repository.
+///
+/// Two calls return the same value only if a rebuild would see the same sources.
+pub fn working_tree_fingerprint(repo: &Path) -> Result<Option<String>, PressureError> {
+    let Some(repository) = discover(repo)? else {
+        return Ok(None);
+    };
+
+    let mut hasher = Sha256::new();
+    let head = head_commit(&repository);
+    match &head {
+        Some(commit) => hasher.update(commit.id().as_bytes()),
+        None => hasher.update(b"unborn"),
+    }
+
+    if !repository.is_bare() {
+        let head_tree = head.as_ref().and_then(|commit| commit.tree().ok());
+        let mut options = DiffOptions::new();
+        options
+            .include_untracked(true)
+            .recurse_untracked_dirs(true)
+            .show_untracked_content(true);
+        let diff = repository
+            .diff_tree_to_workdir_with_index(head_tree.as_ref(), Some(&mut options))
+            .map_err(git_error)?;
+        diff.print(DiffFormat::Patch, |_, _, line| {
+            hasher.update([line.origin() as u8]);
+            hasher.update(line.content());
+            true
+        })
+        .map_err(git_error)?;
+    }
+
+    Ok(Some(format!("{:x}", hasher.finalize())))
+}
+
+/// Full hash of the commit HEAD points at; `None` outside a git repository or before the first commit.
+pub fn head_commit_id(repo: &Path) -> Result<Option<String>, PressureError> {
+    let Some(repository) = discover(repo)? else {
+        return Ok(None);
+    };
+    Ok(head_commit(&repository).map(|commit| commit.id().to_string()))
+}
+
+fn discover(path: &Path) -> Result<Option<Repository>, PressureError> {
+    match Repository::discover(path) {
+        Ok(repository) => Ok(Some(repository)),
+        Err(e) if e.code() == ErrorCode::NotFound => Ok(None),
+        Err(e) => Err(git_error(e)),
+    }
+}
+
+fn head_commit(repository: &Repository) -> Option<git2::Commit<'_>> {
+    repository.head().ok()?.peel_to_commit().ok()
+}
+
+/// The checked-out branch, including an unborn one before the first commit.
+fn current_branch(repository: &Repository) -> Option<String> {
+    match repository.head() {
+        Ok(head) if head.is_branch() => head.shorthand().map(str::to_string),
+        Ok(_) => None,
+        Err(_) => repository
+            .find_reference("HEAD")
+            .ok()?
+            .symbolic_target()?
+            .strip_prefix("refs/heads/")
+            .map(str::to_string),
+    }
+}
+
+fn build_exclude_set(patterns: &[String]) -> Result<GlobSet, PressureError> {
+    let mut builder = GlobSetBuilder::new();
+    for pattern in patterns {
+        let glob = Glob::new(pattern).map_err(|e| exclude_error(pattern, e))?;
+        builder.add(glob);
+    }
+    builder
+        .build()
+        .map_err(|e| exclude_error(&patterns.join(", "), e))
+}
+
+fn exclude_error(pattern: &str, error: globset::Error) -> PressureError {
+    PressureError::Pattern {
+        setting: "pressure.exclude".to_string(),
+        pattern: pattern.to_string(),
+        problem: PatternProblem::syntax(error),
+    }
+}
+
+fn now_seconds() -> i64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|duration| duration.as_secs() as i64)
+        .unwrap_or(0)
+}
+
+fn git_error(error: git2::Error) -> PressureError {
+    PressureError::Git {
+        message: error.message().to_string(),
+    }
+}
+
+struct ChurnCounter {
+    exclude: GlobSet,
+    churn: GitChurn,
+    files: HashSet<String>,
+    excluded: HashSet<String>,
+}
+
+impl ChurnCounter {
+    /// Adds every file in `diff`; binary files count as files without lines.
+    fn add_diff(&mut self, mut diff: Diff<'_>, uncommitted: bool) -> Result<(), PressureError> {
+        // Detect renames so a moved file counts once under its new path, like `git log`.
+        diff.find_similar(None).map_err(git_error)?;
+
+        for index in 0..diff.deltas().len() {
+            let Some(delta) = diff.get_delta(index) else {
+                continue;
+            };
+            let Some(path) = delta.new_file().path().or(delta.old_file().path()) else {
+                continue;
+            };
+            let path = path.to_string_lossy().replace('\\', "/");
+            if self.exclude.is_match(&path) {
+                self.excluded.insert(path);
+                continue;
+            }
+
+            let (added, deleted) = match Patch::from_diff(&diff, index).map_err(git_error)? {
+                Some(patch) => {
+                    let (_, added, deleted) = patch.line_stats().map_err(git_error)?;
+                    (added as u32, deleted as u32)
+                }
+                None => (0, 0),
+            };
+            self.churn.added += added;
+            self.churn.deleted += deleted;
+            if uncommitted {
+                self.churn.uncommitted += added + deleted;
+            }
+            self.files.insert(path);
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,197 @@
+use crate::models::daily_fortune::{Fortune, TechDimensions};
+use crate::utils::i18n::LocalizedError;
+use chrono::{Duration, NaiveDate};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::Path;
+
+pub const HISTORY_FILE_NAME: &str = "history.json";
+/// Version written to `history.json`; bump it when [`History`] changes shape.
+pub const HISTORY_SCHEMA_VERSION: u32 = 1;
+/// Days scoring at least this much count towards a streak.
+pub const HIGH_SCORE_THRESHOLD: u8 = 80;
+
+const SPARK_CHARS: [char; 8] = ['▁', '▂', '▃', '▄', '▅', '▆', '▇', '█'];
+
+/// Journal of past fortunes, one entry per day, oldest first.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct History {
+    pub schema_version: u32,
+    pub entries: Vec<Fortune>,
+}
+
+impl Default for History {
+    fn default() -> Self {
+        History {
+            schema_version: HISTORY_SCHEMA_VERSION,
+            entries: Vec::new(),
+        }
+    }
+}
+
+/// A run of consecutive days scoring at least [`HIGH_SCORE_THRESHOLD`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub struct Streak {
+    pub start: NaiveDate,
+    pub end: NaiveDate,
+    pub days: u32,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub struct DayScore {
+    pub date: NaiveDate,
+    pub score: u8,
+}
+
+/// Summary of the entries shown by `findme history`.
+#[derive(Debug, Serialize)]
+pub struct HistoryStats {
+    pub days: usize,
+    pub average_score: u8,
+    pub best: Option<DayScore>,
+    pub worst: Option<DayScore>,
+    /// The streak that includes the most recent entry, if that day scored high.
+    pub current_streak: Option<Streak>,
+    pub longest_streak: Option<Streak>,
+    pub dimension_averages: TechDimensions,
+}
+
+impl History {
+    /// Reads `history.json` from `dir`; a missing file is an empty history.
+    pub fn load(dir: &Path) -> Result<History, LocalizedError> {
+        let path = dir.join(HISTORY_FILE_NAME);
+        if !path.is_file() {
+            return Ok(History::default());
+        }
+
+        let content = fs::read_to_string(&path).map_err(|e| LocalizedError::read(&path, e))?;
+        let mut history: History = serde_json::from_str(&content).map_err(|e| {
+            LocalizedError::new("file.error.json")
+                .with("error", e)
+                .in_file(&path)
+        })?;
+        if history.schema_version > HISTORY_SCHEMA_VERSION {
+            return Err(LocalizedError::new("file.error.newer_schema")
+                .with("version", history.schema_version)
+                .in_file(&path));
+        }
+        history.schema_version = HISTORY_SCHEMA_VERSION;
+        history.entries.sort_by_key(|entry| entry.date);
+        history.entries.dedup_by_key(|entry| entry.date);
+        Ok(history)
+    }
+
+    pub fn save(&self, dir: &Path) -> Result<(), LocalizedError> {
+        fs::create_dir_all(dir).map_err(|e| LocalizedError::create(dir, e))?;
+        let path = dir.join(HISTORY_FILE_NAME);
+        let content =
+            serde_json::to_string_pretty(self).map_err(|e| LocalizedError::write(&path, e))?;
+        fs::write(&path, content).map_err(|e| LocalizedError::write(&path, e))
+    }
+
+    /// Stores `fortune`, replacing an earlier entry for the same day.
+    pub fn record(&mut self, fortune: &Fortune) {
+        match self
+            .entries
+            .binary_search_by_key(&fortune.date, |entry| entry.date)
+        {
+            Ok(index) => self.entries[index] = fortune.clone(),
+            Err(index) => self.entries.insert(index, fortune.clone()),
+        }
+    }
+
+    /// Entries from the `days` days ending on `today`.
+    pub fn recent(&self, today: NaiveDate, days: u32) -> &[Fortune] {
+        let since = today - Duration::days(days.saturating_sub(1) as i64);
+        let start = self.entries.partition_point(|entry| entry.date < since);
+        let end = self.entries.partition_point(|entry| entry.date <= today);
+        &self.entries[start..end.max(start)]
+    }
+}
+
+/// Scores, streaks and per-dimension averages over `entries` (oldest first).
+pub fn history_stats(entries: &[Fortune]) -> HistoryStats {
+    let average = |value: &dyn Fn(&Fortune) -> u8| {
+        if entries.is_empty() {
+            return 0;
+        }
+        (entries.iter().map(|entry| value(entry) as f32).sum::<f32>() / entries.len() as f32)
+            .round() as u8
+    };
+
+    let best = entries
+        .iter()
+        .rev()
+        .max_by_key(|entry| entry.overall_score)
+        .map(|entry| DayScore {
+            date: entry.date,
+            score: entry.overall_score,
+        });
+    let worst = entries
+        .iter()
+        .rev()
+        .min_by_key(|entry| entry.overall_score)
+        .map(|entry| DayScore {
+            date: entry.date,
+            score: entry.overall_score,
+        });
+
+    let streaks = high_score_streaks(entries);
+    let current_streak = streaks
+        .last()
+        .copied()
+        .filter(|streak| Some(streak.end) == entries.last().map(|entry| entry.date));
+    let longest_streak = streaks
+        .iter()
+        .rev()
+        .max_by_key(|streak| streak.days)
+        .copied();
+
+    HistoryStats {
+        days: entries.len(),
+        average_score: average(&|entry| entry.overall_score),
+        best,
+        worst,
+        current_streak,
+        longest_streak,
+        dimension_averages: TechDimensions {
+            focus: average(&|entry| entry.dimensions.focus),
+            creativity: average(&|entry| entry.dimensions.creativity),
+            debugging: average(&|entry| entry.dimensions.debugging),
+            collaboration: average(&|entry| entry.dimensions.collaboration),
+            risk_tolerance: average(&|entry| entry.dimensions.risk_tolerance),
+        },
+    }
+}
+
+/// One block character per score, from `▁` (0) to `█` (100).
+pub fn sparkline(scores: &[u8]) -> String {
+    scores
+        .iter()
+        .map(|score| {
+            let index = (*score.min(&100) as usize * (SPARK_CHARS.len() - 1) + 50) / 100;
+            SPARK_CHARS[index]
+        })
+        .collect()
+}
+
+fn high_score_streaks(entries: &[Fortune]) -> Vec<Streak> {
+    let mut streaks: Vec<Streak> = Vec::new();
+    for entry in entries
+        .iter()
+        .filter(|entry| entry.overall_score >= HIGH_SCORE_THRESHOLD)
+    {
+        match streaks.last_mut() {
+            Some(streak) if streak.end + Duration::days(1) == entry.date => {
+                streak.end = entry.date;
+                streak.days += 1;
+            }
+            _ => streaks.push(Streak {
+                start: entry.date,
+                end: entry.date,
+                days: 1,
+            }),
+        }
+    }
+    streaks
+}
@@ -1,4 +1,18 @@
+pub mod build_timings;
 pub mod daily_fortune;
 pub mod dev_pressure;
+pub mod fortune_pack;
+pub mod git_repo;
+pub mod history;
+pub mod pressure_cache;
+pub mod pressure_error;
+pub mod pressure_history;
+pub mod pressure_score;
+pub mod probe_command;
+pub mod probes;
+pub mod repo_dashboard;
+pub mod team;
+pub mod test_results;
 
 pub use daily_fortune::*;
+pub use fortune_pack::*;
@@ -0,0 +1,127 @@
+use crate::models::dev_pressure::ProbeKind;
+use crate::models::git_repo::working_tree_fingerprint;
+use crate::models::pressure_error::PressureError;
+use crate::models::probes::ProbeResult;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Version written to cache files; entries from another version are ignored.
+const CACHE_SCHEMA_VERSION: u32 = 3;
+/// Fingerprint used outside a git repository, where only the TTL limits reuse.
+const NO_GIT_FINGERPRINT: &str = "no-git";
+
+/// One probe result and the repository state it was measured against.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct CachedProbe<T> {
+    fingerprint: String,
+    recorded_at: u64,
+    value: T,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct CacheFile {
+    schema_version: u32,
+    #[serde(default)]
+    tests: Option<CachedProbe<Vec<ProbeResult>>>,
+    #[serde(default)]
+    build: Option<CachedProbe<Vec<ProbeResult>>>,
+}
+
+/// Results of the expensive probes for one repository, stored under `<cache dir>/pressure/`.
+///
+/// An entry is reused only while HEAD and the working tree are unchanged and it is younger than the TTL.
+pub struct PressureCache {
+    path: PathBuf,
+    fingerprint: String,
+    ttl_seconds: u64,
+    file: CacheFile,
+}
+
+impl PressureCache {
+    /// Opens the cache for the repository containing `repo`; unreadable caches start empty.
+    pub fn open(
+        cache_dir: &Path,
+        repo: &Path,
+        ttl_minutes: u32,
+    ) -> Result<PressureCache, PressureError> {
+        let root = fs::canonicalize(repo).unwrap_or_else(|_| repo.to_path_buf());
+        let fingerprint =
+            working_tree_fingerprint(&root)?.unwrap_or_else(|| NO_GIT_FINGERPRINT.to_string());
+
+        let path = cache_dir
+            .join("pressure")
+            .join(format!("{}.json", repo_key(&root)));
+        let file = fs::read_to_string(&path)
+            .ok()
+            .and_then(|content| serde_json::from_str::<CacheFile>(&content).ok())
+            .filter(|file| file.schema_version == CACHE_SCHEMA_VERSION)
+            .unwrap_or_default();
+
+        Ok(PressureCache {
+            path,
+            fingerprint,
+            ttl_seconds: ttl_minutes as u64 * 60,
+            file,
+        })
+    }
+
+    /// Results of every probe of `kind` from the last run; the git probe is never cached.
+    pub fn results(&self, kind: ProbeKind) -> Option<Vec<ProbeResult>> {
+        match kind {
+            ProbeKind::Git => None,
+            ProbeKind::Tests => self.fresh(&self.file.tests),
+            ProbeKind::Build => self.fresh(&self.file.build),
+        }
+    }
+
+    pub fn set_results(&mut self, kind: ProbeKind, results: &[ProbeResult]) {
+        let entry = Some(self.entry(results.to_vec()));
+        match kind {
+            ProbeKind::Git => {}
+            ProbeKind::Tests => self.file.tests = entry,
+            ProbeKind::Build => self.file.build = entry,
+        }
+    }
+
+    pub fn save(&mut self) -> Result<(), PressureError> {
+        if let Some(dir) = self.path.parent() {
+            fs::create_dir_all(dir).map_err(|e| PressureError::io(dir, e))?;
+        }
+        self.file.schema_version = CACHE_SCHEMA_VERSION;
+        let content =
+            serde_json::to_string(&self.file).map_err(|e| PressureError::io(&self.path, e))?;
+        fs::write(&self.path, content).map_err(|e| PressureError::io(&self.path, e))
+    }
+
+    fn fresh<T: Clone>(&self, entry: &Option<CachedProbe<T>>) -> Option<T> {
+        entry
+            .as_ref()
+            .filter(|entry| entry.fingerprint == self.fingerprint)
+            .filter(|entry| now_seconds().saturating_sub(entry.recorded_at) < self.ttl_seconds)
+            .map(|entry| entry.value.clone())
+    }
+
+    fn entry<T>(&self, value: T) -> CachedProbe<T> {
+        CachedProbe {
+            fingerprint: self.fingerprint.clone(),
+            recorded_at: now_seconds(),
+            value,
+        }
+    }
+}
+
+/// Short stable name for the repository at `root`, used for per-repository files.
+pub(crate) fn repo_key(root: &Path) -> String {
+    let hash = format!("{:x}", Sha256::digest(root.to_string_lossy().as_bytes()));
+    hash[..16].to_string()
+}
+
+fn now_seconds() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|duration| duration.as_secs())
+        .unwrap_or(0)
+}
@@ -0,0 +1,249 @@
+use crate::models::pressure_score::ScoringProblem;
+use crate::utils::i18n::{i18n_args, MessageArgs};
+use crate::utils::{Language, LocalizedError};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::io;
+use std::path::{Path, PathBuf};
+
+/// Why a pressure probe, or the whole analysis, could not produce a result.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum PressureError {
+    /// The directory to analyze does not exist or is not a directory.
+    NotADirectory { path: PathBuf },
+    /// The git probe ran outside a git repository.
+    NotARepository { path: PathBuf },
+    /// The repository exists but libgit2 could not read it.
+    Git { message: String },
+    /// The program behind a probe is not installed or not on `PATH`.
+    ToolMissing { probe: String, tool: String },
+    /// A probe ran longer than it was allowed to.
+    Timeout { probe: String, seconds: u64 },
+    /// The run was cancelled, e.g. with Ctrl-C, before the probe finished.
+    Cancelled { probe: String },
+    /// A custom probe ran successfully, but none of its count patterns matched the output.
+    NoCountMatch { probe: String },
+    /// A custom probe's count pattern is unusable.
+    CountPattern {
+        probe: String,
+        pattern: String,
+        problem: PatternProblem,
+    },
+    /// A probe's command exited unsuccessfully without reporting any results.
+    CommandFailed {
+        probe: String,
+        command: String,
+        /// `None` when the command was killed by a signal.
+        status: Option<i32>,
+        /// Last lines of standard error.
+        stderr: String,
+    },
+    /// The `[scoring]` table of a `.findme.toml` is invalid.
+    Scoring {
+        path: PathBuf,
+        problem: ScoringProblem,
+    },
+    /// A glob in a setting such as `pressure.exclude` or `pressure.repos` is invalid.
+    Pattern {
+        setting: String,
+        pattern: String,
+        problem: PatternProblem,
+    },
+    /// A file findme keeps, such as the cache, could not be read or written.
+    Io { path: PathBuf, message: String },
+    /// The analysis of a repository stopped on a bug in findme instead of returning.
+    Panicked { message: String },
+}
+
+/// Why a glob or a count pattern was rejected.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum PatternProblem {
+    /// The pattern does not parse; `message` is the parser's explanation.
+    Syntax { message: String },
+    /// A count pattern has no capture group for the number.
+    NoCaptureGroup,
+}
+
+impl PatternProblem {
+    /// The message shown to users, in `lang`.
+    pub fn message(&self, lang: Language) -> String {
+        LocalizedError::from(self).message(lang)
+    }
+
+    pub fn syntax(error: impl fmt::Display) -> PatternProblem {
+        PatternProblem::Syntax {
+            message: error.to_string(),
+        }
+    }
+}
+
+impl From<&PatternProblem> for LocalizedError {
+    fn from(problem: &PatternProblem) -> LocalizedError {
+        match problem {
+            PatternProblem::Syntax { message } => {
+                LocalizedError::new("pressure.error.pattern.syntax").with("message", message)
+            }
+            PatternProblem::NoCaptureGroup => {
+                LocalizedError::new("pressure.error.pattern.no_capture_group")
+            }
+        }
+    }
+}
+
+impl fmt::Display for PatternProblem {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.message(Language::ENGLISH))
+    }
+}
+
+/// Lines of standard error kept in [`PressureError::CommandFailed`].
+const STDERR_TAIL_LINES: usize = 5;
+
+impl PressureError {
+    /// The probe the error belongs to; `None` for errors of the analysis as a whole.
+    pub fn probe(&self) -> Option<&str> {
+        match self {
+            PressureError::NotARepository { .. } | PressureError::Git { .. } => Some("git"),
+            PressureError::ToolMissing { probe, .. }
+            | PressureError::Timeout { probe, .. }
+            | PressureError::Cancelled { probe }
+            | PressureError::NoCountMatch { probe }
+            | PressureError::CountPattern { probe, .. }
+            | PressureError::CommandFailed { probe, .. } => Some(probe),
+            PressureError::NotADirectory { .. }
+            | PressureError::Scoring { .. }
+            | PressureError::Pattern { .. }
+            | PressureError::Io { .. }
+            | PressureError::Panicked { .. } => None,
+        }
+    }
+
+    /// The error for a command that could not be started.
+    pub fn spawn(probe: &str, tool: &str, error: io::Error) -> PressureError {
+        if error.kind() == io::ErrorKind::NotFound {
+            PressureError::ToolMissing {
+                probe: probe.to_string(),
+                tool: tool.to_string(),
+            }
+        } else {
+            PressureError::CommandFailed {
+                probe: probe.to_string(),
+                command: tool.to_string(),
+                status: None,
+                stderr: error.to_string(),
+            }
+        }
+    }
+
+    /// The error for a command that exited with `status`, keeping the end of `stderr`.
+    pub fn command_failed(
+        probe: &str,
+        command: &[&str],
+        status: Option<i32>,
+        stderr: &str,
+    ) -> PressureError {
+        let lines: Vec<&str> = stderr
+            .lines()
+            .filter(|line| !line.trim().is_empty())
+            .collect();
+        PressureError::CommandFailed {
+            probe: probe.to_string(),
+            command: command.join(" "),
+            status,
+            stderr: lines[lines.len().saturating_sub(STDERR_TAIL_LINES)..].join("\n"),
+        }
+    }
+
+    pub fn io(path: &Path, error: impl fmt::Display) -> PressureError {
+        PressureError::Io {
+            path: path.to_path_buf(),
+            message: error.to_string(),
+        }
+    }
+
+    /// The message shown to users, in `lang`.
+    pub fn message(&self, lang: Language) -> String {
+        let fill = |key: &str, args: &MessageArgs| {
+            i18n_args(&format!("pressure.error.{}", key), lang, args)
+        };
+        match self {
+            PressureError::NotADirectory { path } => {
+                fill("not_a_directory", &[("path", &path.display())])
+            }
+            PressureError::NotARepository { path } => {
+                fill("not_a_repository", &[("path", &path.display())])
+            }
+            PressureError::Git { message } => fill("git", &[("message", message)]),
+            PressureError::ToolMissing { probe, tool } => {
+                fill("tool_missing", &[("probe", probe), ("tool", tool)])
+            }
+            PressureError::Timeout { probe, seconds } => {
+                fill("timeout", &[("probe", probe), ("seconds", seconds)])
+            }
+            PressureError::Cancelled { probe } => fill("cancelled", &[("probe", probe)]),
+            PressureError::NoCountMatch { probe } => fill("no_count_match", &[("probe", probe)]),
+            PressureError::CountPattern {
+                probe,
+                pattern,
+                problem,
+            } => fill(
+                "count_pattern",
+                &[
+                    ("probe", probe),
+                    ("pattern", pattern),
+                    ("problem", &problem.message(lang)),
+                ],
+            ),
+            PressureError::CommandFailed {
+                probe,
+                command,
+                status,
+                stderr,
+            } => {
+                let status = status.map_or_else(|| "-".to_string(), |code| code.to_string());
+                let mut text = fill(
+                    "command_failed",
+                    &[("probe", probe), ("command", command), ("status", &status)],
+                );
+                if !stderr.is_empty() {
+                    text.push('\n');
+                    text.push_str(stderr);
+                }
+                text
+            }
+            PressureError::Scoring { path, problem } => fill(
+                "settings_file",
+                &[
+                    ("path", &path.display()),
+                    ("message", &problem.message(lang)),
+                ],
+            ),
+            PressureError::Pattern {
+                setting,
+                pattern,
+                problem,
+            } => fill(
+                "pattern",
+                &[
+                    ("setting", setting),
+                    ("pattern", pattern),
+                    ("problem", &problem.message(lang)),
+                ],
+            ),
+            PressureError::Io { path, message } => {
+                fill("io", &[("path", &path.display()), ("message", message)])
+            }
+            PressureError::Panicked { message } => fill("panicked", &[("message", message)]),
+        }
+    }
+}
+
+impl fmt::Display for PressureError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.message(Language::ENGLISH))
+    }
+}
+
+impl std::error::Error for PressureError {}
@@ -0,0 +1,292 @@
+use crate::models::dev_pressure::{DevPressure, PressureLevel};
+use crate::models::pressure_cache::repo_key;
+use crate::models::pressure_score::ScoreMetric;
+use crate::utils::i18n::LocalizedError;
+use chrono::{DateTime, Duration, Local, NaiveDate};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Version written to pressure history files; bump it when [`PressureSnapshot`] changes shape.
+pub const PRESSURE_HISTORY_SCHEMA_VERSION: u32 = 1;
+/// Snapshots kept per repository; older ones are dropped.
+pub const MAX_PRESSURE_SNAPSHOTS: usize = 1000;
+
+const CHART_BLOCKS: [char; 8] = ['▁', '▂', '▃', '▄', '▅', '▆', '▇', '█'];
+
+/// The pressure of one run, as recorded for `findme pressure history`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PressureSnapshot {
+    pub recorded_at: DateTime<Local>,
+    pub repo: PathBuf,
+    /// HEAD at the time of the run; `None` outside a git repository.
+    pub commit: Option<String>,
+    pub score: u32,
+    pub level: PressureLevel,
+    /// Metrics are `None` when their probe did not run.
+    pub churn_lines: Option<f64>,
+    /// From 0 to 1.
+    pub test_pass_rate: Option<f64>,
+    pub build_seconds: Option<f64>,
+    #[serde(default)]
+    pub build_failed: bool,
+}
+
+impl PressureSnapshot {
+    pub fn new(pressure: &DevPressure, recorded_at: DateTime<Local>) -> PressureSnapshot {
+        let value = |metric: ScoreMetric| {
+            pressure
+                .score_breakdown
+                .iter()
+                .find(|contribution| contribution.metric == metric)
+        };
+        PressureSnapshot {
+            recorded_at,
+            repo: pressure.root.clone(),
+            commit: pressure.commit.clone(),
+            score: pressure.pressure_score,
+            level: pressure.pressure_level,
+            churn_lines: value(ScoreMetric::Churn).map(|churn| churn.value),
+            test_pass_rate: value(ScoreMetric::Tests).map(|tests| tests.value),
+            build_seconds: value(ScoreMetric::Build).map(|build| build.value),
+            build_failed: value(ScoreMetric::Build).is_some_and(|build| build.failed),
+        }
+    }
+}
+
+/// Every recorded pressure run for one repository, oldest first.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PressureHistory {
+    pub schema_version: u32,
+    pub snapshots: Vec<PressureSnapshot>,
+}
+
+impl Default for PressureHistory {
+    fn default() -> Self {
+        PressureHistory {
+            schema_version: PRESSURE_HISTORY_SCHEMA_VERSION,
+            snapshots: Vec::new(),
+        }
+    }
+}
+
+/// A series drawn by `findme pressure history`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TrendMetric {
+    Score,
+    Churn,
+    PassRate,
+    BuildTime,
+}
+
+impl TrendMetric {
+    pub const ALL: [TrendMetric; 4] = [
+        TrendMetric::Score,
+        TrendMetric::Churn,
+        TrendMetric::PassRate,
+        TrendMetric::BuildTime,
+    ];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            TrendMetric::Score => "score",
+            TrendMetric::Churn => "churn",
+            TrendMetric::PassRate => "pass_rate",
+            TrendMetric::BuildTime => "build_time",
+        }
+    }
+
+    /// Score, lines, pass rate (0–1) or seconds; `None` when the probe did not run.
+    pub fn value(&self, snapshot: &PressureSnapshot) -> Option<f64> {
+        match self {
+            TrendMetric::Score => Some(snapshot.score as f64),
+            TrendMetric::Churn => snapshot.churn_lines,
+            TrendMetric::PassRate => snapshot.test_pass_rate,
+            TrendMetric::BuildTime => snapshot.build_seconds,
+        }
+    }
+
+    /// Pass rate is the only series where a higher value means less pressure.
+    fn higher_is_better(&self) -> bool {
+        *self == TrendMetric::PassRate
+    }
+
+    /// Whether moving from `from` to `to` is big enough to call out.
+    fn regressed(&self, from: f64, to: f64) -> bool {
+        match self {
+            TrendMetric::Score => to - from >= 25.0,
+            TrendMetric::Churn => to >= from * 2.0 && to - from >= 50.0,
+            TrendMetric::PassRate => from - to >= 0.1,
+            TrendMetric::BuildTime => from > 0.0 && to >= from * 2.0 && to - from >= 5.0,
+        }
+    }
+}
+
+/// A metric that got markedly worse than its best value in the window.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Regression {
+    pub metric: TrendMetric,
+    /// Best value before the latest snapshot.
+    pub from: f64,
+    /// Value in the latest snapshot.
+    pub to: f64,
+    /// When `from` was recorded.
+    pub since: DateTime<Local>,
+}
+
+impl PressureHistory {
+    /// Reads the history of the repository at `repo` from `dir`; a missing file is an empty history.
+    pub fn load(dir: &Path, repo: &Path) -> Result<PressureHistory, LocalizedError> {
+        let path = pressure_history_path(dir, repo);
+        if !path.is_file() {
+            return Ok(PressureHistory::default());
+        }
+
+        let content = fs::read_to_string(&path).map_err(|e| LocalizedError::read(&path, e))?;
+        let mut history: PressureHistory = serde_json::from_str(&content).map_err(|e| {
+            LocalizedError::new("file.error.json")
+                .with("error", e)
+                .in_file(&path)
+        })?;
+        if history.schema_version > PRESSURE_HISTORY_SCHEMA_VERSION {
+            return Err(LocalizedError::new("file.error.newer_schema")
+                .with("version", history.schema_version)
+                .in_file(&path));
+        }
+        history.schema_version = PRESSURE_HISTORY_SCHEMA_VERSION;
+        history
+            .snapshots
+            .sort_by_key(|snapshot| snapshot.recorded_at);
+        Ok(history)
+    }
+
+    pub fn save(&self, dir: &Path, repo: &Path) -> Result<(), LocalizedError> {
+        let path = pressure_history_path(dir, repo);
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent).map_err(|e| LocalizedError::create(parent, e))?;
+        }
+        let content = serde_json::to_string(self).map_err(|e| LocalizedError::write(&path, e))?;
+        fs::write(&path, content).map_err(|e| LocalizedError::write(&path, e))
+    }
+
+    /// Appends `snapshot`, dropping the oldest beyond [`MAX_PRESSURE_SNAPSHOTS`].
+    pub fn record(&mut self, snapshot: PressureSnapshot) {
+        let index = self
+            .snapshots
+            .partition_point(|existing| existing.recorded_at <= snapshot.recorded_at);
+        self.snapshots.insert(index, snapshot);
+        let excess = self.snapshots.len().saturating_sub(MAX_PRESSURE_SNAPSHOTS);
+        self.snapshots.drain(..excess);
+    }
+
+    /// Snapshots from the `days` days ending on `today`.
+    pub fn recent(&self, today: NaiveDate, days: u32) -> &[PressureSnapshot] {
+        let since = today - Duration::days(days.saturating_sub(1) as i64);
+        let date = |snapshot: &PressureSnapshot| snapshot.recorded_at.date_naive();
+        let start = self
+            .snapshots
+            .partition_point(|snapshot| date(snapshot) < since);
+        let end = self
+            .snapshots
+            .partition_point(|snapshot| date(snapshot) <= today);
+        &self.snapshots[start..end.max(start)]
+    }
+}
+
+/// `<dir>/pressure-history/<repo key>.json`.
+pub fn pressure_history_path(dir: &Path, repo: &Path) -> PathBuf {
+    let root = fs::canonicalize(repo).unwrap_or_else(|_| repo.to_path_buf());
+    dir.join("pressure-history")
+        .join(format!("{}.json", repo_key(&root)))
+}
+
+/// One value per day from `today - days + 1` to `today`: the last snapshot of that day, if any.
+pub fn daily_values(
+    snapshots: &[PressureSnapshot],
+    metric: TrendMetric,
+    today: NaiveDate,
+    days: u32,
+) -> Vec<Option<f64>> {
+    (0..days as i64)
+        .rev()
+        .map(|offset| {
+            let date = today - Duration::days(offset);
+            snapshots
+                .iter()
+                .rev()
+                .filter(|snapshot| snapshot.recorded_at.date_naive() == date)
+                .find_map(|snapshot| metric.value(snapshot))
+        })
+        .collect()
+}
+
+/// Metrics whose latest value is markedly worse than their best earlier value in `snapshots`.
+pub fn detect_regressions(snapshots: &[PressureSnapshot]) -> Vec<Regression> {
+    TrendMetric::ALL
+        .into_iter()
+        .filter_map(|metric| {
+            let mut measured = snapshots
+                .iter()
+                .filter_map(|snapshot| Some((metric.value(snapshot)?, snapshot.recorded_at)));
+            let (to, _) = measured.next_back()?;
+            let (from, since) = measured.reduce(|best, candidate| {
+                let better = if metric.higher_is_better() {
+                    candidate.0 >= best.0
+                } else {
+                    candidate.0 <= best.0
+                };
+                if better {
+                    candidate
+                } else {
+                    best
+                }
+            })?;
+            metric.regressed(from, to).then_some(Regression {
+                metric,
+                from,
+                to,
+                since,
+            })
+        })
+        .collect()
+}
+
+/// Rows of a column chart, top first, one column per value scaled against `max`.
+///
+/// Days without a value show `·` on the bottom row; any measured value shows at least `▁`.
+pub fn bar_chart(values: &[Option<f64>], max: f64, height: usize) -> Vec<String> {
+    let levels = CHART_BLOCKS.len();
+    let max = if max > 0.0 { max } else { 1.0 };
+    // Eighths of a row each column fills.
+    let fills: Vec<Option<usize>> = values
+        .iter()
+        .map(|value| {
+            value.map(|value| {
+                let eighths = (value.clamp(0.0, max) / max * (height * levels) as f64).round();
+                (eighths as usize).max(1)
+            })
+        })
+        .collect();
+
+    (0..height)
+        .rev()
+        .map(|row| {
+            fills
+                .iter()
+                .map(|fill| match fill {
+                    None if row == 0 => '·',
+                    None => ' ',
+                    Some(fill) => {
+                        let filled = fill.saturating_sub(row * levels).min(levels);
+                        if filled == 0 {
+                            ' '
+                        } else {
+                            CHART_BLOCKS[filled - 1]
+                        }
+                    }
+                })
+                .collect()
+        })
+        .collect()
+}
@@ -0,0 +1,348 @@
+use crate::models::dev_pressure::PressureLevel;
+use crate::models::pressure_error::PressureError;
+use crate::utils::i18n::{i18n, i18n_args};
+use crate::utils::Language;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Per-repository settings file, looked up from the working directory upwards.
+pub const REPO_CONFIG_FILE_NAME: &str = ".findme.toml";
+
+/// A measurement the pressure score is built from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ScoreMetric {
+    /// Lines changed recently.
+    Churn,
+    /// Share of tests passing, from 0 to 1.
+    Tests,
+    /// Build duration in seconds.
+    Build,
+}
+
+impl ScoreMetric {
+    /// The metric's name in `[scoring]`, e.g. `churn`.
+    pub fn name(&self) -> &'static str {
+        match self {
+            ScoreMetric::Churn => "churn",
+            ScoreMetric::Tests => "tests",
+            ScoreMetric::Build => "build",
+        }
+    }
+}
+
+/// Why the `[scoring]` table of a `.findme.toml` was rejected.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum ScoringProblem {
+    /// The file is not TOML, or `[scoring]` has an unknown key or a value of the wrong type.
+    Syntax {
+        message: String,
+    },
+    /// A weight is negative or not a finite number.
+    Weight {
+        metric: ScoreMetric,
+    },
+    EmptyThresholds {
+        metric: ScoreMetric,
+    },
+    /// Churn and build thresholds must ascend, test pass rates descend.
+    ThresholdOrder {
+        metric: ScoreMetric,
+    },
+    /// A test threshold is not a pass rate between 0 and 1.
+    PassRate,
+    /// The level boundaries are not `0 < medium < high < critical <= 100`.
+    LevelOrder,
+}
+
+impl ScoringProblem {
+    /// The message shown to users, in `lang`.
+    pub fn message(&self, lang: Language) -> String {
+        let metric_rule = |key: &str, metric: &ScoreMetric| {
+            i18n_args(
+                &format!("pressure.error.scoring.{}", key),
+                lang,
+                &[("metric", &metric.name())],
+            )
+        };
+        match self {
+            ScoringProblem::Syntax { message } => i18n_args(
+                "pressure.error.scoring.syntax",
+                lang,
+                &[("message", message)],
+            ),
+            ScoringProblem::Weight { metric } => metric_rule("weight", metric),
+            ScoringProblem::EmptyThresholds { metric } => metric_rule("empty_thresholds", metric),
+            ScoringProblem::ThresholdOrder {
+                metric: metric @ ScoreMetric::Tests,
+            } => metric_rule("descending", metric),
+            ScoringProblem::ThresholdOrder { metric } => metric_rule("ascending", metric),
+            ScoringProblem::PassRate => i18n("pressure.error.scoring.pass_rate", lang),
+            ScoringProblem::LevelOrder => i18n("pressure.error.scoring.level_order", lang),
+        }
+    }
+}
+
+/// Points a metric adds as it crosses its thresholds.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct MetricRule {
+    /// Points added once every threshold is crossed; each threshold adds an equal share.
+    pub weight: f64,
+    /// Ascending limits for churn and build; descending pass rates for tests.
+    pub thresholds: Vec<f64>,
+}
+
+impl MetricRule {
+    fn new(weight: f64, thresholds: &[f64]) -> MetricRule {
+        MetricRule {
+            weight,
+            thresholds: thresholds.to_vec(),
+        }
+    }
+}
+
+/// Weights, thresholds and level boundaries, from the `[scoring]` table of `.findme.toml`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ScoringModel {
+    pub churn: MetricRule,
+    pub tests: MetricRule,
+    pub build: MetricRule,
+    /// Lowest 0–100 score for each level above low.
+    pub medium: f64,
+    pub high: f64,
+    pub critical: f64,
+}
+
+impl Default for ScoringModel {
+    fn default() -> Self {
+        ScoringModel {
+            churn: MetricRule::new(40.0, &[20.0, 50.0, 100.0, 200.0]),
+            tests: MetricRule::new(30.0, &[0.9, 0.7, 0.5]),
+            build: MetricRule::new(30.0, &[15.0, 30.0, 60.0]),
+            medium: 30.0,
+            high: 60.0,
+            critical: 80.0,
+        }
+    }
+}
+
+/// What one metric added to the score.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ScoreContribution {
+    pub metric: ScoreMetric,
+    /// Lines, pass rate (0–1) or seconds.
+    pub value: f64,
+    /// Set when the build failed, which crosses every build threshold.
+    pub failed: bool,
+    pub thresholds_crossed: usize,
+    /// Share of the 0–100 score.
+    pub points: f64,
+    /// Most this metric could have added to the 0–100 score.
+    pub max_points: f64,
+}
+
+/// Metric values to score; `None` for metrics that were not measured.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ScoreInputs {
+    pub churn_lines: Option<f64>,
+    pub test_pass_rate: Option<f64>,
+    pub build_seconds: Option<f64>,
+    pub build_failed: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PressureScore {
+    /// 0–100.
+    pub score: u32,
+    pub level: PressureLevel,
+    /// One entry per measured metric.
+    pub contributions: Vec<ScoreContribution>,
+}
+
+impl PressureScore {
+    /// The metric that added the most points, if any added some.
+    pub fn top_contribution(&self) -> Option<&ScoreContribution> {
+        self.contributions
+            .iter()
+            .filter(|contribution| contribution.points > 0.0)
+            .max_by(|a, b| a.points.total_cmp(&b.points))
+    }
+}
+
+#[derive(Debug, Default, Deserialize)]
+struct RepoConfig {
+    #[serde(default)]
+    scoring: Option<ScoringModel>,
+}
+
+impl ScoringModel {
+    /// The model from the nearest `.findme.toml` above `dir`, with the file it came from.
+    ///
+    /// Without such a file, or without a `[scoring]` table in it, the defaults apply.
+    pub fn load(dir: &Path) -> Result<(ScoringModel, Option<PathBuf>), PressureError> {
+        let dir = fs::canonicalize(dir).unwrap_or_else(|_| dir.to_path_buf());
+        let Some(path) = dir
+            .ancestors()
+            .map(|ancestor| ancestor.join(REPO_CONFIG_FILE_NAME))
+            .find(|path| path.is_file())
+        else {
+            return Ok((ScoringModel::default(), None));
+        };
+
+        let content = fs::read_to_string(&path).map_err(|e| PressureError::io(&path, e))?;
+        let model = ScoringModel::parse(&content)
+            .map_err(|problem| PressureError::Scoring {
+                path: path.clone(),
+                problem,
+            })?
+            .unwrap_or_default();
+        Ok((model, Some(path)))
+    }
+
+    /// Reads the `[scoring]` table of a `.findme.toml`; `None` when it has none.
+    pub fn parse(content: &str) -> Result<Option<ScoringModel>, ScoringProblem> {
+        let config: RepoConfig = toml::from_str(content).map_err(|e| ScoringProblem::Syntax {
+            message: e.message().to_string(),
+        })?;
+        if let Some(model) = &config.scoring {
+            model.validate()?;
+        }
+        Ok(config.scoring)
+    }
+
+    pub fn validate(&self) -> Result<(), ScoringProblem> {
+        for (metric, rule, ascending) in [
+            (ScoreMetric::Churn, &self.churn, true),
+            (ScoreMetric::Tests, &self.tests, false),
+            (ScoreMetric::Build, &self.build, true),
+        ] {
+            if !rule.weight.is_finite() || rule.weight < 0.0 {
+                return Err(ScoringProblem::Weight { metric });
+            }
+            if rule.thresholds.is_empty() {
+                return Err(ScoringProblem::EmptyThresholds { metric });
+            }
+            let ordered = rule.thresholds.windows(2).all(|pair| {
+                if ascending {
+                    pair[0] < pair[1]
+                } else {
+                    pair[0] > pair[1]
+                }
+            });
+            if !ordered {
+                return Err(ScoringProblem::ThresholdOrder { metric });
+            }
+        }
+        if self
+            .tests
+            .thresholds
+            .iter()
+            .any(|rate| !(0.0..=1.0).contains(rate))
+        {
+            return Err(ScoringProblem::PassRate);
+        }
+        if !(0.0 < self.medium
+            && self.medium < self.high
+            && self.high < self.critical
+            && self.critical <= 100.0)
+        {
+            return Err(ScoringProblem::LevelOrder);
+        }
+        Ok(())
+    }
+
+    /// Scores the measured metrics against the weight of the whole model.
+    ///
+    /// Metrics that were not measured add nothing rather than inflating the others, so
+    /// churn alone cannot push the score past its own share.
+    pub fn score(&self, inputs: &ScoreInputs) -> PressureScore {
+        let mut contributions = Vec::new();
+        if let Some(lines) = inputs.churn_lines {
+            let crossed = self.churn.thresholds.iter().filter(|&&t| lines > t).count();
+            contributions.push(contribution(
+                ScoreMetric::Churn,
+                &self.churn,
+                lines,
+                crossed,
+                false,
+            ));
+        }
+        if let Some(rate) = inputs.test_pass_rate {
+            let crossed = self.tests.thresholds.iter().filter(|&&t| rate < t).count();
+            contributions.push(contribution(
+                ScoreMetric::Tests,
+                &self.tests,
+                rate,
+                crossed,
+                false,
+            ));
+        }
+        if let Some(seconds) = inputs.build_seconds {
+            let crossed = if inputs.build_failed {
+                self.build.thresholds.len()
+            } else {
+                self.build
+                    .thresholds
+                    .iter()
+                    .filter(|&&t| seconds > t)
+                    .count()
+            };
+            contributions.push(contribution(
+                ScoreMetric::Build,
+                &self.build,
+                seconds,
+                crossed,
+                inputs.build_failed,
+            ));
+        }
+
+        let possible = self.churn.weight + self.tests.weight + self.build.weight;
+        if possible > 0.0 {
+            for contribution in &mut contributions {
+                contribution.points *= 100.0 / possible;
+                contribution.max_points *= 100.0 / possible;
+            }
+        }
+        let score: f64 = contributions.iter().map(|c| c.points).sum();
+
+        PressureScore {
+            score: score.round().clamp(0.0, 100.0) as u32,
+            level: self.level(score),
+            contributions,
+        }
+    }
+
+    pub fn level(&self, score: f64) -> PressureLevel {
+        if score >= self.critical {
+            PressureLevel::Critical
+        } else if score >= self.high {
+            PressureLevel::High
+        } else if score >= self.medium {
+            PressureLevel::Medium
+        } else {
+            PressureLevel::Low
+        }
+    }
+}
+
+/// Unscaled points: an equal share of the weight per crossed threshold.
+fn contribution(
+    metric: ScoreMetric,
+    rule: &MetricRule,
+    value: f64,
+    thresholds_crossed: usize,
+    failed: bool,
+) -> ScoreContribution {
+    ScoreContribution {
+        metric,
+        value,
+        failed,
+        thresholds_crossed,
+        points: rule.weight * thresholds_crossed as f64 / rule.thresholds.len() as f64,
+        max_points: rule.weight,
+    }
+}
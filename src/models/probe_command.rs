@@ -0,0 +1,202 @@
+use crate::models::pressure_error::PressureError;
+use std::io::Read;
+use std::path::Path;
+use std::process::{Child, Command, ExitStatus, Output, Stdio};
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::{Duration, Instant};
+
+/// Seconds a probe may run before it is stopped, unless the config says otherwise.
+pub const DEFAULT_PROBE_TIMEOUT_SECONDS: u64 = 600;
+
+/// How often a running command is checked for exit, timeout and cancellation.
+const POLL_INTERVAL: Duration = Duration::from_millis(50);
+/// Time a stopped command gets to exit after SIGTERM before it is killed.
+const TERMINATE_GRACE: Duration = Duration::from_secs(2);
+
+/// Shared between the probes of a run and whoever watches them.
+///
+/// Cancelling stops the running commands and every probe that has not started yet;
+/// [`ProbeControl::running`] tells a progress display what is running right now.
+#[derive(Debug, Clone, Default)]
+pub struct ProbeControl {
+    state: Arc<ControlState>,
+}
+
+#[derive(Debug, Default)]
+struct ControlState {
+    cancelled: AtomicBool,
+    running: Mutex<Vec<(u64, String, Instant)>>,
+    next_id: AtomicU64,
+}
+
+impl PartialEq for ProbeControl {
+    fn eq(&self, other: &Self) -> bool {
+        Arc::ptr_eq(&self.state, &other.state)
+    }
+}
+
+impl ProbeControl {
+    pub fn cancel(&self) {
+        self.state.cancelled.store(true, Ordering::SeqCst);
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.state.cancelled.load(Ordering::SeqCst)
+    }
+
+    /// Marks `label` as running until the returned guard is dropped.
+    pub fn start(&self, label: &str) -> RunningProbe {
+        let id = self.state.next_id.fetch_add(1, Ordering::SeqCst);
+        self.lock_running()
+            .push((id, label.to_string(), Instant::now()));
+        RunningProbe {
+            control: self.clone(),
+            id,
+        }
+    }
+
+    /// Labels of the running probes with how long each has been running, oldest first.
+    pub fn running(&self) -> Vec<(String, Duration)> {
+        self.lock_running()
+            .iter()
+            .map(|(_, label, started)| (label.clone(), started.elapsed()))
+            .collect()
+    }
+
+    fn lock_running(&self) -> std::sync::MutexGuard<'_, Vec<(u64, String, Instant)>> {
+        // A panicking probe thread must not hide the others from the progress display.
+        self.state.running.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+/// Keeps a probe listed in [`ProbeControl::running`] while alive.
+#[derive(Debug)]
+pub struct RunningProbe {
+    control: ProbeControl,
+    id: u64,
+}
+
+impl Drop for RunningProbe {
+    fn drop(&mut self) {
+        self.control
+            .lock_running()
+            .retain(|(id, _, _)| *id != self.id);
+    }
+}
+
+/// Runs `args` in `dir` like [`Command::output`], but stops it after `timeout_seconds`
+/// (0 waits forever) or when `control` is cancelled.
+///
+/// The command gets its own process group, so the tools it starts (rustc, test binaries)
+/// are stopped with it.
+pub fn run_command(
+    dir: &Path,
+    probe: &str,
+    args: &[&str],
+    timeout_seconds: u64,
+    control: &ProbeControl,
+) -> Result<Output, PressureError> {
+    if control.is_cancelled() {
+        return Err(PressureError::Cancelled {
+            probe: probe.to_string(),
+        });
+    }
+
+    let mut command = Command::new(args[0]);
+    command
+        .args(&args[1..])
+        .current_dir(dir)
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped());
+    #[cfg(unix)]
+    {
+        use std::os::unix::process::CommandExt;
+        // Ctrl-C then reaches findme only, which stops the group itself.
+        command.process_group(0);
+    }
+    let mut child = command
+        .spawn()
+        .map_err(|e| PressureError::spawn(probe, args[0], e))?;
+
+    let stdout = read_in_background(child.stdout.take());
+    let stderr = read_in_background(child.stderr.take());
+
+    let started = Instant::now();
+    let timeout = Duration::from_secs(timeout_seconds);
+    let result = loop {
+        match child.try_wait() {
+            Ok(Some(status)) => break Ok(status),
+            Ok(None) => {}
+            Err(e) => {
+                stop(&mut child);
+                break Err(PressureError::spawn(probe, args[0], e));
+            }
+        }
+        if control.is_cancelled() {
+            stop(&mut child);
+            break Err(PressureError::Cancelled {
+                probe: probe.to_string(),
+            });
+        }
+        if timeout_seconds > 0 && started.elapsed() >= timeout {
+            stop(&mut child);
+            break Err(PressureError::Timeout {
+                probe: probe.to_string(),
+                seconds: timeout_seconds,
+            });
+        }
+        thread::sleep(POLL_INTERVAL);
+    };
+
+    let stdout = stdout.join().unwrap_or_default();
+    let stderr = stderr.join().unwrap_or_default();
+    result.map(|status: ExitStatus| Output {
+        status,
+        stdout,
+        stderr,
+    })
+}
+
+fn read_in_background<R: Read + Send + 'static>(pipe: Option<R>) -> thread::JoinHandle<Vec<u8>> {
+    thread::spawn(move || {
+        let mut buffer = Vec::new();
+        if let Some(mut pipe) = pipe {
+            let _ = pipe.read_to_end(&mut buffer);
+        }
+        buffer
+    })
+}
+
+/// Asks the command's process group to exit, then kills whatever is left.
+fn stop(child: &mut Child) {
+    #[cfg(unix)]
+    {
+        // A negative pid addresses the whole process group: the child was spawned with
+        // `process_group(0)`, so its pid is the group id of it and everything it started.
+        let group = -(child.id() as libc::pid_t);
+        // SAFETY: `kill` only sends a signal and touches no memory of ours. The child has
+        // not been waited for yet, so its pid, and with it the group id, still belongs to it.
+        unsafe { libc::kill(group, libc::SIGTERM) };
+        let deadline = Instant::now() + TERMINATE_GRACE;
+        while Instant::now() < deadline {
+            if matches!(child.try_wait(), Ok(Some(_))) {
+                // Children that ignored SIGTERM would keep the pipes open.
+                // SAFETY: as above, `kill` has no memory effects. The child is reaped now,
+                // but a pid is not reused while a process group with that id still has
+                // members, so the signal reaches only the leftovers of this group; once the
+                // group is empty the call fails with `ESRCH` instead.
+                unsafe { libc::kill(group, libc::SIGKILL) };
+                return;
+            }
+            thread::sleep(POLL_INTERVAL);
+        }
+        // SAFETY: as above, `kill` has no memory effects, and the child is still unreaped,
+        // so the group id cannot have been handed to another process.
+        unsafe { libc::kill(group, libc::SIGKILL) };
+    }
+    let _ = child.kill();
+    let _ = child.wait();
+}
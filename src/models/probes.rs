@@ -0,0 +1,551 @@
+use crate::models::build_timings::{measure_build_timings, BuildTimings};
+use crate::models::dev_pressure::{PressureOptions, ProbeKind};
+use crate::models::pressure_error::{PatternProblem, PressureError};
+use crate::models::probe_command::run_command;
+use crate::models::test_results::{
+    parse_go_test_json, parse_pytest_output, parse_test_counts, run_cargo_tests, TestBinaryResult,
+    TestRunSummary,
+};
+use crate::utils::LocalizedError;
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Output;
+use std::time::Instant;
+
+/// Tests that passed.
+pub const TESTS_PASSED: &str = "tests.passed";
+/// Tests that failed.
+pub const TESTS_FAILED: &str = "tests.failed";
+/// Tests that were skipped or ignored.
+pub const TESTS_IGNORED: &str = "tests.ignored";
+/// 1 when the tests could not run at all, e.g. because they did not compile.
+pub const TESTS_BROKEN: &str = "tests.broken";
+/// Duration of the build in seconds.
+pub const BUILD_SECONDS: &str = "build.seconds";
+/// 1 when the build failed.
+pub const BUILD_FAILED: &str = "build.failed";
+
+/// A tool that measures the repository, such as a test runner or a build system.
+pub trait Probe {
+    /// Short name shown next to its results, e.g. `cargo` or `pytest`.
+    fn name(&self) -> &str;
+
+    /// Whether this probe answers for the tests or the build.
+    fn kind(&self) -> ProbeKind;
+
+    /// Whether the tool is set up for the project containing `dir`.
+    fn detect(&self, dir: &Path) -> bool;
+
+    fn run(&self, dir: &Path, options: &PressureOptions) -> Result<ProbeResult, PressureError>;
+}
+
+/// Named metrics from one probe, plus details only some tools report.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ProbeResult {
+    pub probe: String,
+    pub kind: ProbeKind,
+    /// Values keyed by names such as [`TESTS_PASSED`] or [`BUILD_SECONDS`].
+    pub metrics: BTreeMap<String, f64>,
+    /// Per-binary test results, when the tool reports them.
+    pub tests: Option<TestRunSummary>,
+    /// Recorded build durations; only cargo keeps these.
+    pub builds: Option<BuildTimings>,
+}
+
+impl ProbeResult {
+    fn new(probe: &str, kind: ProbeKind) -> ProbeResult {
+        ProbeResult {
+            probe: probe.to_string(),
+            kind,
+            metrics: BTreeMap::new(),
+            tests: None,
+            builds: None,
+        }
+    }
+
+    /// Test metrics taken from a run summary.
+    pub fn from_tests(probe: &str, tests: TestRunSummary) -> ProbeResult {
+        let mut result = ProbeResult::new(probe, ProbeKind::Tests);
+        result.set(TESTS_PASSED, tests.passed() as f64);
+        result.set(TESTS_FAILED, tests.failed() as f64);
+        result.set(TESTS_IGNORED, tests.ignored() as f64);
+        result.set(TESTS_BROKEN, if tests.compiled() { 0.0 } else { 1.0 });
+        result.tests = Some(tests);
+        result
+    }
+
+    /// Build metrics for one timed build command.
+    pub fn from_build(probe: &str, seconds: f32, success: bool) -> ProbeResult {
+        let mut result = ProbeResult::new(probe, ProbeKind::Build);
+        result.set(BUILD_SECONDS, seconds as f64);
+        result.set(BUILD_FAILED, if success { 0.0 } else { 1.0 });
+        result
+    }
+
+    pub fn metric(&self, name: &str) -> Option<f64> {
+        self.metrics.get(name).copied()
+    }
+
+    fn set(&mut self, name: &str, value: f64) {
+        self.metrics.insert(name.to_string(), value);
+    }
+}
+
+/// A command declared under `[[pressure.custom_probes]]` in the config.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CustomProbe {
+    pub name: String,
+    #[serde(default = "default_custom_kind")]
+    pub kind: ProbeKind,
+    /// Program and arguments, run in the repository without a shell.
+    pub command: Vec<String>,
+    /// Regex whose first capture group is the number of passed tests.
+    #[serde(default)]
+    pub passed: Option<String>,
+    /// Regex whose first capture group is the number of failed tests.
+    #[serde(default)]
+    pub failed: Option<String>,
+    /// Overrides `pressure.timeout_seconds` for this probe.
+    #[serde(default)]
+    pub timeout_seconds: Option<u64>,
+}
+
+fn default_custom_kind() -> ProbeKind {
+    ProbeKind::Tests
+}
+
+impl CustomProbe {
+    pub fn validate(&self) -> Result<(), LocalizedError> {
+        if self.name.trim().is_empty() {
+            return Err(LocalizedError::new("probe.error.no_name"));
+        }
+        if self.kind == ProbeKind::Git {
+            return Err(LocalizedError::new("probe.error.kind").with("name", &self.name));
+        }
+        if self.command.is_empty() {
+            return Err(LocalizedError::new("probe.error.empty_command").with("name", &self.name));
+        }
+        for pattern in [&self.passed, &self.failed].into_iter().flatten() {
+            count_pattern(pattern).map_err(|problem| {
+                LocalizedError::new("probe.error.pattern")
+                    .with("name", &self.name)
+                    .with("pattern", pattern)
+                    .caused_by(LocalizedError::from(&problem))
+            })?;
+        }
+        Ok(())
+    }
+}
+
+impl Probe for CustomProbe {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn kind(&self) -> ProbeKind {
+        self.kind
+    }
+
+    fn detect(&self, _dir: &Path) -> bool {
+        true
+    }
+
+    fn run(&self, dir: &Path, options: &PressureOptions) -> Result<ProbeResult, PressureError> {
+        let args: Vec<&str> = self.command.iter().map(String::as_str).collect();
+        let timeout = self.timeout_seconds.unwrap_or(options.timeout_seconds);
+        let (output, seconds) = run_timed(dir, &self.name, &args, timeout, options)?;
+        if self.kind == ProbeKind::Build {
+            return Ok(ProbeResult::from_build(
+                &self.name,
+                seconds,
+                output.status.success(),
+            ));
+        }
+
+        let text = combined_output(&output);
+        let counts = if self.passed.is_some() || self.failed.is_some() {
+            let count = |pattern: Option<&str>| {
+                last_count(pattern, &text).map_err(|problem| PressureError::CountPattern {
+                    probe: self.name.clone(),
+                    pattern: pattern.unwrap_or_default().to_string(),
+                    problem,
+                })
+            };
+            let passed = count(self.passed.as_deref())?;
+            let failed = count(self.failed.as_deref())?;
+            if passed.is_none() && failed.is_none() && output.status.success() {
+                return Err(PressureError::NoCountMatch {
+                    probe: self.name.clone(),
+                });
+            }
+            (passed.is_some() || failed.is_some()).then(|| TestBinaryResult {
+                name: self.name.clone(),
+                passed: passed.unwrap_or(0),
+                failed: failed.unwrap_or(0),
+                ..TestBinaryResult::default()
+            })
+        } else {
+            parse_test_counts(&self.name, &text)
+        };
+        Ok(ProbeResult::from_tests(
+            &self.name,
+            summary_from_counts(counts, &output),
+        ))
+    }
+}
+
+/// `cargo test` in the enclosing cargo project.
+pub struct CargoTests;
+
+impl Probe for CargoTests {
+    fn name(&self) -> &str {
+        "cargo"
+    }
+
+    fn kind(&self) -> ProbeKind {
+        ProbeKind::Tests
+    }
+
+    fn detect(&self, dir: &Path) -> bool {
+        find_upwards(dir, "Cargo.toml").is_some()
+    }
+
+    fn run(&self, dir: &Path, options: &PressureOptions) -> Result<ProbeResult, PressureError> {
+        let tests = run_cargo_tests(dir, options)?.unwrap_or_default();
+        Ok(ProbeResult::from_tests(self.name(), tests))
+    }
+}
+
+/// `cargo build`, together with the builds cargo recorded on its own.
+pub struct CargoBuild;
+
+impl Probe for CargoBuild {
+    fn name(&self) -> &str {
+        "cargo"
+    }
+
+    fn kind(&self) -> ProbeKind {
+        ProbeKind::Build
+    }
+
+    fn detect(&self, dir: &Path) -> bool {
+        find_upwards(dir, "Cargo.toml").is_some()
+    }
+
+    fn run(&self, dir: &Path, options: &PressureOptions) -> Result<ProbeResult, PressureError> {
+        let Some(run) = measure_build_timings(dir, options)? else {
+            return Ok(ProbeResult::new(self.name(), ProbeKind::Build));
+        };
+        // A successful build that compiled nothing says little; report the latest real one.
+        let mut result = match &run.timings {
+            _ if !run.success => ProbeResult::from_build(self.name(), run.seconds, false),
+            Some(timings) => ProbeResult::from_build(self.name(), timings.latest.seconds, true),
+            None => ProbeResult::new(self.name(), ProbeKind::Build),
+        };
+        result.builds = run.timings;
+        Ok(result)
+    }
+}
+
+/// A `package.json` script run through npm, or pnpm when the project has a pnpm lockfile.
+pub struct NodeScript {
+    manager: &'static str,
+    script: &'static str,
+}
+
+impl NodeScript {
+    pub fn tests(dir: &Path) -> NodeScript {
+        NodeScript {
+            manager: node_manager(dir),
+            script: "test",
+        }
+    }
+
+    pub fn build(dir: &Path) -> NodeScript {
+        NodeScript {
+            manager: node_manager(dir),
+            script: "build",
+        }
+    }
+}
+
+/// What `npm init` puts in `scripts.test`; it is not a test suite.
+const NPM_PLACEHOLDER_TEST: &str = "echo \"Error: no test specified\" && exit 1";
+
+impl Probe for NodeScript {
+    fn name(&self) -> &str {
+        self.manager
+    }
+
+    fn kind(&self) -> ProbeKind {
+        if self.script == "build" {
+            ProbeKind::Build
+        } else {
+            ProbeKind::Tests
+        }
+    }
+
+    fn detect(&self, dir: &Path) -> bool {
+        find_upwards(dir, "package.json")
+            .and_then(|path| fs::read_to_string(path).ok())
+            .and_then(|content| serde_json::from_str::<serde_json::Value>(&content).ok())
+            .and_then(|package| {
+                package["scripts"][self.script]
+                    .as_str()
+                    .map(|script| script != NPM_PLACEHOLDER_TEST)
+            })
+            .unwrap_or(false)
+    }
+
+    fn run(&self, dir: &Path, options: &PressureOptions) -> Result<ProbeResult, PressureError> {
+        let args = match self.script {
+            "test" => vec![self.manager, "test"],
+            script => vec![self.manager, "run", script],
+        };
+        let (output, seconds) =
+            run_timed(dir, self.name(), &args, options.timeout_seconds, options)?;
+        if self.kind() == ProbeKind::Build {
+            return Ok(ProbeResult::from_build(
+                self.name(),
+                seconds,
+                output.status.success(),
+            ));
+        }
+
+        let counts = parse_test_counts(self.name(), &combined_output(&output));
+        Ok(ProbeResult::from_tests(
+            self.name(),
+            summary_from_counts(counts, &output),
+        ))
+    }
+}
+
+/// `go test ./...` in a go module.
+pub struct GoTests;
+
+impl Probe for GoTests {
+    fn name(&self) -> &str {
+        "go"
+    }
+
+    fn kind(&self) -> ProbeKind {
+        ProbeKind::Tests
+    }
+
+    fn detect(&self, dir: &Path) -> bool {
+        find_upwards(dir, "go.mod").is_some()
+    }
+
+    fn run(&self, dir: &Path, options: &PressureOptions) -> Result<ProbeResult, PressureError> {
+        let args = ["go", "test", "-json", "./..."];
+        let (output, _) = run_timed(dir, self.name(), &args, options.timeout_seconds, options)?;
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        let tests = parse_go_test_json(&String::from_utf8_lossy(&output.stdout), &stderr);
+        if !output.status.success() && tests.binaries.is_empty() && tests.compiled() {
+            return Err(PressureError::command_failed(
+                self.name(),
+                &args,
+                output.status.code(),
+                &stderr,
+            ));
+        }
+        Ok(ProbeResult::from_tests(self.name(), tests))
+    }
+}
+
+/// `pytest` in a project that configures it.
+pub struct Pytest;
+
+impl Probe for Pytest {
+    fn name(&self) -> &str {
+        "pytest"
+    }
+
+    fn kind(&self) -> ProbeKind {
+        ProbeKind::Tests
+    }
+
+    fn detect(&self, dir: &Path) -> bool {
+        if find_upwards(dir, "pytest.ini").is_some() || find_upwards(dir, "conftest.py").is_some() {
+            return true;
+        }
+        [
+            ("pyproject.toml", "[tool.pytest"),
+            ("setup.cfg", "[tool:pytest]"),
+            ("tox.ini", "[pytest]"),
+        ]
+        .iter()
+        .any(|(file, section)| {
+            find_upwards(dir, file)
+                .and_then(|path| fs::read_to_string(path).ok())
+                .is_some_and(|content| content.contains(section))
+        })
+    }
+
+    fn run(&self, dir: &Path, options: &PressureOptions) -> Result<ProbeResult, PressureError> {
+        let args = ["pytest", "-rfE", "--color=no"];
+        let (output, _) = run_timed(dir, self.name(), &args, options.timeout_seconds, options)?;
+        let tests = parse_pytest_output(&combined_output(&output));
+        // Exit code 5 means no tests were collected.
+        let failed = !matches!(output.status.code(), Some(0) | Some(5));
+        if failed && tests.binaries.is_empty() && tests.compiled() {
+            return Err(PressureError::command_failed(
+                self.name(),
+                &args,
+                output.status.code(),
+                &String::from_utf8_lossy(&output.stderr),
+            ));
+        }
+        Ok(ProbeResult::from_tests(self.name(), tests))
+    }
+}
+
+/// `make` with the default target, for projects no other build probe recognizes.
+pub struct Make;
+
+impl Probe for Make {
+    fn name(&self) -> &str {
+        "make"
+    }
+
+    fn kind(&self) -> ProbeKind {
+        ProbeKind::Build
+    }
+
+    fn detect(&self, dir: &Path) -> bool {
+        ["GNUmakefile", "makefile", "Makefile"]
+            .iter()
+            .any(|file| dir.join(file).is_file())
+    }
+
+    fn run(&self, dir: &Path, options: &PressureOptions) -> Result<ProbeResult, PressureError> {
+        let (output, seconds) = run_timed(
+            dir,
+            self.name(),
+            &["make"],
+            options.timeout_seconds,
+            options,
+        )?;
+        Ok(ProbeResult::from_build(
+            self.name(),
+            seconds,
+            output.status.success(),
+        ))
+    }
+}
+
+/// Every built-in probe, in the order they are tried.
+pub fn builtin_probes(dir: &Path) -> Vec<Box<dyn Probe>> {
+    vec![
+        Box::new(CargoTests),
+        Box::new(CargoBuild),
+        Box::new(NodeScript::tests(dir)),
+        Box::new(NodeScript::build(dir)),
+        Box::new(GoTests),
+        Box::new(Pytest),
+        Box::new(Make),
+    ]
+}
+
+/// The probes that answer for `kind` in `dir`.
+///
+/// Custom probes of that kind replace the built-ins. Otherwise every built-in that detects
+/// its tool runs, except `make`, which is only used when no other build tool is found
+/// because Makefiles usually wrap one.
+pub fn select_probes(dir: &Path, kind: ProbeKind, custom: &[CustomProbe]) -> Vec<Box<dyn Probe>> {
+    let custom: Vec<Box<dyn Probe>> = custom
+        .iter()
+        .filter(|probe| probe.kind == kind)
+        .map(|probe| Box::new(probe.clone()) as Box<dyn Probe>)
+        .collect();
+    if !custom.is_empty() {
+        return custom;
+    }
+
+    let mut detected: Vec<Box<dyn Probe>> = builtin_probes(dir)
+        .into_iter()
+        .filter(|probe| probe.kind() == kind && probe.detect(dir))
+        .collect();
+    if detected.len() > 1 {
+        detected.retain(|probe| probe.name() != "make");
+    }
+    detected
+}
+
+/// Compiles a custom count pattern, which needs a capture group for the number.
+pub(crate) fn count_pattern(pattern: &str) -> Result<Regex, PatternProblem> {
+    let regex = Regex::new(pattern).map_err(PatternProblem::syntax)?;
+    if regex.captures_len() < 2 {
+        return Err(PatternProblem::NoCaptureGroup);
+    }
+    Ok(regex)
+}
+
+/// Number captured by the last match of `pattern` in `text`.
+fn last_count(pattern: Option<&str>, text: &str) -> Result<Option<u32>, PatternProblem> {
+    let Some(pattern) = pattern else {
+        return Ok(None);
+    };
+    Ok(count_pattern(pattern)?
+        .captures_iter(text)
+        .last()
+        .and_then(|captures| captures.get(1)?.as_str().parse().ok()))
+}
+
+/// A single-binary summary; a failing command without counts is reported like a failed compile.
+fn summary_from_counts(counts: Option<TestBinaryResult>, output: &Output) -> TestRunSummary {
+    let mut summary = TestRunSummary::default();
+    match counts {
+        Some(binary) => summary.binaries.push(binary),
+        None if !output.status.success() => {
+            let text = combined_output(output);
+            let last_line = text
+                .lines()
+                .rev()
+                .find(|line| !line.trim().is_empty())
+                .unwrap_or_default();
+            summary.compile_errors.push(last_line.trim().to_string());
+        }
+        None => {}
+    }
+    summary
+}
+
+fn run_timed(
+    dir: &Path,
+    probe: &str,
+    args: &[&str],
+    timeout_seconds: u64,
+    options: &PressureOptions,
+) -> Result<(Output, f32), PressureError> {
+    let start = Instant::now();
+    let output = run_command(dir, probe, args, timeout_seconds, &options.control)?;
+    Ok((output, start.elapsed().as_secs_f32()))
+}
+
+fn combined_output(output: &Output) -> String {
+    format!(
+        "{}\n{}",
+        String::from_utf8_lossy(&output.stdout),
+        String::from_utf8_lossy(&output.stderr)
+    )
+}
+
+fn find_upwards(dir: &Path, file: &str) -> Option<PathBuf> {
+    // `.` has no ancestors of its own.
+    let dir = fs::canonicalize(dir).unwrap_or_else(|_| dir.to_path_buf());
+    dir.ancestors()
+        .map(|ancestor| ancestor.join(file))
+        .find(|path| path.is_file())
+}
+
+fn node_manager(dir: &Path) -> &'static str {
+    if find_upwards(dir, "pnpm-lock.yaml").is_some() {
+        "pnpm"
+    } else {
+        "npm"
+    }
+}
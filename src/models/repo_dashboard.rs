@@ -0,0 +1,202 @@
+use crate::models::dev_pressure::{analyze_dev_pressure_in, DevPressure, PressureOptions};
+use crate::models::pressure_error::{PatternProblem, PressureError};
+use crate::utils::Language;
+use globset::GlobBuilder;
+use std::any::Any;
+use std::cmp;
+use std::fs;
+use std::panic::{self, AssertUnwindSafe};
+use std::path::{Component, Path, PathBuf};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::thread;
+
+/// Directory levels a `**` pattern searches below its literal prefix.
+const MAX_GLOB_DEPTH: usize = 4;
+
+/// The pressure of one repository in `findme pressure`; a failed analysis keeps its error.
+#[derive(Debug)]
+pub struct RepoPressure {
+    pub repo: PathBuf,
+    pub result: Result<DevPressure, PressureError>,
+}
+
+/// Turns `--repos` arguments or `pressure.repos` entries into directories.
+///
+/// Plain paths are kept even when missing, so the dashboard can report them. Globs
+/// (`~/code/*`, `work/**/service-*`) match git repositories only. `~` is the home
+/// directory and relative paths start at `base`.
+pub fn expand_repo_patterns(
+    patterns: &[String],
+    base: &Path,
+) -> Result<Vec<PathBuf>, PressureError> {
+    let mut repos = Vec::new();
+    for pattern in patterns {
+        let pattern = pattern.trim();
+        if pattern.is_empty() {
+            continue;
+        }
+        let path = expand_home(pattern);
+        let path = if path.is_absolute() {
+            path
+        } else {
+            base.join(path)
+        };
+
+        if !pattern.contains(['*', '?', '[', '{']) {
+            repos.push(path);
+            continue;
+        }
+
+        let text = path.to_string_lossy().into_owned();
+        let matcher = GlobBuilder::new(&text)
+            .literal_separator(true)
+            .build()
+            .map_err(|e| PressureError::Pattern {
+                setting: "pressure.repos".to_string(),
+                pattern: pattern.to_string(),
+                problem: PatternProblem::syntax(e),
+            })?
+            .compile_matcher();
+        let (root, rest) = literal_prefix(&path);
+        let depth = if rest.iter().any(|part| part == "**") {
+            rest.len() - 1 + MAX_GLOB_DEPTH
+        } else {
+            rest.len()
+        };
+        let mut found = Vec::new();
+        walk_dirs(&root, depth, &mut found);
+        repos.extend(
+            found
+                .into_iter()
+                .filter(|dir| matcher.is_match(dir) && dir.join(".git").exists()),
+        );
+    }
+
+    let mut seen = Vec::new();
+    repos.retain(|repo| {
+        let key = fs::canonicalize(repo).unwrap_or_else(|_| repo.clone());
+        if seen.contains(&key) {
+            false
+        } else {
+            seen.push(key);
+            true
+        }
+    });
+    Ok(repos)
+}
+
+/// Analyzes the repositories on a few worker threads, most pressured first; failed
+/// analyses come last. An analysis that panics becomes an error row for its repository.
+pub fn analyze_repos(
+    repos: &[PathBuf],
+    options: &PressureOptions,
+    lang: Language,
+) -> Vec<RepoPressure> {
+    let workers = thread::available_parallelism()
+        .map_or(1, |count| count.get())
+        .min(repos.len());
+    let next = AtomicUsize::new(0);
+    let mut analyzed: Vec<(usize, Result<DevPressure, PressureError>)> = thread::scope(|scope| {
+        let handles: Vec<_> = (0..workers)
+            .map(|_| {
+                scope.spawn(|| {
+                    let mut done = Vec::new();
+                    loop {
+                        let index = next.fetch_add(1, Ordering::Relaxed);
+                        let Some(repo) = repos.get(index) else {
+                            break;
+                        };
+                        let result = panic::catch_unwind(AssertUnwindSafe(|| {
+                            analyze_dev_pressure_in(repo, options, lang)
+                        }))
+                        .unwrap_or_else(|payload| Err(panicked(payload)));
+                        done.push((index, result));
+                    }
+                    done
+                })
+            })
+            .collect();
+        handles
+            .into_iter()
+            .flat_map(|handle| handle.join().unwrap_or_default())
+            .collect()
+    });
+    analyzed.sort_by_key(|(index, _)| *index);
+
+    let mut results: Vec<RepoPressure> = analyzed
+        .into_iter()
+        .map(|(index, result)| RepoPressure {
+            repo: repos[index].clone(),
+            result,
+        })
+        .collect();
+
+    results.sort_by(|a, b| match (&a.result, &b.result) {
+        (Ok(a_pressure), Ok(b_pressure)) => b_pressure
+            .pressure_score
+            .cmp(&a_pressure.pressure_score)
+            .then_with(|| a.repo.cmp(&b.repo)),
+        (Ok(_), Err(_)) => cmp::Ordering::Less,
+        (Err(_), Ok(_)) => cmp::Ordering::Greater,
+        (Err(_), Err(_)) => a.repo.cmp(&b.repo),
+    });
+    results
+}
+
+/// The error row for an analysis that panicked with `payload`.
+fn panicked(payload: Box<dyn Any + Send>) -> PressureError {
+    let message = payload
+        .downcast_ref::<&str>()
+        .map(|text| text.to_string())
+        .or_else(|| payload.downcast_ref::<String>().cloned())
+        .unwrap_or_else(|| "unknown panic".to_string());
+    PressureError::Panicked { message }
+}
+
+fn expand_home(pattern: &str) -> PathBuf {
+    match pattern.strip_prefix("~") {
+        Some(rest) if rest.is_empty() || rest.starts_with('/') => dirs::home_dir()
+            .map(|home| home.join(rest.trim_start_matches('/')))
+            .unwrap_or_else(|| PathBuf::from(pattern)),
+        _ => PathBuf::from(pattern),
+    }
+}
+
+/// Splits `path` before its first component containing a glob character.
+fn literal_prefix(path: &Path) -> (PathBuf, Vec<String>) {
+    let mut root = PathBuf::new();
+    let mut rest = Vec::new();
+    for component in path.components() {
+        let text = component.as_os_str().to_string_lossy();
+        if rest.is_empty()
+            && (matches!(component, Component::RootDir | Component::Prefix(_))
+                || !text.contains(['*', '?', '[', '{']))
+        {
+            root.push(component);
+        } else {
+            rest.push(text.into_owned());
+        }
+    }
+    (root, rest)
+}
+
+/// Every directory up to `depth` levels below `dir`, skipping hidden ones.
+fn walk_dirs(dir: &Path, depth: usize, found: &mut Vec<PathBuf>) {
+    if depth == 0 {
+        return;
+    }
+    let Ok(entries) = fs::read_dir(dir) else {
+        return;
+    };
+    let mut dirs: Vec<PathBuf> = entries
+        .flatten()
+        .filter(|entry| entry.file_type().is_ok_and(|kind| kind.is_dir()))
+        .filter(|entry| !entry.file_name().to_string_lossy().starts_with('.'))
+        .map(|entry| entry.path())
+        .collect();
+    dirs.sort();
+    for dir in dirs {
+        walk_dirs(&dir, depth - 1, found);
+        found.push(dir);
+    }
+}
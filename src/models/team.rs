@@ -0,0 +1,185 @@
+use crate::models::daily_fortune::{generate_fortune_for_date, Fortune, TechDimensions};
+use crate::models::fortune_pack::{FortunePack, DIMENSION_NAMES};
+use crate::utils::{Language, LocalizedError};
+use chrono::NaiveDate;
+use serde::{Deserialize, Serialize};
+use std::cmp::Reverse;
+use std::collections::HashSet;
+use std::fs;
+use std::path::Path;
+
+/// How many pairings the team view lists.
+pub const TOP_PAIRINGS: usize = 3;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct TeamMember {
+    pub name: String,
+    pub birthday: String,
+}
+
+/// Contents of `team.toml`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Team {
+    #[serde(default)]
+    pub name: Option<String>,
+    pub members: Vec<TeamMember>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct MemberFortune {
+    pub name: String,
+    pub fortune: Fortune,
+}
+
+/// Two teammates whose strongest dimensions complement each other.
+#[derive(Debug, Serialize)]
+pub struct Pairing {
+    pub first: String,
+    pub second: String,
+    pub first_strength: &'static str,
+    pub second_strength: &'static str,
+    /// Average of the better score of the two in every dimension.
+    pub synergy: u8,
+    /// Shared collaboration plus the better focus and debugging of the pair.
+    pub pair_programming: u8,
+}
+
+#[derive(Debug, Serialize)]
+pub struct TeamFortune {
+    pub name: Option<String>,
+    pub date: NaiveDate,
+    pub team_score: u8,
+    pub dimensions: TechDimensions,
+    pub members: Vec<MemberFortune>,
+    /// Sorted by synergy, best first.
+    pub pairings: Vec<Pairing>,
+    pub pair_programming: Option<(String, String)>,
+}
+
+impl Team {
+    pub fn from_toml(content: &str) -> Result<Team, LocalizedError> {
+        let team: Team = toml::from_str(content)
+            .map_err(|e| LocalizedError::new("team.error.syntax").with("error", e))?;
+        team.validate()?;
+        Ok(team)
+    }
+
+    pub fn load(path: &Path) -> Result<Team, LocalizedError> {
+        let content = fs::read_to_string(path).map_err(|e| LocalizedError::read(path, e))?;
+        Team::from_toml(&content).map_err(|e| e.in_file(path))
+    }
+
+    fn validate(&self) -> Result<(), LocalizedError> {
+        if self.members.is_empty() {
+            return Err(LocalizedError::new("team.error.no_members"));
+        }
+
+        let mut names = HashSet::new();
+        for member in &self.members {
+            let name = member.name.trim();
+            if name.is_empty() {
+                return Err(LocalizedError::new("team.error.empty_name"));
+            }
+            if !names.insert(name) {
+                return Err(LocalizedError::new("team.error.duplicate_member").with("name", name));
+            }
+            if NaiveDate::parse_from_str(member.birthday.trim(), "%Y-%m-%d").is_err() {
+                return Err(LocalizedError::new("team.error.invalid_birthday")
+                    .with("name", name)
+                    .with("birthday", &member.birthday));
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Computes every member's fortune for `date` and combines them.
+pub fn generate_team_fortune(
+    team: &Team,
+    date: NaiveDate,
+    pack: &FortunePack,
+    lang: Language,
+) -> Result<TeamFortune, LocalizedError> {
+    let members = team
+        .members
+        .iter()
+        .map(|member| {
+            generate_fortune_for_date(date, Some(member.birthday.trim()), pack, None, lang).map(
+                |fortune| MemberFortune {
+                    name: member.name.trim().to_string(),
+                    fortune,
+                },
+            )
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let count = members.len() as f32;
+    let average = |value: &dyn Fn(&Fortune) -> u8| {
+        (members
+            .iter()
+            .map(|member| value(&member.fortune) as f32)
+            .sum::<f32>()
+            / count)
+            .round() as u8
+    };
+
+    let team_score = average(&|fortune| fortune.overall_score);
+    let dimensions = TechDimensions {
+        focus: average(&|fortune| fortune.dimensions.focus),
+        creativity: average(&|fortune| fortune.dimensions.creativity),
+        debugging: average(&|fortune| fortune.dimensions.debugging),
+        collaboration: average(&|fortune| fortune.dimensions.collaboration),
+        risk_tolerance: average(&|fortune| fortune.dimensions.risk_tolerance),
+    };
+
+    let mut pairings = Vec::new();
+    for (i, first) in members.iter().enumerate() {
+        for second in &members[i + 1..] {
+            pairings.push(pair(first, second));
+        }
+    }
+
+    let pair_programming = pairings
+        .iter()
+        .rev()
+        .max_by_key(|pairing| pairing.pair_programming)
+        .map(|pairing| (pairing.first.clone(), pairing.second.clone()));
+    pairings.sort_by_key(|pairing| Reverse(pairing.synergy));
+
+    Ok(TeamFortune {
+        name: team.name.clone(),
+        date,
+        team_score,
+        dimensions,
+        members,
+        pairings,
+        pair_programming,
+    })
+}
+
+fn pair(first: &MemberFortune, second: &MemberFortune) -> Pairing {
+    let a = &first.fortune.dimensions;
+    let b = &second.fortune.dimensions;
+
+    let coverage: u32 = DIMENSION_NAMES
+        .iter()
+        .map(|dimension| a.value(dimension).max(b.value(dimension)) as u32)
+        .sum();
+    let synergy = (coverage as f32 / DIMENSION_NAMES.len() as f32).round() as u8;
+
+    let pair_programming = (a.collaboration.min(b.collaboration) as f32 * 0.4
+        + a.focus.max(b.focus) as f32 * 0.3
+        + a.debugging.max(b.debugging) as f32 * 0.3)
+        .round() as u8;
+
+    Pairing {
+        first: first.name.clone(),
+        second: second.name.clone(),
+        first_strength: a.primary_dimension(),
+        second_strength: b.primary_dimension(),
+        synergy,
+        pair_programming,
+    }
+}
@@ -0,0 +1,347 @@
+use crate::models::dev_pressure::PressureOptions;
+use crate::models::pressure_error::PressureError;
+use crate::models::probe_command::run_command;
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+use std::sync::LazyLock;
+
+/// Counts reported by one test binary's `test result:` line.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct TestBinaryResult {
+    /// Target as the tool names it, e.g. `tests/cli.rs` for cargo or the package path for go.
+    pub name: String,
+    pub passed: u32,
+    pub failed: u32,
+    pub ignored: u32,
+    pub measured: u32,
+    pub filtered_out: u32,
+    pub failures: Vec<String>,
+}
+
+/// Everything a test run reported, summed over all test binaries.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct TestRunSummary {
+    pub binaries: Vec<TestBinaryResult>,
+    /// Compiler errors that kept the tests from running at all.
+    pub compile_errors: Vec<String>,
+}
+
+impl TestRunSummary {
+    pub fn passed(&self) -> u32 {
+        self.binaries.iter().map(|binary| binary.passed).sum()
+    }
+
+    pub fn failed(&self) -> u32 {
+        self.binaries.iter().map(|binary| binary.failed).sum()
+    }
+
+    pub fn ignored(&self) -> u32 {
+        self.binaries.iter().map(|binary| binary.ignored).sum()
+    }
+
+    pub fn filtered_out(&self) -> u32 {
+        self.binaries.iter().map(|binary| binary.filtered_out).sum()
+    }
+
+    /// Tests that actually ran.
+    pub fn executed(&self) -> u32 {
+        self.passed() + self.failed()
+    }
+
+    pub fn compiled(&self) -> bool {
+        self.compile_errors.is_empty()
+    }
+
+    /// Share of executed tests that passed; a failed compile counts as 0.
+    pub fn success_rate(&self) -> Option<f32> {
+        if !self.compiled() {
+            return Some(0.0);
+        }
+        match self.executed() {
+            0 => None,
+            executed => Some(self.passed() as f32 / executed as f32),
+        }
+    }
+
+    /// Failing tests as `(binary, test)` pairs, in the order cargo ran them.
+    pub fn failing_tests(&self) -> impl Iterator<Item = (&str, &str)> {
+        self.binaries.iter().flat_map(|binary| {
+            binary
+                .failures
+                .iter()
+                .map(move |test| (binary.name.as_str(), test.as_str()))
+        })
+    }
+}
+
+/// Runs `cargo test --no-fail-fast` in `dir`; `None` when `dir` is not inside a cargo project.
+pub fn run_cargo_tests(
+    dir: &Path,
+    options: &PressureOptions,
+) -> Result<Option<TestRunSummary>, PressureError> {
+    if !dir
+        .ancestors()
+        .any(|ancestor| ancestor.join("Cargo.toml").is_file())
+    {
+        return Ok(None);
+    }
+
+    let args = ["cargo", "test", "--no-fail-fast", "--color", "never"];
+    let output = run_command(
+        dir,
+        "cargo",
+        &args,
+        options.timeout_seconds,
+        &options.control,
+    )?;
+
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    let summary = parse_cargo_test_output(&String::from_utf8_lossy(&output.stdout), &stderr);
+    // Failing before any test binary ran, e.g. on a broken manifest.
+    if !output.status.success() && summary.binaries.is_empty() && summary.compiled() {
+        return Err(PressureError::command_failed(
+            "cargo",
+            &args,
+            output.status.code(),
+            &stderr,
+        ));
+    }
+    Ok(Some(summary))
+}
+
+/// Pairs cargo's `Running`/`Doc-tests` lines on stderr with libtest's result blocks on stdout.
+///
+/// Both streams list the binaries in the same order, one block per binary.
+pub fn parse_cargo_test_output(stdout: &str, stderr: &str) -> TestRunSummary {
+    let mut names = stderr.lines().filter_map(binary_name);
+    let mut summary = TestRunSummary::default();
+    let mut failures = Vec::new();
+
+    for line in stdout.lines() {
+        if let Some(test) = line
+            .strip_prefix("test ")
+            .and_then(|rest| rest.strip_suffix(" ... FAILED"))
+        {
+            failures.push(test.to_string());
+        } else if let Some(counts) = line.strip_prefix("test result: ") {
+            let mut binary = TestBinaryResult {
+                name: names
+                    .next()
+                    .unwrap_or_else(|| format!("binary {}", summary.binaries.len() + 1)),
+                failures: std::mem::take(&mut failures),
+                ..TestBinaryResult::default()
+            };
+            // `FAILED. 1 passed; 1 failed; 0 ignored; 0 measured; 3 filtered out; finished in 0.01s`
+            let counts = counts.split_once(". ").map_or(counts, |(_, counts)| counts);
+            for part in counts.split(';') {
+                let mut words = part.split_whitespace();
+                let (Some(count), Some(label)) = (words.next(), words.last()) else {
+                    continue;
+                };
+                let count = count.parse().unwrap_or(0);
+                match label {
+                    "passed" => binary.passed = count,
+                    "failed" => binary.failed = count,
+                    "ignored" => binary.ignored = count,
+                    "measured" => binary.measured = count,
+                    "out" => binary.filtered_out = count,
+                    _ => {}
+                }
+            }
+            summary.binaries.push(binary);
+        }
+    }
+
+    summary.compile_errors = compile_errors(stderr);
+    summary
+}
+
+/// Reads the summary counts most JavaScript and Python runners print, such as jest's
+/// `Tests: 1 failed, 5 passed, 6 total`, mocha's `5 passing` or node's `# fail 1`.
+///
+/// The last count of each kind wins, since runners print their totals at the end.
+/// `None` when the output has no recognizable counts.
+pub fn parse_test_counts(name: &str, output: &str) -> Option<TestBinaryResult> {
+    static COUNT: LazyLock<Regex> = LazyLock::new(|| {
+        Regex::new(
+            r"(?m)(\d+) (passed|passing|failed|failing|skipped|pending|todo)\b|^\S*\s*#?\s*(pass|fail|skipped|todo) (\d+)\s*$",
+        )
+        .expect("valid count pattern")
+    });
+
+    let mut result = TestBinaryResult {
+        name: name.to_string(),
+        ..TestBinaryResult::default()
+    };
+    let mut found = false;
+    for captures in COUNT.captures_iter(output) {
+        let (count, label) = match (captures.get(1), captures.get(2)) {
+            (Some(count), Some(label)) => (count.as_str(), label.as_str()),
+            _ => (&captures[4], &captures[3]),
+        };
+        let count = count.parse().unwrap_or(0);
+        match label {
+            "passed" | "passing" | "pass" => result.passed = count,
+            "failed" | "failing" | "fail" => result.failed = count,
+            _ => result.ignored = count,
+        }
+        found = true;
+    }
+    found.then_some(result)
+}
+
+/// Reads `pytest -rfE` output: the final counts line plus its `FAILED`/`ERROR` summary lines.
+///
+/// Errors during collection keep the tests from running, like a failed compile.
+pub fn parse_pytest_output(output: &str) -> TestRunSummary {
+    static COUNTS: LazyLock<Regex> = LazyLock::new(|| {
+        Regex::new(r"(\d+) (passed|failed|skipped|xfailed|xpassed|deselected|errors?)\b")
+            .expect("valid pytest pattern")
+    });
+
+    let mut binary = TestBinaryResult {
+        name: "pytest".to_string(),
+        ..TestBinaryResult::default()
+    };
+    let mut errors = Vec::new();
+    for line in output.lines() {
+        if let Some(test) = line.strip_prefix("FAILED ") {
+            binary
+                .failures
+                .push(test.split(" - ").next().unwrap_or(test).to_string());
+        } else if let Some(test) = line.strip_prefix("ERROR ") {
+            errors.push(test.to_string());
+        }
+    }
+
+    if let Some(summary) = output
+        .lines()
+        .rev()
+        .find(|line| COUNTS.is_match(line) && line.contains(" in "))
+    {
+        for captures in COUNTS.captures_iter(summary) {
+            let count: u32 = captures[1].parse().unwrap_or(0);
+            match &captures[2] {
+                "passed" | "xpassed" => binary.passed += count,
+                "failed" | "error" | "errors" => binary.failed += count,
+                "skipped" | "xfailed" => binary.ignored += count,
+                "deselected" => binary.filtered_out += count,
+                _ => {}
+            }
+        }
+    }
+
+    let mut summary = TestRunSummary::default();
+    if output.contains("during collection") {
+        summary.compile_errors = errors;
+    } else {
+        binary.failures.extend(errors);
+    }
+    summary.binaries.push(binary);
+    summary
+}
+
+/// Reads `go test -json ./...`: one binary per package, counting top-level tests only.
+///
+/// Build errors come from `build-output` events (go 1.24+) or, for older versions, stderr.
+pub fn parse_go_test_json(stdout: &str, stderr: &str) -> TestRunSummary {
+    static BUILD_ERROR: LazyLock<Regex> =
+        LazyLock::new(|| Regex::new(r"^\S+\.go:\d+(:\d+)?: ").expect("valid go error pattern"));
+
+    let mut summary = TestRunSummary::default();
+    let mut build_output = String::new();
+    for event in stdout
+        .lines()
+        .filter_map(|line| serde_json::from_str::<serde_json::Value>(line).ok())
+    {
+        let action = event["Action"].as_str().unwrap_or_default();
+        if action == "build-output" {
+            build_output.push_str(event["Output"].as_str().unwrap_or_default());
+            continue;
+        }
+        let (Some(package), Some(test)) = (event["Package"].as_str(), event["Test"].as_str())
+        else {
+            continue;
+        };
+        if test.contains('/') || !matches!(action, "pass" | "fail" | "skip") {
+            continue;
+        }
+
+        let index = match summary
+            .binaries
+            .iter()
+            .position(|binary| binary.name == package)
+        {
+            Some(index) => index,
+            None => {
+                summary.binaries.push(TestBinaryResult {
+                    name: package.to_string(),
+                    ..TestBinaryResult::default()
+                });
+                summary.binaries.len() - 1
+            }
+        };
+        let binary = &mut summary.binaries[index];
+        match action {
+            "pass" => binary.passed += 1,
+            "fail" => {
+                binary.failed += 1;
+                binary.failures.push(test.to_string());
+            }
+            _ => binary.ignored += 1,
+        }
+    }
+
+    summary.compile_errors = build_output
+        .lines()
+        .chain(stderr.lines())
+        .filter(|line| BUILD_ERROR.is_match(line))
+        .map(str::to_string)
+        .collect();
+    summary
+}
+
+fn binary_name(line: &str) -> Option<String> {
+    let line = line.trim();
+    if let Some(target) = line.strip_prefix("Running ") {
+        let name = target.split(" (").next().unwrap_or(target);
+        return Some(name.to_string());
+    }
+    line.strip_prefix("Doc-tests ")
+        .map(|package| format!("doc-tests {}", package))
+}
+
+/// `error[E0308]: mismatched types (tests/it.rs:2:21)` for each compiler error.
+fn compile_errors(stderr: &str) -> Vec<String> {
+    if !stderr.contains("could not compile") {
+        return Vec::new();
+    }
+
+    let mut errors = Vec::new();
+    let mut lines = stderr.lines().peekable();
+    while let Some(line) = lines.next() {
+        if !line.starts_with("error[") && !line.starts_with("error: ") {
+            continue;
+        }
+        if line.starts_with("error: could not compile") || line.starts_with("error: aborting") {
+            continue;
+        }
+        let location = lines
+            .peek()
+            .and_then(|next| next.trim().strip_prefix("--> "))
+            .map(|location| format!(" ({})", location))
+            .unwrap_or_default();
+        errors.push(format!("{}{}", line, location));
+    }
+
+    if errors.is_empty() {
+        if let Some(line) = stderr
+            .lines()
+            .find(|line| line.starts_with("error: could not compile"))
+        {
+            errors.push(line.to_string());
+        }
+    }
+    errors
+}
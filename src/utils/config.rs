@@ -0,0 +1,493 @@
+use crate::models::dev_pressure::ProbeKind;
+use crate::models::probes::CustomProbe;
+use crate::utils::{available_languages, is_supported, Language, LocalizedError};
+use chrono::NaiveDate;
+use std::fs;
+use std::path::{Path, PathBuf};
+use toml::{Table, Value};
+
+/// Version written to `schema_version`; bump it together with an upgrade step in [`UserConfig::parse`].
+pub const CONFIG_SCHEMA_VERSION: i64 = 1;
+pub const CONFIG_FILE_NAME: &str = "config.toml";
+/// The `key=value` file used before the TOML config existed.
+pub const LEGACY_CONFIG_FILE_NAME: &str = "config.txt";
+
+const SCHEMA_VERSION_KEY: &str = "schema_version";
+
+/// A key findme understands, with the check its value has to pass.
+pub struct KnownKey {
+    pub name: &'static str,
+    validate: fn(&Value) -> Result<(), LocalizedError>,
+}
+
+pub const KNOWN_KEYS: &[KnownKey] = &[
+    KnownKey {
+        name: "language",
+        validate: validate_language,
+    },
+    KnownKey {
+        name: "birthday",
+        validate: validate_birthday,
+    },
+    KnownKey {
+        name: "pressure.window_hours",
+        validate: validate_window_hours,
+    },
+    KnownKey {
+        name: "pressure.exclude",
+        validate: validate_globs,
+    },
+    KnownKey {
+        name: "pressure.probes",
+        validate: validate_probes,
+    },
+    KnownKey {
+        name: "pressure.cache_ttl_minutes",
+        validate: validate_minutes,
+    },
+    KnownKey {
+        name: "pressure.timeout_seconds",
+        validate: validate_seconds,
+    },
+    KnownKey {
+        name: "pressure.custom_probes",
+        validate: validate_custom_probes,
+    },
+    KnownKey {
+        name: "pressure.repos",
+        validate: validate_globs,
+    },
+];
+
+/// Directory holding the config file, packs and other local state.
+///
+/// `FINDME_CONFIG_DIR` overrides the default `~/.findme`.
+pub fn config_dir() -> PathBuf {
+    if let Ok(dir) = std::env::var("FINDME_CONFIG_DIR") {
+        return PathBuf::from(dir);
+    }
+
+    let mut config_dir = dirs::home_dir().unwrap_or_else(|| PathBuf::from("."));
+    config_dir.push(".findme");
+    config_dir
+}
+
+/// User preferences stored in `config.toml`.
+///
+/// Keys findme does not know about are kept as-is and written back on save.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct UserConfig {
+    table: Table,
+}
+
+impl UserConfig {
+    /// Loads and validates the config in `dir`, migrating a legacy `config.txt` first.
+    pub fn load(dir: &Path) -> Result<UserConfig, LocalizedError> {
+        let config = UserConfig::load_unvalidated(dir)?;
+        let path = dir.join(CONFIG_FILE_NAME);
+        let content = fs::read_to_string(&path).unwrap_or_default();
+        config.validate(&content).map_err(|e| e.in_file(&path))?;
+        Ok(config)
+    }
+
+    /// Loads the config without checking known keys, so `findme config set` can repair it.
+    pub fn load_unvalidated(dir: &Path) -> Result<UserConfig, LocalizedError> {
+        let path = dir.join(CONFIG_FILE_NAME);
+        if path.is_file() {
+            let content = fs::read_to_string(&path).map_err(|e| LocalizedError::read(&path, e))?;
+            return UserConfig::parse(&content).map_err(|e| e.in_file(&path));
+        }
+
+        let legacy_path = dir.join(LEGACY_CONFIG_FILE_NAME);
+        if legacy_path.is_file() {
+            let content = fs::read_to_string(&legacy_path)
+                .map_err(|e| LocalizedError::read(&legacy_path, e))?;
+            let config = UserConfig::parse_legacy(&content).map_err(|e| e.in_file(&legacy_path))?;
+            config.save(dir)?;
+            let backup = dir.join(format!("{}.bak", LEGACY_CONFIG_FILE_NAME));
+            fs::rename(&legacy_path, &backup).map_err(|e| {
+                LocalizedError::new("file.error.move")
+                    .with("path", legacy_path.display())
+                    .with("error", e)
+            })?;
+            return Ok(config);
+        }
+
+        Ok(UserConfig::default())
+    }
+
+    /// Parses `config.toml` contents and upgrades older schema versions.
+    pub fn parse(content: &str) -> Result<UserConfig, LocalizedError> {
+        let mut table: Table = content.parse().map_err(|e: toml::de::Error| {
+            let error = LocalizedError::new("config.error.syntax").with("error", e.message());
+            match e.span() {
+                Some(span) => at_line(line_of_offset(content, span.start), error),
+                None => error,
+            }
+        })?;
+
+        let version = match table.get(SCHEMA_VERSION_KEY) {
+            None => CONFIG_SCHEMA_VERSION,
+            Some(Value::Integer(version)) => *version,
+            Some(_) => {
+                return Err(locate(
+                    content,
+                    SCHEMA_VERSION_KEY,
+                    LocalizedError::new("config.error.expected_integer"),
+                ))
+            }
+        };
+        if version > CONFIG_SCHEMA_VERSION {
+            return Err(LocalizedError::new("config.error.newer_schema")
+                .with("version", version)
+                .with("supported", CONFIG_SCHEMA_VERSION));
+        }
+        // Version 1 is the first TOML schema; upgrades from older versions go here.
+        table.remove(SCHEMA_VERSION_KEY);
+
+        Ok(UserConfig { table })
+    }
+
+    /// Parses the legacy `key=value` file, or a bare `zh`/`en` language line.
+    pub fn parse_legacy(content: &str) -> Result<UserConfig, LocalizedError> {
+        let mut config = UserConfig::default();
+        let trimmed = content.trim();
+        if trimmed.is_empty() {
+            return Ok(config);
+        }
+
+        if !trimmed.contains('=') {
+            config.set("language", trimmed).map_err(|e| at_line(1, e))?;
+            return Ok(config);
+        }
+
+        for (index, line) in content.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            let (key, value) = line.split_once('=').ok_or_else(|| {
+                at_line(
+                    index + 1,
+                    LocalizedError::new("config.error.expected_key_value"),
+                )
+            })?;
+            let (key, value) = (key.trim(), value.trim());
+            if known_key(key).is_some() {
+                config.set(key, value).map_err(|e| at_line(index + 1, e))?;
+            } else {
+                config
+                    .table
+                    .insert(key.to_string(), Value::String(value.to_string()));
+            }
+        }
+        Ok(config)
+    }
+
+    pub fn save(&self, dir: &Path) -> Result<(), LocalizedError> {
+        fs::create_dir_all(dir).map_err(|e| LocalizedError::create(dir, e))?;
+        let path = dir.join(CONFIG_FILE_NAME);
+
+        let mut table = self.table.clone();
+        table.insert(
+            SCHEMA_VERSION_KEY.to_string(),
+            Value::Integer(CONFIG_SCHEMA_VERSION),
+        );
+        let content = toml::to_string(&table).map_err(|e| LocalizedError::write(&path, e))?;
+        fs::write(&path, content).map_err(|e| LocalizedError::write(&path, e))
+    }
+
+    /// Checks every known key that is present; errors name the line and key.
+    pub fn validate(&self, content: &str) -> Result<(), LocalizedError> {
+        for key in KNOWN_KEYS {
+            if let Some(value) = self.get(key.name) {
+                (key.validate)(value).map_err(|e| locate(content, key.name, e))?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Looks up a key; dots address nested tables, e.g. `pressure.window_hours`.
+    pub fn get(&self, key: &str) -> Option<&Value> {
+        let mut parts = key.split('.');
+        let mut value = self.table.get(parts.next()?)?;
+        for part in parts {
+            value = value.as_table()?.get(part)?;
+        }
+        Some(value)
+    }
+
+    /// Sets a key from command-line text, validating it when findme knows the key.
+    ///
+    /// Unknown keys accept any TOML value and fall back to a plain string.
+    pub fn set(&mut self, key: &str, raw: &str) -> Result<(), LocalizedError> {
+        if key.is_empty() || key == SCHEMA_VERSION_KEY || key.split('.').any(str::is_empty) {
+            return Err(LocalizedError::new("config.error.read_only_key").with("key", key));
+        }
+
+        let value = parse_value(raw);
+        let value = match known_key(key) {
+            // `1990-05-15` parses as a TOML date, so known keys also try the raw text.
+            Some(known) => match (known.validate)(&value) {
+                Ok(()) => normalize(key, value),
+                Err(e) => {
+                    let text = Value::String(raw.trim().to_string());
+                    (known.validate)(&text).map_err(|_| {
+                        LocalizedError::new("config.error.key")
+                            .with("key", key)
+                            .caused_by(e)
+                    })?;
+                    normalize(key, text)
+                }
+            },
+            None => value,
+        };
+
+        let mut parts: Vec<&str> = key.split('.').collect();
+        let last = parts.pop().unwrap_or(key);
+        let mut table = &mut self.table;
+        for part in parts {
+            let entry = table
+                .entry(part.to_string())
+                .or_insert_with(|| Value::Table(Table::new()));
+            table = entry
+                .as_table_mut()
+                .ok_or_else(|| LocalizedError::new("config.error.not_a_table").with("key", part))?;
+        }
+        table.insert(last.to_string(), value);
+        Ok(())
+    }
+
+    /// Removes a key, returning whether it was set.
+    pub fn unset(&mut self, key: &str) -> bool {
+        let mut parts: Vec<&str> = key.split('.').collect();
+        let Some(last) = parts.pop() else {
+            return false;
+        };
+        let mut table = &mut self.table;
+        for part in parts {
+            match table.get_mut(part).and_then(Value::as_table_mut) {
+                Some(inner) => table = inner,
+                None => return false,
+            }
+        }
+        table.remove(last).is_some()
+    }
+
+    /// Every leaf value as `(dotted key, TOML text)`, sorted by key.
+    pub fn entries(&self) -> Vec<(String, String)> {
+        let mut entries = Vec::new();
+        flatten("", &self.table, &mut entries);
+        entries.sort();
+        entries
+    }
+
+    pub fn language(&self) -> Option<Language> {
+        self.get("language")
+            .and_then(Value::as_str)
+            .and_then(Language::from_code)
+    }
+
+    pub fn set_language(&mut self, lang: Language) {
+        self.table.insert(
+            "language".to_string(),
+            Value::String(lang.code().to_string()),
+        );
+    }
+
+    pub fn birthday(&self) -> Option<&str> {
+        self.get("birthday").and_then(Value::as_str)
+    }
+
+    pub fn set_birthday(&mut self, birthday: &str) -> Result<(), LocalizedError> {
+        self.set("birthday", birthday)
+    }
+}
+
+fn known_key(name: &str) -> Option<&'static KnownKey> {
+    KNOWN_KEYS.iter().find(|key| key.name == name)
+}
+
+fn validate_language(value: &Value) -> Result<(), LocalizedError> {
+    let text = expect_string(value)?;
+    let codes: Vec<&str> = available_languages()
+        .iter()
+        .map(|lang| lang.code())
+        .collect();
+    Language::from_code(text)
+        .filter(|lang| is_supported(*lang))
+        .map(|_| ())
+        .ok_or_else(|| {
+            LocalizedError::new("config.error.unsupported_language")
+                .with("language", text)
+                .with("available", codes.join(", "))
+        })
+}
+
+fn validate_birthday(value: &Value) -> Result<(), LocalizedError> {
+    let text = expect_string(value)?;
+    NaiveDate::parse_from_str(text.trim(), "%Y-%m-%d")
+        .map(|_| ())
+        .map_err(|_| LocalizedError::new("config.error.invalid_date").with("date", text))
+}
+
+fn validate_window_hours(value: &Value) -> Result<(), LocalizedError> {
+    match value.as_integer() {
+        Some(hours) if (1..=24 * 366).contains(&hours) => Ok(()),
+        Some(hours) => Err(LocalizedError::new("config.error.hours_out_of_range")
+            .with("hours", hours)
+            .with("max", 24 * 366)),
+        None => Err(LocalizedError::new("config.error.expected_hours")),
+    }
+}
+
+fn validate_globs(value: &Value) -> Result<(), LocalizedError> {
+    let expected = || LocalizedError::new("config.error.expected_globs");
+    let patterns = value.as_array().ok_or_else(expected)?;
+    for pattern in patterns {
+        let pattern = pattern.as_str().ok_or_else(expected)?;
+        globset::Glob::new(pattern).map_err(|e| {
+            LocalizedError::new("config.error.invalid_glob")
+                .with("pattern", pattern)
+                .with("error", e)
+        })?;
+    }
+    Ok(())
+}
+
+fn validate_probes(value: &Value) -> Result<(), LocalizedError> {
+    let expected = || LocalizedError::new("config.error.expected_probes");
+    let probes = value.as_array().ok_or_else(expected)?;
+    for probe in probes {
+        let probe = probe.as_str().ok_or_else(expected)?;
+        probe
+            .parse::<ProbeKind>()
+            .map_err(|e| LocalizedError::new("config.error.invalid_probe").caused_by(e))?;
+    }
+    Ok(())
+}
+
+fn validate_minutes(value: &Value) -> Result<(), LocalizedError> {
+    match value.as_integer() {
+        Some(minutes) if minutes >= 0 && u32::try_from(minutes).is_ok() => Ok(()),
+        Some(minutes) => {
+            Err(LocalizedError::new("config.error.invalid_minutes").with("minutes", minutes))
+        }
+        None => Err(LocalizedError::new("config.error.expected_minutes")),
+    }
+}
+
+fn validate_seconds(value: &Value) -> Result<(), LocalizedError> {
+    match value.as_integer() {
+        Some(seconds) if seconds >= 0 => Ok(()),
+        Some(seconds) => {
+            Err(LocalizedError::new("config.error.invalid_seconds").with("seconds", seconds))
+        }
+        None => Err(LocalizedError::new("config.error.expected_seconds")),
+    }
+}
+
+fn validate_custom_probes(value: &Value) -> Result<(), LocalizedError> {
+    let probes = value
+        .as_array()
+        .ok_or_else(|| LocalizedError::new("config.error.expected_custom_probes"))?;
+    for probe in probes {
+        let invalid = LocalizedError::new("config.error.invalid_custom_probe");
+        let probe: CustomProbe = probe
+            .clone()
+            .try_into()
+            .map_err(|e: toml::de::Error| invalid.clone().with("error", e.message()))?;
+        probe.validate().map_err(|e| invalid.caused_by(e))?;
+    }
+    Ok(())
+}
+
+fn expect_string(value: &Value) -> Result<&str, LocalizedError> {
+    value
+        .as_str()
+        .ok_or_else(|| LocalizedError::new("config.error.expected_string"))
+}
+
+/// Stores known keys in their canonical spelling, e.g. `chinese` as `zh`.
+fn normalize(key: &str, value: Value) -> Value {
+    match (key, value.as_str()) {
+        ("language", Some(text)) => Language::from_code(text)
+            .map(|lang| Value::String(lang.code().to_string()))
+            .unwrap_or(value),
+        ("birthday", Some(text)) => Value::String(text.trim().to_string()),
+        _ => value,
+    }
+}
+
+fn parse_value(raw: &str) -> Value {
+    let raw = raw.trim();
+    format!("value = {}", raw)
+        .parse::<Table>()
+        .ok()
+        .and_then(|mut table| table.remove("value"))
+        .unwrap_or_else(|| Value::String(raw.to_string()))
+}
+
+fn flatten(prefix: &str, table: &Table, entries: &mut Vec<(String, String)>) {
+    for (key, value) in table {
+        let name = if prefix.is_empty() {
+            key.clone()
+        } else {
+            format!("{}.{}", prefix, key)
+        };
+        match value {
+            Value::Table(inner) => flatten(&name, inner, entries),
+            _ => entries.push((name, value.to_string())),
+        }
+    }
+}
+
+fn line_of_offset(content: &str, offset: usize) -> usize {
+    content[..offset.min(content.len())].matches('\n').count() + 1
+}
+
+/// Prefixes a validation error with the key and, when found, its line.
+fn locate(content: &str, key: &str, error: LocalizedError) -> LocalizedError {
+    let error = LocalizedError::new("config.error.key")
+        .with("key", key)
+        .caused_by(error);
+    match line_of_key(content, key) {
+        Some(line) => at_line(line, error),
+        None => error,
+    }
+}
+
+fn at_line(line: usize, error: LocalizedError) -> LocalizedError {
+    LocalizedError::new("config.error.line")
+        .with("line", line)
+        .caused_by(error)
+}
+
+/// Finds the line defining a dotted key, following `[section]` headers.
+fn line_of_key(content: &str, key: &str) -> Option<usize> {
+    let mut section = String::new();
+    for (index, line) in content.lines().enumerate() {
+        let line = line.trim();
+        if let Some(header) = line.strip_prefix('[').and_then(|l| l.strip_suffix(']')) {
+            // `[[pressure.custom_probes]]` defines the key itself.
+            section = header.trim_matches(['[', ']']).trim().to_string();
+            if section == key {
+                return Some(index + 1);
+            }
+            continue;
+        }
+        if let Some((name, _)) = line.split_once('=') {
+            let name = name.trim().trim_matches('"');
+            let full = if section.is_empty() {
+                name.to_string()
+            } else {
+                format!("{}.{}", section, name)
+            };
+            if full == key {
+                return Some(index + 1);
+            }
+        }
+    }
+    None
+}
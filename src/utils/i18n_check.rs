@@ -0,0 +1,241 @@
+use crate::utils::i18n::{plural_categories, I18n, LocalizedError, Message};
+use crate::utils::Language;
+use regex::Regex;
+use serde::Serialize;
+use std::collections::{BTreeMap, BTreeSet};
+use std::fs;
+use std::path::Path;
+use std::sync::LazyLock;
+
+/// What `findme i18n check` found wrong with the translations.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct TranslationReport {
+    /// Keys another locale has but this one lacks, by language tag.
+    pub missing: BTreeMap<String, Vec<String>>,
+    /// Keys the code asks for that no locale has, such as a misspelt `fortune.excelent`.
+    pub undefined: Vec<String>,
+    /// Keys no string in the code can produce; `None` when no sources were scanned.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub unused: Option<Vec<String>>,
+    /// Messages whose `{name}` placeholders differ from the English ones.
+    pub placeholder_mismatches: Vec<PlaceholderMismatch>,
+    /// Plural messages without a form their language needs.
+    pub plural_gaps: Vec<PluralGap>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct PlaceholderMismatch {
+    pub key: String,
+    pub language: String,
+    pub expected: Vec<String>,
+    pub found: Vec<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct PluralGap {
+    pub key: String,
+    pub language: String,
+    /// Categories of [`plural_categories`] the message has no form for.
+    pub missing: Vec<String>,
+}
+
+impl TranslationReport {
+    pub fn is_clean(&self) -> bool {
+        self.missing.is_empty()
+            && self.undefined.is_empty()
+            && self.unused.as_ref().is_none_or(|unused| unused.is_empty())
+            && self.placeholder_mismatches.is_empty()
+            && self.plural_gaps.is_empty()
+    }
+}
+
+/// Compares the locales of `i18n` with each other and, given the contents of the source
+/// files, with the keys the code uses.
+///
+/// A key counts as used when a string literal in the sources equals it, or when a literal
+/// with `{}` in it, like `"pressure.advice.{}.risk"` passed to `format!`, matches it. Only
+/// literals passed straight to `i18n(...)` and `i18n_args(...)` are checked for being
+/// undefined; keys picked from tables are caught at runtime by the warning debug builds
+/// print.
+pub fn check_translations(i18n: &I18n, sources: Option<&[String]>) -> TranslationReport {
+    let languages = i18n.languages();
+    let mut all_keys = BTreeSet::new();
+    for lang in &languages {
+        if let Some(messages) = i18n.messages(*lang) {
+            all_keys.extend(messages.keys().cloned());
+        }
+    }
+
+    let mut report = TranslationReport::default();
+    for lang in &languages {
+        let Some(messages) = i18n.messages(*lang) else {
+            continue;
+        };
+        let missing: Vec<String> = all_keys
+            .iter()
+            .filter(|key| !messages.contains_key(*key))
+            .cloned()
+            .collect();
+        if !missing.is_empty() {
+            report.missing.insert(lang.code().to_string(), missing);
+        }
+    }
+
+    let english = i18n.messages(Language::ENGLISH);
+    for lang in &languages {
+        let Some(messages) = i18n.messages(*lang) else {
+            continue;
+        };
+        for (key, message) in messages {
+            if let Message::Plural(forms) = message {
+                let missing: Vec<String> = plural_categories(*lang)
+                    .iter()
+                    .filter(|category| !forms.contains_key(**category))
+                    .map(|category| category.to_string())
+                    .collect();
+                if !missing.is_empty() {
+                    report.plural_gaps.push(PluralGap {
+                        key: key.clone(),
+                        language: lang.code().to_string(),
+                        missing,
+                    });
+                }
+            }
+            if *lang == Language::ENGLISH {
+                continue;
+            }
+            let Some(reference) = english.and_then(|english| english.get(key)) else {
+                continue;
+            };
+            let expected = placeholder_names(reference);
+            let found = placeholder_names(message);
+            if expected != found {
+                report.placeholder_mismatches.push(PlaceholderMismatch {
+                    key: key.clone(),
+                    language: lang.code().to_string(),
+                    expected,
+                    found,
+                });
+            }
+        }
+    }
+    report
+        .placeholder_mismatches
+        .sort_by(|a, b| (&a.key, &a.language).cmp(&(&b.key, &b.language)));
+    report
+        .plural_gaps
+        .sort_by(|a, b| (&a.key, &a.language).cmp(&(&b.key, &b.language)));
+
+    if let Some(sources) = sources {
+        let usage = SourceUsage::scan(sources);
+        report.unused = Some(
+            all_keys
+                .iter()
+                .filter(|key| !usage.uses(key))
+                .cloned()
+                .collect(),
+        );
+        report.undefined = usage
+            .requested
+            .iter()
+            .filter(|key| !all_keys.contains(*key))
+            .cloned()
+            .collect();
+    }
+    report
+}
+
+/// The contents of every `.rs` file below `dir`, for [`check_translations`].
+pub fn read_sources(dir: &Path) -> Result<Vec<String>, LocalizedError> {
+    let mut sources = Vec::new();
+    let mut pending = vec![dir.to_path_buf()];
+    while let Some(dir) = pending.pop() {
+        let entries = fs::read_dir(&dir).map_err(|e| LocalizedError::read(&dir, e))?;
+        for entry in entries.flatten() {
+            let path = entry.path();
+            if path.is_dir() {
+                pending.push(path);
+            } else if path.extension().is_some_and(|ext| ext == "rs") {
+                sources
+                    .push(fs::read_to_string(&path).map_err(|e| LocalizedError::read(&path, e))?);
+            }
+        }
+    }
+    Ok(sources)
+}
+
+/// Sorted names of the `{name}` placeholders in any form of a message.
+pub fn placeholder_names(message: &Message) -> Vec<String> {
+    static PLACEHOLDER: LazyLock<Regex> =
+        LazyLock::new(|| Regex::new(r"\{([a-z_]+)\}").expect("placeholder pattern is valid"));
+    let names: BTreeSet<String> = message
+        .texts()
+        .into_iter()
+        .flat_map(|text| PLACEHOLDER.captures_iter(text))
+        .map(|captures| captures[1].to_string())
+        .collect();
+    names.into_iter().collect()
+}
+
+/// Key-shaped string literals found in the sources.
+struct SourceUsage {
+    /// Literals without placeholders.
+    literals: BTreeSet<String>,
+    /// Literals with `{}` in them, as patterns matching the keys they can become.
+    patterns: Vec<Regex>,
+    /// Keys passed straight to `i18n(...)`, `i18n_args(...)` or `LocalizedError::new(...)`,
+    /// or to a closure that prefixes them, like `fill("timeout", ...)` for
+    /// `pressure.error.timeout`.
+    requested: BTreeSet<String>,
+}
+
+impl SourceUsage {
+    fn scan(sources: &[String]) -> SourceUsage {
+        let literal = Regex::new(r#""([a-z][a-z0-9_]*(?:\.(?:[a-z0-9_]+|\{\}))+)""#)
+            .expect("literal pattern is valid");
+        let call = Regex::new(r#"\b(?:i18n|i18n_args|LocalizedError::new)\(\s*"([^"]+)""#)
+            .expect("call pattern is valid");
+        // A closure whose `&str` argument is formatted into a key prefix, like the one
+        // `PressureError::message` calls `fill`.
+        let helper = Regex::new(
+            r#"let\s+(\w+)\s*=\s*\|\w+:\s*&str[^|]*\|\s*\{?\s*i18n(?:_args)?\(\s*&format!\(\s*"([^"{}]*)\{\}""#,
+        )
+        .expect("helper pattern is valid");
+
+        let mut usage = SourceUsage {
+            literals: BTreeSet::new(),
+            patterns: Vec::new(),
+            requested: BTreeSet::new(),
+        };
+        for source in sources {
+            for captures in literal.captures_iter(source) {
+                let text = &captures[1];
+                if text.contains("{}") {
+                    let pattern = regex::escape(text).replace(r"\{\}", "[a-z0-9_.]+");
+                    usage.patterns.push(
+                        Regex::new(&format!("^{}$", pattern)).expect("escaped literal is valid"),
+                    );
+                } else {
+                    usage.literals.insert(text.to_string());
+                }
+            }
+            for captures in call.captures_iter(source) {
+                usage.requested.insert(captures[1].to_string());
+            }
+            for helper in helper.captures_iter(source) {
+                let calls = Regex::new(&format!(r#"\b{}\(\s*"([^"]+)""#, &helper[1]))
+                    .expect("helper call pattern is valid");
+                for captures in calls.captures_iter(source) {
+                    usage
+                        .requested
+                        .insert(format!("{}{}", &helper[2], &captures[1]));
+                }
+            }
+        }
+        usage
+    }
+
+    fn uses(&self, key: &str) -> bool {
+        self.literals.contains(key) || self.patterns.iter().any(|pattern| pattern.is_match(key))
+    }
+}
@@ -1,11 +1,95 @@
+pub mod config;
 pub mod i18n;
+pub mod i18n_check;
 pub mod user;
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
-pub enum Language {
-    Chinese,
-    English,
+use std::fmt;
+use std::sync::Mutex;
+
+/// A locale such as `en`, `zh`, `ja` or `zh-TW`.
+///
+/// Tags are normalized (`zh_tw` becomes `zh-TW`) and interned, so a `Language` is as cheap
+/// to copy as the old two-language enum. Translations fall back along [`Language::fallback_chain`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Language(&'static str);
+
+impl Language {
+    pub const CHINESE: Language = Language("zh");
+    pub const ENGLISH: Language = Language("en");
+
+    /// Parses a language tag such as `de`, `zh-TW` or `pt_BR`, or the names `chinese` and `english`.
+    pub fn from_code(code: &str) -> Option<Language> {
+        let code = code.trim();
+        match code.to_lowercase().as_str() {
+            "chinese" => return Some(Language::CHINESE),
+            "english" => return Some(Language::ENGLISH),
+            _ => {}
+        }
+
+        let mut parts = code.split(['-', '_']);
+        let language = parts.next()?;
+        if !(2..=3).contains(&language.len()) || !language.chars().all(|c| c.is_ascii_alphabetic())
+        {
+            return None;
+        }
+        let mut tag = language.to_ascii_lowercase();
+        for part in parts {
+            if !(2..=8).contains(&part.len()) || !part.chars().all(|c| c.is_ascii_alphanumeric()) {
+                return None;
+            }
+            tag.push('-');
+            match part.len() {
+                // Region, e.g. `TW`.
+                2 => tag.push_str(&part.to_ascii_uppercase()),
+                // Script, e.g. `Hant`.
+                4 => {
+                    tag.push_str(&part[..1].to_ascii_uppercase());
+                    tag.push_str(&part[1..].to_ascii_lowercase());
+                }
+                _ => tag.push_str(&part.to_ascii_lowercase()),
+            }
+        }
+        Some(Language::intern(tag))
+    }
+
+    pub fn code(&self) -> &'static str {
+        self.0
+    }
+
+    /// Locales tried for a message, most specific first: `zh-Hant-TW`, `zh-Hant`, `zh`, then
+    /// English, which has every message.
+    pub fn fallback_chain(&self) -> Vec<Language> {
+        let mut chain = vec![*self];
+        let mut tag = self.0;
+        while let Some((parent, _)) = tag.rsplit_once('-') {
+            tag = parent;
+            chain.push(Language::intern(tag.to_string()));
+        }
+        if !chain.contains(&Language::ENGLISH) {
+            chain.push(Language::ENGLISH);
+        }
+        chain
+    }
+
+    /// The `'static` copy of `tag`, leaked once per distinct tag.
+    fn intern(tag: String) -> Language {
+        static TAGS: Mutex<Vec<&'static str>> = Mutex::new(Vec::new());
+        let mut tags = TAGS.lock().unwrap_or_else(|e| e.into_inner());
+        if let Some(existing) = tags.iter().find(|existing| **existing == tag) {
+            return Language(existing);
+        }
+        let tag: &'static str = Box::leak(tag.into_boxed_str());
+        tags.push(tag);
+        Language(tag)
+    }
+}
+
+impl fmt::Display for Language {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.0)
+    }
 }
 
-pub use i18n::i18n;
-pub use user::detect_user_name;
+pub use config::{config_dir, UserConfig};
+pub use i18n::{available_languages, i18n, i18n_args, is_supported, LocalizedError};
+pub use user::{detect_system_language, detect_user_name, language_from_locale};
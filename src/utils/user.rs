@@ -1,3 +1,4 @@
+use crate::utils::{is_supported, Language};
 use std::env;
 
 /// Detects the display name for the current user.
@@ -20,3 +21,28 @@ pub fn detect_user_name() -> String {
 
     "Developer".to_string()
 }
+
+/// Locale variables in POSIX precedence: `LC_ALL` overrides `LC_MESSAGES`, which overrides `LANG`.
+const LOCALE_VARIABLES: [&str; 3] = ["LC_ALL", "LC_MESSAGES", "LANG"];
+
+/// The interface language from the system locale, if findme has translations for it.
+///
+/// The first of `LC_ALL`, `LC_MESSAGES` and `LANG` that is set decides, as it does for
+/// other programs.
+pub fn detect_system_language() -> Option<Language> {
+    LOCALE_VARIABLES
+        .iter()
+        .filter_map(|key| env::var(key).ok())
+        .find(|value| !value.trim().is_empty())
+        .and_then(|value| language_from_locale(&value))
+}
+
+/// The language of a locale such as `zh_CN.UTF-8` or `de_DE@euro`; `None` for `C`, `POSIX`
+/// and locales without translations.
+pub fn language_from_locale(locale: &str) -> Option<Language> {
+    let name = locale.trim().split(['.', '@']).next()?;
+    if name == "C" || name == "POSIX" {
+        return None;
+    }
+    Language::from_code(name).filter(|lang| is_supported(*lang))
+}
@@ -0,0 +1,164 @@
+use crate::models::fortune_pack::DIMENSION_NAMES;
+use crate::models::Fortune;
+use crate::utils::{i18n, Language, LocalizedError};
+use crate::views::daily_fortune_view::{dimension_label, score_color, weekday_label};
+use chrono::{Datelike, Duration, NaiveDate, Weekday};
+use colored::*;
+use std::cmp::Reverse;
+use std::fmt;
+use std::str::FromStr;
+
+const CELL_WIDTH: usize = 10;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CalendarSpan {
+    Week,
+    Month,
+}
+
+impl FromStr for CalendarSpan {
+    type Err = LocalizedError;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value.to_lowercase().as_str() {
+            "week" => Ok(CalendarSpan::Week),
+            "month" => Ok(CalendarSpan::Month),
+            _ => Err(LocalizedError::new("calendar.error.unknown_span").with("span", value)),
+        }
+    }
+}
+
+impl fmt::Display for CalendarSpan {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CalendarSpan::Week => f.write_str("week"),
+            CalendarSpan::Month => f.write_str("month"),
+        }
+    }
+}
+
+impl CalendarSpan {
+    /// First and last day of the Monday-based week or the month containing `anchor`.
+    pub fn range(&self, anchor: NaiveDate) -> (NaiveDate, NaiveDate) {
+        match self {
+            CalendarSpan::Week => {
+                let monday =
+                    anchor - Duration::days(anchor.weekday().num_days_from_monday() as i64);
+                (monday, monday + Duration::days(6))
+            }
+            CalendarSpan::Month => {
+                let first = anchor.with_day(1).unwrap();
+                let next_month = if first.month() == 12 {
+                    NaiveDate::from_ymd_opt(first.year() + 1, 1, 1)
+                } else {
+                    NaiveDate::from_ymd_opt(first.year(), first.month() + 1, 1)
+                }
+                .unwrap();
+                (first, next_month - Duration::days(1))
+            }
+        }
+    }
+}
+
+/// Lays out consecutive daily fortunes as a Monday-first calendar grid.
+pub fn display_calendar(fortunes: &[Fortune], lang: Language) {
+    let (Some(first), Some(last)) = (fortunes.first(), fortunes.last()) else {
+        return;
+    };
+
+    println!();
+    println!("{}", "=".repeat(CELL_WIDTH * 7).cyan());
+    println!(
+        "{}",
+        format!(
+            "{} ~ {} · {}",
+            first.date.format("%Y-%m-%d"),
+            last.date.format("%Y-%m-%d"),
+            i18n("calendar.title", lang)
+        )
+        .bold()
+        .yellow()
+    );
+    println!("{}", "=".repeat(CELL_WIDTH * 7).cyan());
+
+    let weekdays = [
+        Weekday::Mon,
+        Weekday::Tue,
+        Weekday::Wed,
+        Weekday::Thu,
+        Weekday::Fri,
+        Weekday::Sat,
+        Weekday::Sun,
+    ];
+    let header: String = weekdays
+        .iter()
+        .map(|weekday| pad(&weekday_label(*weekday, lang), CELL_WIDTH))
+        .collect();
+    println!("{}", header.trim_end().bold());
+
+    let leading_blanks = first.date.weekday().num_days_from_monday() as usize;
+    let mut cells: Vec<Option<&Fortune>> = vec![None; leading_blanks];
+    cells.extend(fortunes.iter().map(Some));
+
+    for week in cells.chunks(7) {
+        let mut day_line = String::new();
+        let mut dimension_line = String::new();
+        for cell in week {
+            match cell {
+                Some(fortune) => {
+                    let color = score_color(fortune.overall_score);
+                    let day = format!("{:>2} {:>3}", fortune.date.day(), fortune.overall_score);
+                    day_line.push_str(&pad(&day, CELL_WIDTH).color(color).to_string());
+                    let short = short_dimension_label(fortune.dimensions.primary_dimension(), lang);
+                    dimension_line.push_str(&pad(&short, CELL_WIDTH).color(color).to_string());
+                }
+                None => {
+                    day_line.push_str(&" ".repeat(CELL_WIDTH));
+                    dimension_line.push_str(&" ".repeat(CELL_WIDTH));
+                }
+            }
+        }
+        println!("{}", day_line.trim_end());
+        println!("{}", dimension_line.trim_end());
+        println!();
+    }
+
+    println!("{}", i18n("calendar.best_days_label", lang).bold().yellow());
+    for dimension in DIMENSION_NAMES {
+        if let Some(best) = fortunes
+            .iter()
+            .max_by_key(|fortune| (fortune.dimensions.value(dimension), Reverse(fortune.date)))
+        {
+            println!(
+                "  {}: {} {} ({})",
+                dimension_label(dimension, lang),
+                best.date.format("%Y-%m-%d"),
+                weekday_label(best.date.weekday(), lang),
+                best.dimensions.value(dimension)
+            );
+        }
+    }
+
+    println!("{}", "=".repeat(CELL_WIDTH * 7).cyan());
+    println!();
+}
+
+fn short_dimension_label(dimension: &str, lang: Language) -> String {
+    let key = match dimension {
+        "focus" => "calendar.short.focus",
+        "creativity" => "calendar.short.creativity",
+        "debugging" => "calendar.short.debugging",
+        "collaboration" => "calendar.short.collaboration",
+        _ => "calendar.short.risk",
+    };
+    i18n(key, lang)
+}
+
+/// Pads to a terminal column width, counting CJK characters as two columns.
+fn pad(text: &str, width: usize) -> String {
+    let used: usize = text
+        .chars()
+        .map(|c| if c >= '\u{2E80}' { 2 } else { 1 })
+        .sum();
+    format!("{}{}", text, " ".repeat(width.saturating_sub(used)))
+}
@@ -1,12 +1,12 @@
-use crate::models::Fortune;
-use crate::utils::{detect_user_name, i18n, Language};
-use chrono::Local;
+use crate::models::{AdjustmentReason, Fortune};
+use crate::utils::{detect_user_name, i18n, i18n_args, Language};
+use chrono::{Datelike, Weekday};
 use colored::*;
 
 pub fn display_fortune(fortune: &Fortune, lang: Language) {
     println!();
     println!("{}", "=".repeat(60).cyan());
-    let today = Local::now().format("%Y-%m-%d");
+    let today = fortune.date.format("%Y-%m-%d");
     println!(
         "{}",
         format!("{} · {}", today, i18n("app.title", lang))
@@ -17,23 +17,13 @@ pub fn display_fortune(fortune: &Fortune, lang: Language) {
     let user_name = detect_user_name();
     println!(
         "{}",
-        i18n("app.welcome", lang)
-            .replace("{}", &user_name)
-            .color("bright_blue")
+        i18n_args("app.welcome", lang, &[("name", &user_name)]).color("bright_blue")
     );
 
     println!("{}", "=".repeat(60).cyan());
     println!();
 
-    let score_color = match fortune.overall_score {
-        90..=100 => "bright_green",
-        80..=89 => "green",
-        70..=79 => "yellow",
-        60..=69 => "bright_yellow",
-        50..=59 => "bright_red",
-        40..=49 => "red",
-        _ => "red",
-    };
+    let score_color = score_color(fortune.overall_score);
 
     println!(
         "{}: {}",
@@ -48,28 +38,31 @@ pub fn display_fortune(fortune: &Fortune, lang: Language) {
     display_dimension_bar(
         i18n("fortune.focus_label", lang),
         fortune.dimensions.focus,
-        lang,
+        &adjustment_note(fortune, "focus"),
     );
     display_dimension_bar(
         i18n("fortune.creativity_label", lang),
         fortune.dimensions.creativity,
-        lang,
+        &adjustment_note(fortune, "creativity"),
     );
     display_dimension_bar(
         i18n("fortune.debugging_label", lang),
         fortune.dimensions.debugging,
-        lang,
+        &adjustment_note(fortune, "debugging"),
     );
     display_dimension_bar(
         i18n("fortune.collaboration_label", lang),
         fortune.dimensions.collaboration,
-        lang,
+        &adjustment_note(fortune, "collaboration"),
     );
     display_dimension_bar(
         i18n("fortune.risk_label", lang),
         fortune.dimensions.risk_tolerance,
-        lang,
+        &adjustment_note(fortune, "risk_tolerance"),
     );
+    if !fortune.adjustments.is_empty() {
+        println!("  {}", i18n("fortune.adjusted_note", lang).bright_black());
+    }
     println!();
 
     println!(
@@ -100,29 +93,98 @@ pub fn display_fortune(fortune: &Fortune, lang: Language) {
     println!();
 }
 
-fn display_dimension_bar(label: String, value: u8, _lang: Language) {
+/// ` -20 🧪` for each pressure adjustment of `dimension`.
+fn adjustment_note(fortune: &Fortune, dimension: &str) -> String {
+    fortune
+        .adjustments
+        .iter()
+        .filter(|adjustment| adjustment.dimension == dimension)
+        .map(|adjustment| {
+            let emoji = match adjustment.reason {
+                AdjustmentReason::Churn => "📝",
+                AdjustmentReason::Tests => "🧪",
+                AdjustmentReason::Build => "⚡",
+                AdjustmentReason::Conflicts => "💥",
+            };
+            format!(" {:+} {}", adjustment.delta, emoji)
+        })
+        .collect()
+}
+
+fn display_dimension_bar(label: String, value: u8, note: &str) {
     let bar_length = 20;
     let filled_length = (value as f32 / 100.0 * bar_length as f32) as usize;
     let empty_length = bar_length - filled_length;
 
-    let bar_color = match value {
-        90..=100 => "bright_green",
-        80..=89 => "green",
-        70..=79 => "yellow",
-        60..=69 => "bright_yellow",
-        50..=59 => "bright_red",
-        40..=49 => "red",
-        _ => "red",
-    };
+    let bar_color = score_color(value);
 
     let filled_bar = "█".repeat(filled_length).color(bar_color);
     let empty_bar = "░".repeat(empty_length).color("bright_black");
 
     println!(
-        "  {}: {}{} {}",
+        "  {}: {}{} {}{}",
         label,
         filled_bar,
         empty_bar,
-        format!("{:3}", value).color(bar_color)
+        format!("{:3}", value).color(bar_color),
+        note.bright_black()
     );
 }
+
+/// Prints one compact row per fortune, used for `--from/--to` ranges.
+pub fn display_fortune_rows(fortunes: &[Fortune], lang: Language) {
+    println!();
+    for fortune in fortunes {
+        let color = score_color(fortune.overall_score);
+        let bar_length = 10;
+        let filled_length = (fortune.overall_score as f32 / 100.0 * bar_length as f32) as usize;
+
+        println!(
+            "{} {}  {} {}{}  {}  {}",
+            fortune.date.format("%Y-%m-%d"),
+            weekday_label(fortune.date.weekday(), lang),
+            format!("{:3}", fortune.overall_score).color(color).bold(),
+            "█".repeat(filled_length).color(color),
+            "░".repeat(bar_length - filled_length).color("bright_black"),
+            dimension_label(fortune.dimensions.primary_dimension(), lang),
+            fortune.message.green()
+        );
+    }
+    println!();
+}
+
+/// Localized label for a dimension name such as `"focus"` or `"risk_tolerance"`.
+pub fn dimension_label(dimension: &str, lang: Language) -> String {
+    match dimension {
+        "focus" => i18n("fortune.focus_label", lang),
+        "creativity" => i18n("fortune.creativity_label", lang),
+        "debugging" => i18n("fortune.debugging_label", lang),
+        "collaboration" => i18n("fortune.collaboration_label", lang),
+        _ => i18n("fortune.risk_label", lang),
+    }
+}
+
+pub fn weekday_label(weekday: Weekday, lang: Language) -> String {
+    let key = match weekday {
+        Weekday::Mon => "weekday.mon",
+        Weekday::Tue => "weekday.tue",
+        Weekday::Wed => "weekday.wed",
+        Weekday::Thu => "weekday.thu",
+        Weekday::Fri => "weekday.fri",
+        Weekday::Sat => "weekday.sat",
+        Weekday::Sun => "weekday.sun",
+    };
+    i18n(key, lang)
+}
+
+pub fn score_color(score: u8) -> &'static str {
+    match score {
+        90..=100 => "bright_green",
+        80..=89 => "green",
+        70..=79 => "yellow",
+        60..=69 => "bright_yellow",
+        50..=59 => "bright_red",
+        40..=49 => "red",
+        _ => "red",
+    }
+}
@@ -0,0 +1,210 @@
+use crate::models::dev_pressure::{DevPressure, PressureLevel};
+use crate::models::pressure_score::ScoreMetric;
+use crate::models::repo_dashboard::RepoPressure;
+use crate::utils::i18n::{i18n, i18n_args};
+use crate::utils::Language;
+use colored::*;
+use std::path::Path;
+
+/// Prints one row per repository, most pressured first, with failed analyses last.
+pub fn display_pressure_dashboard(repos: &[RepoPressure], lang: Language) {
+    println!();
+    println!("{}", "=".repeat(60).bright_blue());
+    println!("{}", i18n("dashboard.title", lang).bright_blue().bold());
+    println!("{}", "=".repeat(60).bright_blue());
+
+    if repos.is_empty() {
+        println!("{}", i18n("dashboard.no_repos", lang));
+        println!();
+        return;
+    }
+
+    let names = repo_names(repos);
+    let rows: Vec<[String; 5]> = repos
+        .iter()
+        .zip(&names)
+        .map(|(repo, name)| match &repo.result {
+            Ok(pressure) => [
+                format!(
+                    "{} {:>3}",
+                    level_emoji(pressure.pressure_level),
+                    pressure.pressure_score
+                ),
+                name.clone(),
+                metric(pressure, ScoreMetric::Churn, lang),
+                metric(pressure, ScoreMetric::Tests, lang),
+                metric(pressure, ScoreMetric::Build, lang),
+            ],
+            Err(_) => [
+                "❌   -".to_string(),
+                name.clone(),
+                "-".to_string(),
+                "-".to_string(),
+                "-".to_string(),
+            ],
+        })
+        .collect();
+
+    let headers = [
+        i18n("dashboard.score", lang),
+        i18n("dashboard.repo", lang),
+        i18n("dashboard.churn", lang),
+        i18n("dashboard.tests", lang),
+        i18n("dashboard.build", lang),
+    ];
+    let widths: Vec<usize> = (0..headers.len())
+        .map(|column| {
+            rows.iter()
+                .map(|row| display_width(&row[column]))
+                .chain([display_width(&headers[column])])
+                .max()
+                .unwrap_or(0)
+        })
+        .collect();
+
+    let header: Vec<String> = headers
+        .iter()
+        .zip(&widths)
+        .map(|(text, width)| pad(text, *width))
+        .collect();
+    println!(
+        "  {}  {}",
+        header.join("  ").bold(),
+        i18n("dashboard.status", lang).bold()
+    );
+
+    for (repo, row) in repos.iter().zip(&rows) {
+        let cells: Vec<String> = row
+            .iter()
+            .zip(&widths)
+            .map(|(text, width)| pad(text, *width))
+            .collect();
+        match &repo.result {
+            Ok(pressure) => {
+                let level = level_text(pressure.pressure_level, lang)
+                    .color(level_color(pressure.pressure_level))
+                    .bold();
+                // The first problem is enough to tell which probe to look at.
+                let problem = pressure
+                    .errors
+                    .first()
+                    .map(|e| format!(" ⚠️ {}", first_line(&e.message(lang))))
+                    .unwrap_or_default();
+                println!(
+                    "  {}  {}{}",
+                    cells.join("  "),
+                    level,
+                    problem.bright_yellow()
+                );
+            }
+            Err(e) => println!(
+                "  {}  {}",
+                cells.join("  "),
+                i18n_args(
+                    "dashboard.error",
+                    lang,
+                    &[("error", &first_line(&e.message(lang)))]
+                )
+                .red()
+            ),
+        }
+    }
+
+    let failed = repos.iter().filter(|repo| repo.result.is_err()).count();
+    println!();
+    println!(
+        "{}",
+        i18n_args(
+            "dashboard.summary",
+            lang,
+            &[("count", &repos.len()), ("failed", &failed)]
+        )
+        .bright_black()
+    );
+    println!("{}", "=".repeat(60).bright_blue());
+    println!();
+}
+
+/// Directory names, or full paths when two repositories share a name.
+fn repo_names(repos: &[RepoPressure]) -> Vec<String> {
+    let name = |repo: &Path| {
+        repo.file_name()
+            .map(|name| name.to_string_lossy().into_owned())
+            .unwrap_or_else(|| repo.display().to_string())
+    };
+    let names: Vec<String> = repos.iter().map(|repo| name(&repo.repo)).collect();
+    repos
+        .iter()
+        .zip(&names)
+        .map(|(repo, short)| {
+            if names.iter().filter(|other| *other == short).count() > 1 {
+                repo.repo.display().to_string()
+            } else {
+                short.clone()
+            }
+        })
+        .collect()
+}
+
+/// The measured value of `metric`, or `-` when its probe did not run.
+fn metric(pressure: &DevPressure, metric: ScoreMetric, lang: Language) -> String {
+    let Some(contribution) = pressure
+        .score_breakdown
+        .iter()
+        .find(|contribution| contribution.metric == metric)
+    else {
+        return "-".to_string();
+    };
+    match metric {
+        ScoreMetric::Churn => format!("{}", contribution.value),
+        ScoreMetric::Tests => format!("{:.0}%", contribution.value * 100.0),
+        ScoreMetric::Build if contribution.failed => i18n("dashboard.build_failed", lang),
+        ScoreMetric::Build => format!("{:.1}s", contribution.value),
+    }
+}
+
+fn level_emoji(level: PressureLevel) -> &'static str {
+    match level {
+        PressureLevel::Low => "🟢",
+        PressureLevel::Medium => "🟡",
+        PressureLevel::High => "🟠",
+        PressureLevel::Critical => "🔴",
+    }
+}
+
+fn level_text(level: PressureLevel, lang: Language) -> String {
+    match level {
+        PressureLevel::Low => i18n("dashboard.level.low", lang),
+        PressureLevel::Medium => i18n("dashboard.level.medium", lang),
+        PressureLevel::High => i18n("dashboard.level.high", lang),
+        PressureLevel::Critical => i18n("dashboard.level.critical", lang),
+    }
+}
+
+fn level_color(level: PressureLevel) -> &'static str {
+    match level {
+        PressureLevel::Low => "green",
+        PressureLevel::Medium => "yellow",
+        PressureLevel::High => "bright_red",
+        PressureLevel::Critical => "red",
+    }
+}
+
+fn first_line(text: &str) -> &str {
+    text.lines().next().unwrap_or_default()
+}
+
+/// Terminal columns taken by `text`; CJK characters and emoji take two.
+fn display_width(text: &str) -> usize {
+    text.chars()
+        .map(|c| if c as u32 >= 0x1100 { 2 } else { 1 })
+        .sum()
+}
+
+fn pad(text: &str, width: usize) -> String {
+    format!(
+        "{}{}",
+        text,
+        " ".repeat(width.saturating_sub(display_width(text)))
+    )
+}
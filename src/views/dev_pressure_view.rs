@@ -1,9 +1,15 @@
-use crate::models::dev_pressure::{DevPressure, PressureLevel};
-use crate::utils::i18n::i18n;
+use crate::models::build_timings::{BuildKind, BuildTimings};
+use crate::models::dev_pressure::{DevPressure, PressureLevel, ProbeKind};
+use crate::models::git_repo::RepoSignals;
+use crate::models::pressure_score::ScoreMetric;
+use crate::models::probes::{ProbeResult, BUILD_FAILED, BUILD_SECONDS};
+use crate::models::test_results::TestRunSummary;
+use crate::utils::i18n::{i18n, i18n_args};
 use crate::utils::Language;
 use colored::*;
 
-pub fn display_dev_pressure(pressure: &DevPressure, lang: Language) {
+/// Prints the pressure index; `explain` adds the points behind the score.
+pub fn display_dev_pressure(pressure: &DevPressure, explain: bool, lang: Language) {
     println!();
     println!("{}", "=".repeat(60).bright_blue());
     println!("{}", i18n("pressure.title", lang).bright_blue().bold());
@@ -24,27 +30,61 @@ pub fn display_dev_pressure(pressure: &DevPressure, lang: Language) {
     };
 
     println!(
-        "{} {} {}",
+        "{} {} {} {}",
         pressure_emoji,
         i18n("pressure.level_label", lang).bold(),
-        pressure_text.bold()
+        pressure_text.bold(),
+        format!("({}/100)", pressure.pressure_score).bright_black()
     );
     println!();
 
     println!("{}", i18n("pressure.metrics_label", lang).bold());
-    println!(
-        "  {} {} {}",
-        "📝".bright_cyan(),
-        i18n("pressure.git_diff_label", lang),
-        format!(
-            "{} {}",
-            pressure.git_diff_lines,
-            i18n("pressure.lines", lang)
-        )
-        .bright_white()
-    );
+    let churn = &pressure.git_churn;
+    if pressure.failed_probes.contains(&ProbeKind::Git) {
+        println!(
+            "  {} {} {}",
+            "📝".bright_cyan(),
+            i18n("pressure.git_diff_label", lang),
+            i18n("pressure.probe_failed", lang).bright_red()
+        );
+    } else {
+        println!(
+            "  {} {} {} {}",
+            "📝".bright_cyan(),
+            i18n("pressure.git_diff_label", lang),
+            i18n_args(
+                "pressure.lines",
+                lang,
+                &[("count", &pressure.git_diff_lines)]
+            )
+            .bright_white(),
+            format!(
+                "(+{} / -{}, {}, {})",
+                churn.added,
+                churn.deleted,
+                i18n_args("pressure.files", lang, &[("count", &churn.files)]),
+                i18n_args("pressure.window", lang, &[("hours", &churn.window_hours)])
+            )
+            .bright_black()
+        );
+    }
+    if churn.uncommitted > 0 {
+        println!(
+            "  {} {} {}",
+            "✏️".bright_cyan(),
+            i18n("pressure.uncommitted_label", lang),
+            i18n_args("pressure.lines", lang, &[("count", &churn.uncommitted)]).bright_white()
+        );
+    }
 
-    if pressure.has_tests {
+    if !pressure.probes.contains(&ProbeKind::Tests) {
+        println!(
+            "  {} {} {}",
+            "🧪".bright_cyan(),
+            i18n("pressure.test_success_label", lang),
+            skipped(ProbeKind::Tests, lang)
+        );
+    } else if pressure.has_tests {
         let success_color = if pressure.test_success_rate >= 0.9 {
             "bright_green"
         } else if pressure.test_success_rate >= 0.7 {
@@ -53,38 +93,111 @@ pub fn display_dev_pressure(pressure: &DevPressure, lang: Language) {
             "bright_red"
         };
 
+        let counts = pressure
+            .tests
+            .as_ref()
+            .filter(|tests| tests.compiled())
+            .map(|tests| {
+                format!(
+                    " ({} {}, {} {}, {} {})",
+                    tests.passed(),
+                    i18n("pressure.passed", lang),
+                    tests.failed(),
+                    i18n("pressure.failed", lang),
+                    tests.ignored(),
+                    i18n("pressure.ignored", lang)
+                )
+            })
+            .unwrap_or_default();
         println!(
-            "  {} {} {}",
+            "  {} {} {}{}",
             "🧪".bright_cyan(),
             i18n("pressure.test_success_label", lang),
-            format!("{:.1}%", pressure.test_success_rate * 100.0).color(success_color)
+            format!("{:.1}%", pressure.test_success_rate * 100.0).color(success_color),
+            (counts
+                + &probe_names(pressure, ProbeKind::Tests)
+                + &cached(pressure, ProbeKind::Tests, lang))
+                .bright_black()
         );
+        if let Some(tests) = &pressure.tests {
+            display_test_problems(tests, lang);
+        }
     } else {
+        let message = if pressure.timed_out_probes.contains(&ProbeKind::Tests) {
+            i18n("pressure.probe_timed_out", lang)
+        } else if pressure.failed_probes.contains(&ProbeKind::Tests) {
+            i18n("pressure.probe_failed", lang)
+        } else if ran(pressure, ProbeKind::Tests) {
+            i18n("pressure.no_tests", lang)
+        } else {
+            i18n("pressure.no_test_runner", lang)
+        };
         println!(
             "  {} {} {}",
             "🧪".bright_cyan(),
             i18n("pressure.test_success_label", lang),
-            i18n("pressure.no_tests", lang).bright_yellow()
+            message.bright_yellow()
         );
     }
 
-    let build_color = if pressure.build_time_seconds <= 15 {
-        "bright_green"
-    } else if pressure.build_time_seconds <= 30 {
-        "bright_yellow"
+    if !pressure.probes.contains(&ProbeKind::Build) {
+        println!(
+            "  {} {} {}",
+            "⚡".bright_cyan(),
+            i18n("pressure.build_time_label", lang),
+            skipped(ProbeKind::Build, lang)
+        );
+    } else if !ran(pressure, ProbeKind::Build) {
+        let message = if pressure.timed_out_probes.contains(&ProbeKind::Build) {
+            i18n("pressure.probe_timed_out", lang)
+        } else if pressure.failed_probes.contains(&ProbeKind::Build) {
+            i18n("pressure.probe_failed", lang)
+        } else {
+            i18n("pressure.no_build_tool", lang)
+        };
+        println!(
+            "  {} {} {}",
+            "⚡".bright_cyan(),
+            i18n("pressure.build_time_label", lang),
+            message.bright_yellow()
+        );
     } else {
-        "bright_red"
-    };
-
-    println!(
-        "  {} {} {}",
-        "⚡".bright_cyan(),
-        i18n("pressure.build_time_label", lang),
-        format!("{}s", pressure.build_time_seconds).color(build_color)
-    );
+        for result in pressure
+            .results
+            .iter()
+            .filter(|result| result.kind == ProbeKind::Build)
+        {
+            display_build(pressure, result, lang);
+        }
+    }
 
     println!();
 
+    if let Some(repo) = &pressure.repo {
+        display_repo_signals(repo, lang);
+    }
+
+    if !pressure.errors.is_empty() {
+        println!("{}", i18n("pressure.problems_label", lang).bold());
+        for error in &pressure.errors {
+            let message = error.message(lang);
+            let mut lines = message.lines();
+            println!(
+                "  {} {}",
+                "•".bright_red(),
+                lines.next().unwrap_or_default().bright_yellow()
+            );
+            for line in lines {
+                println!("    {}", line.bright_black());
+            }
+        }
+        println!();
+    }
+
+    if explain {
+        display_score_breakdown(pressure, lang);
+    }
+
     println!("{}", i18n("pressure.advice_label", lang).bold());
     println!(
         "  {} {}",
@@ -99,3 +212,338 @@ pub fn display_dev_pressure(pressure: &DevPressure, lang: Language) {
 
     println!("{}", "=".repeat(60).bright_blue());
 }
+
+fn display_score_breakdown(pressure: &DevPressure, lang: Language) {
+    println!("{}", i18n("pressure.explain_label", lang).bold());
+    if pressure.score_breakdown.is_empty() {
+        println!(
+            "  {}",
+            i18n("pressure.explain.nothing", lang).bright_black()
+        );
+    }
+
+    for contribution in &pressure.score_breakdown {
+        let (emoji, key, value) = match contribution.metric {
+            ScoreMetric::Churn => (
+                "📝",
+                "pressure.explain.churn",
+                i18n_args("pressure.lines", lang, &[("count", &contribution.value)]),
+            ),
+            ScoreMetric::Tests => (
+                "🧪",
+                "pressure.explain.tests",
+                format!("{:.1}%", contribution.value * 100.0),
+            ),
+            ScoreMetric::Build => (
+                "⚡",
+                "pressure.explain.build",
+                duration(contribution.value as f32),
+            ),
+        };
+        let reason = if contribution.failed {
+            i18n("pressure.explain.failed", lang)
+        } else {
+            i18n_args(
+                "pressure.explain.thresholds",
+                lang,
+                &[("count", &contribution.thresholds_crossed)],
+            )
+        };
+        println!(
+            "  {} {} {} {} {}",
+            emoji.bright_cyan(),
+            i18n(key, lang),
+            value.bright_white(),
+            format!(
+                "→ {:.1} / {:.1} {}",
+                contribution.points,
+                contribution.max_points,
+                i18n("pressure.explain.points", lang)
+            )
+            .bright_yellow(),
+            format!("({})", reason).bright_black()
+        );
+    }
+
+    let model = &pressure.scoring_model;
+    let levels = i18n_args(
+        "pressure.explain.levels",
+        lang,
+        &[
+            ("medium", &model.medium),
+            ("high", &model.high),
+            ("critical", &model.critical),
+        ],
+    );
+    println!(
+        "  {} {} {}",
+        "=".bright_cyan(),
+        i18n("pressure.explain.total", lang),
+        format!("{}/100 ({})", pressure.pressure_score, levels).bright_white()
+    );
+
+    let source = match &pressure.scoring_file {
+        Some(path) => path.display().to_string(),
+        None => i18n("pressure.explain.default_model", lang),
+    };
+    println!(
+        "  {}",
+        i18n_args("pressure.explain.model", lang, &[("source", &source)]).bright_black()
+    );
+    println!();
+}
+
+fn skipped(probe: ProbeKind, lang: Language) -> ColoredString {
+    i18n_args("pressure.skipped", lang, &[("probe", &probe.name())]).bright_black()
+}
+
+/// Whether any probe of `kind` found its tool.
+fn ran(pressure: &DevPressure, kind: ProbeKind) -> bool {
+    pressure.results.iter().any(|result| result.kind == kind)
+}
+
+/// ` [cargo, npm]` for the probes of `kind` that ran.
+fn probe_names(pressure: &DevPressure, kind: ProbeKind) -> String {
+    let names: Vec<&str> = pressure
+        .results
+        .iter()
+        .filter(|result| result.kind == kind)
+        .map(|result| result.probe.as_str())
+        .collect();
+    if names.is_empty() {
+        String::new()
+    } else {
+        format!(" [{}]", names.join(", "))
+    }
+}
+
+fn cached(pressure: &DevPressure, probe: ProbeKind, lang: Language) -> String {
+    if pressure.cached_probes.contains(&probe) {
+        format!(" {}", i18n("pressure.cached", lang))
+    } else {
+        String::new()
+    }
+}
+
+fn display_build(pressure: &DevPressure, result: &ProbeResult, lang: Language) {
+    let tool = format!(" [{}]", result.probe);
+    let cached = cached(pressure, ProbeKind::Build, lang);
+
+    if let Some(builds) = &result.builds {
+        println!(
+            "  {} {} {} {}",
+            "⚡".bright_cyan(),
+            i18n("pressure.build_time_label", lang),
+            duration(builds.latest.seconds).color(build_color(builds.latest.seconds)),
+            format!(
+                "({}){}{}",
+                build_kind(builds.latest.kind, lang),
+                tool,
+                cached
+            )
+            .bright_black()
+        );
+        display_build_medians(builds, lang);
+    } else if let Some(seconds) = result.metric(BUILD_SECONDS) {
+        let failed = if result
+            .metric(BUILD_FAILED)
+            .is_some_and(|failed| failed > 0.0)
+        {
+            format!(" {}", i18n("pressure.build.failed", lang))
+        } else {
+            String::new()
+        };
+        println!(
+            "  {} {} {}{}{}",
+            "⚡".bright_cyan(),
+            i18n("pressure.build_time_label", lang),
+            duration(seconds as f32).color(build_color(seconds as f32)),
+            failed.bright_red(),
+            (tool + &cached).bright_black()
+        );
+    } else {
+        println!(
+            "  {} {} {}{}",
+            "⚡".bright_cyan(),
+            i18n("pressure.build_time_label", lang),
+            i18n("pressure.build.none", lang).bright_yellow(),
+            (tool + &cached).bright_black()
+        );
+    }
+}
+
+fn build_color(seconds: f32) -> &'static str {
+    if seconds <= 15.0 {
+        "bright_green"
+    } else if seconds <= 30.0 {
+        "bright_yellow"
+    } else {
+        "bright_red"
+    }
+}
+
+fn display_build_medians(builds: &BuildTimings, lang: Language) {
+    let mut details = Vec::new();
+    if let Some(seconds) = builds.clean_median_seconds {
+        details.push(format!(
+            "{} {}",
+            build_kind(BuildKind::Clean, lang),
+            duration(seconds)
+        ));
+    }
+    if let Some(seconds) = builds.incremental_median_seconds {
+        details.push(format!(
+            "{} {}",
+            build_kind(BuildKind::Incremental, lang),
+            duration(seconds)
+        ));
+    }
+    details.push(i18n_args(
+        "pressure.build.count",
+        lang,
+        &[("count", &builds.recorded_builds)],
+    ));
+
+    println!(
+        "    {} {} {} {}",
+        "📊".bright_cyan(),
+        i18n("pressure.build.median_label", lang),
+        duration(builds.median_seconds).bright_white(),
+        format!("({})", details.join(", ")).bright_black()
+    );
+}
+
+fn build_kind(kind: BuildKind, lang: Language) -> String {
+    match kind {
+        BuildKind::Clean => i18n("pressure.build.clean", lang),
+        BuildKind::Incremental => i18n("pressure.build.incremental", lang),
+    }
+}
+
+/// `8.4s` below a minute, `2m 05s` above.
+fn duration(seconds: f32) -> String {
+    if seconds < 60.0 {
+        format!("{:.1}s", seconds)
+    } else {
+        let whole = seconds.round() as u32;
+        format!("{}m {:02}s", whole / 60, whole % 60)
+    }
+}
+
+/// How many failing tests or compile errors the pressure view lists.
+const TOP_PROBLEMS: usize = 5;
+
+fn display_test_problems(tests: &TestRunSummary, lang: Language) {
+    if !tests.compiled() {
+        println!(
+            "    {} {}",
+            "🛑".bright_red(),
+            i18n("pressure.compile_errors_label", lang).bright_red()
+        );
+        for error in tests.compile_errors.iter().take(TOP_PROBLEMS) {
+            println!("      {}", error.bright_white());
+        }
+        print_remaining(tests.compile_errors.len(), lang);
+        return;
+    }
+
+    let failing: Vec<(&str, &str)> = tests.failing_tests().collect();
+    if failing.is_empty() {
+        return;
+    }
+    println!(
+        "    {} {}",
+        "❌".bright_red(),
+        i18n("pressure.failing_tests_label", lang).bright_red()
+    );
+    for (binary, test) in failing.iter().take(TOP_PROBLEMS) {
+        println!(
+            "      {} {}",
+            test.bright_white(),
+            format!("[{}]", binary).bright_black()
+        );
+    }
+    print_remaining(failing.len(), lang);
+}
+
+fn print_remaining(total: usize, lang: Language) {
+    if total > TOP_PROBLEMS {
+        println!(
+            "      {}",
+            i18n_args("pressure.more", lang, &[("count", &(total - TOP_PROBLEMS))]).bright_black()
+        );
+    }
+}
+
+fn display_repo_signals(repo: &RepoSignals, lang: Language) {
+    println!("{}", i18n("pressure.repo_label", lang).bold());
+
+    let branch = repo
+        .branch
+        .clone()
+        .unwrap_or_else(|| i18n("pressure.detached", lang));
+    let upstream = match (repo.ahead, repo.behind) {
+        (Some(ahead), Some(behind)) => format!(" ↑{} ↓{}", ahead, behind),
+        _ => String::new(),
+    };
+    println!(
+        "  {} {} {}{}",
+        "🌿".bright_cyan(),
+        i18n("pressure.branch_label", lang),
+        branch.bright_white(),
+        upstream.bright_black()
+    );
+
+    if let Some(age) = repo.last_commit_age_seconds {
+        println!(
+            "  {} {} {}",
+            "🕒".bright_cyan(),
+            i18n("pressure.last_commit_label", lang),
+            commit_age(age, lang).bright_white()
+        );
+    }
+
+    if let Some(operation) = &repo.in_progress {
+        println!(
+            "  {} {} {}",
+            "🚧".bright_cyan(),
+            i18n("pressure.in_progress_label", lang),
+            operation.bright_yellow()
+        );
+    }
+    if repo.conflicted_files > 0 {
+        println!(
+            "  {} {} {}",
+            "💥".bright_cyan(),
+            i18n("pressure.conflicts_label", lang),
+            format!("{}", repo.conflicted_files).bright_red()
+        );
+    }
+    if repo.untracked_files > 0 {
+        println!(
+            "  {} {} {}",
+            "❓".bright_cyan(),
+            i18n("pressure.untracked_label", lang),
+            format!("{}", repo.untracked_files).bright_white()
+        );
+    }
+    if repo.stash_count > 0 {
+        println!(
+            "  {} {} {}",
+            "📦".bright_cyan(),
+            i18n("pressure.stash_label", lang),
+            format!("{}", repo.stash_count).bright_white()
+        );
+    }
+
+    println!();
+}
+
+fn commit_age(seconds: u64, lang: Language) -> String {
+    let (key, value) = match seconds {
+        0..=3599 => ("pressure.age.minutes", seconds / 60),
+        3600..=86399 => ("pressure.age.hours", seconds / 3600),
+        _ => ("pressure.age.days", seconds / 86400),
+    };
+    i18n_args(key, lang, &[("count", &value)])
+}
@@ -0,0 +1,112 @@
+use crate::models::fortune_pack::DIMENSION_NAMES;
+use crate::models::history::{sparkline, HistoryStats, Streak, HIGH_SCORE_THRESHOLD};
+use crate::models::Fortune;
+use crate::utils::{i18n, i18n_args, Language};
+use crate::views::daily_fortune_view::{dimension_label, score_color, weekday_label};
+use chrono::Datelike;
+use colored::*;
+
+/// Prints the journal entries in `entries` with their summary statistics.
+pub fn display_history(entries: &[Fortune], stats: &HistoryStats, lang: Language) {
+    println!();
+    println!("{}", "=".repeat(60).cyan());
+    println!("{}", i18n("history.title", lang).bold().yellow());
+    println!("{}", "=".repeat(60).cyan());
+    println!();
+
+    if entries.is_empty() {
+        println!("{}", i18n("history.empty", lang));
+        println!();
+        return;
+    }
+
+    for entry in entries {
+        println!(
+            "  {} {}  {}  {}",
+            entry.date.format("%Y-%m-%d"),
+            weekday_label(entry.date.weekday(), lang),
+            format!("{:3}", entry.overall_score)
+                .color(score_color(entry.overall_score))
+                .bold(),
+            dimension_label(entry.dimensions.primary_dimension(), lang)
+        );
+    }
+    println!();
+
+    let scores: Vec<u8> = entries.iter().map(|entry| entry.overall_score).collect();
+    println!(
+        "{}: {}",
+        i18n("history.trend_label", lang),
+        sparkline(&scores).cyan()
+    );
+    println!(
+        "{}: {}",
+        i18n("history.average_label", lang),
+        format!("{}", stats.average_score)
+            .color(score_color(stats.average_score))
+            .bold()
+    );
+    if let (Some(best), Some(worst)) = (stats.best, stats.worst) {
+        println!(
+            "{}: {} ({})",
+            i18n("history.best_label", lang),
+            format!("{}", best.score).color(score_color(best.score)),
+            best.date.format("%Y-%m-%d")
+        );
+        println!(
+            "{}: {} ({})",
+            i18n("history.worst_label", lang),
+            format!("{}", worst.score).color(score_color(worst.score)),
+            worst.date.format("%Y-%m-%d")
+        );
+    }
+    println!();
+
+    println!(
+        "{}",
+        i18n_args(
+            "history.streaks_label",
+            lang,
+            &[("score", &HIGH_SCORE_THRESHOLD)]
+        )
+        .bold()
+        .yellow()
+    );
+    println!(
+        "  {}: {}",
+        i18n("history.current_streak_label", lang),
+        streak_text(stats.current_streak, lang)
+    );
+    println!(
+        "  {}: {}",
+        i18n("history.longest_streak_label", lang),
+        streak_text(stats.longest_streak, lang)
+    );
+    println!();
+
+    println!("{}", i18n("history.dimensions_label", lang).bold().yellow());
+    for dimension in DIMENSION_NAMES {
+        let average = stats.dimension_averages.value(dimension);
+        println!(
+            "  {}: {}",
+            dimension_label(dimension, lang),
+            format!("{}", average).color(score_color(average))
+        );
+    }
+    println!();
+
+    println!("{}", "=".repeat(60).cyan());
+    println!();
+}
+
+fn streak_text(streak: Option<Streak>, lang: Language) -> String {
+    match streak {
+        Some(streak) => format!(
+            "{} ({} ~ {})",
+            i18n_args("history.streak_days", lang, &[("count", &streak.days)]),
+            streak.start.format("%Y-%m-%d"),
+            streak.end.format("%Y-%m-%d")
+        ),
+        None => i18n("history.no_streak", lang),
+    }
+}
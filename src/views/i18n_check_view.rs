@@ -0,0 +1,86 @@
+use crate::utils::i18n::{i18n, i18n_args};
+use crate::utils::i18n_check::TranslationReport;
+use crate::utils::Language;
+use colored::*;
+
+/// Prints what `findme i18n check` found, one section per kind of problem.
+pub fn display_translation_report(report: &TranslationReport, lang: Language) {
+    println!();
+    println!("{}", "=".repeat(60).bright_blue());
+    println!("{}", i18n("i18n_check.title", lang).bright_blue().bold());
+    println!("{}", "=".repeat(60).bright_blue());
+
+    for (language, keys) in &report.missing {
+        let heading = i18n_args(
+            "i18n_check.missing",
+            lang,
+            &[("language", language), ("count", &keys.len())],
+        );
+        print_keys(&heading, keys);
+    }
+    if !report.undefined.is_empty() {
+        print_keys(&i18n("i18n_check.undefined", lang), &report.undefined);
+    }
+    match &report.unused {
+        Some(unused) if !unused.is_empty() => {
+            print_keys(&i18n("i18n_check.unused", lang), unused);
+        }
+        Some(_) => {}
+        None => println!("\n{}", i18n("i18n_check.no_sources", lang).dimmed()),
+    }
+    if !report.placeholder_mismatches.is_empty() {
+        println!(
+            "\n{}",
+            i18n("i18n_check.placeholders", lang).yellow().bold()
+        );
+        for mismatch in &report.placeholder_mismatches {
+            println!(
+                "  {} [{}]: {}",
+                mismatch.key,
+                mismatch.language,
+                i18n_args(
+                    "i18n_check.placeholder_counts",
+                    lang,
+                    &[
+                        ("found", &placeholder_list(&mismatch.found)),
+                        ("expected", &placeholder_list(&mismatch.expected)),
+                    ]
+                )
+            );
+        }
+    }
+    if !report.plural_gaps.is_empty() {
+        println!("\n{}", i18n("i18n_check.plural_gaps", lang).yellow().bold());
+        for gap in &report.plural_gaps {
+            println!(
+                "  {} [{}]: {}",
+                gap.key,
+                gap.language,
+                gap.missing.join(", ")
+            );
+        }
+    }
+
+    if report.is_clean() {
+        println!("\n{}", i18n("i18n_check.clean", lang).green());
+    }
+    println!();
+}
+
+fn print_keys(heading: &str, keys: &[String]) {
+    println!("\n{}", heading.yellow().bold());
+    for key in keys {
+        println!("  {}", key);
+    }
+}
+
+fn placeholder_list(names: &[String]) -> String {
+    if names.is_empty() {
+        return "-".to_string();
+    }
+    names
+        .iter()
+        .map(|name| format!("{{{}}}", name))
+        .collect::<Vec<_>>()
+        .join(" ")
+}
@@ -1,4 +1,13 @@
+pub mod calendar_view;
 pub mod daily_fortune_view;
+pub mod dashboard_view;
 pub mod dev_pressure_view;
+pub mod history_view;
+pub mod i18n_check_view;
+pub mod pressure_history_view;
+pub mod progress_view;
+pub mod report_view;
+pub mod team_view;
 
 pub use daily_fortune_view::*;
+pub use report_view::*;
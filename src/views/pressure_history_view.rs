@@ -0,0 +1,183 @@
+use crate::models::pressure_history::{
+    bar_chart, daily_values, PressureSnapshot, Regression, TrendMetric,
+};
+use crate::utils::{i18n, i18n_args, Language};
+use crate::views::daily_fortune_view::weekday_label;
+use chrono::{DateTime, Datelike, Duration, Local, NaiveDate};
+use colored::*;
+use std::path::Path;
+
+/// Rows per chart.
+const CHART_HEIGHT: usize = 4;
+/// Width of the y-axis labels.
+const AXIS_WIDTH: usize = 6;
+
+/// Prints one chart per metric over the `days` days ending on `today`, then any regressions.
+pub fn display_pressure_history(
+    repo: &Path,
+    snapshots: &[PressureSnapshot],
+    regressions: &[Regression],
+    today: NaiveDate,
+    days: u32,
+    lang: Language,
+) {
+    println!();
+    println!("{}", "=".repeat(60).cyan());
+    println!("{}", i18n("pressure_history.title", lang).bold().yellow());
+    println!("{}", "=".repeat(60).cyan());
+    println!(
+        "{}",
+        i18n_args("pressure_history.repo", lang, &[("path", &repo.display())])
+    );
+
+    if snapshots.is_empty() {
+        println!();
+        println!("{}", i18n("pressure_history.empty", lang));
+        println!();
+        return;
+    }
+    println!(
+        "{}",
+        i18n_args(
+            "pressure_history.runs",
+            lang,
+            &[("count", &snapshots.len()), ("days", &days)]
+        )
+    );
+    println!();
+
+    for metric in TrendMetric::ALL {
+        display_chart(snapshots, metric, today, days, lang);
+    }
+
+    println!(
+        "{}",
+        i18n("pressure_history.regressions_label", lang)
+            .bold()
+            .yellow()
+    );
+    if regressions.is_empty() {
+        println!(
+            "  {}",
+            i18n("pressure_history.no_regressions", lang).green()
+        );
+    }
+    for regression in regressions {
+        println!("  {}", regression_text(regression, lang).red());
+    }
+    println!();
+    println!("{}", "=".repeat(60).cyan());
+    println!();
+}
+
+fn display_chart(
+    snapshots: &[PressureSnapshot],
+    metric: TrendMetric,
+    today: NaiveDate,
+    days: u32,
+    lang: Language,
+) {
+    let label = i18n(&format!("pressure_history.metric.{}", metric.name()), lang);
+    let latest = snapshots
+        .iter()
+        .rev()
+        .find_map(|snapshot| metric.value(snapshot));
+    let Some(latest) = latest else {
+        println!(
+            "{}  {}",
+            label.bold(),
+            i18n("pressure_history.no_data", lang).dimmed()
+        );
+        println!();
+        return;
+    };
+    println!(
+        "{}  {} {}",
+        label.bold(),
+        i18n("pressure_history.latest", lang),
+        format_value(metric, latest).bold()
+    );
+
+    let values = daily_values(snapshots, metric, today, days);
+    let max = match metric {
+        TrendMetric::Score => 100.0,
+        TrendMetric::PassRate => 1.0,
+        TrendMetric::Churn | TrendMetric::BuildTime => {
+            values.iter().flatten().copied().fold(0.0, f64::max)
+        }
+    };
+    let rows = bar_chart(&values, max, CHART_HEIGHT);
+    let last_row = rows.len() - 1;
+    for (index, row) in rows.iter().enumerate() {
+        let axis = match index {
+            0 => format_value(metric, max),
+            _ if index == last_row => format_value(metric, 0.0),
+            _ => String::new(),
+        };
+        println!("{:>width$} ┤{}", axis, row.cyan(), width = AXIS_WIDTH);
+    }
+    println!(
+        "{:>width$} └{}",
+        "",
+        "─".repeat(days as usize),
+        width = AXIS_WIDTH
+    );
+
+    let first = (today - Duration::days(days.saturating_sub(1) as i64))
+        .format("%m-%d")
+        .to_string();
+    let last = today.format("%m-%d").to_string();
+    let dates = if days as usize >= first.len() + last.len() + 2 {
+        format!(
+            "{}{:>width$}",
+            first,
+            last,
+            width = days as usize - first.len()
+        )
+    } else {
+        first
+    };
+    println!("{:>width$}  {}", "", dates.dimmed(), width = AXIS_WIDTH);
+    println!();
+}
+
+fn format_value(metric: TrendMetric, value: f64) -> String {
+    match metric {
+        TrendMetric::Score | TrendMetric::Churn => format!("{:.0}", value),
+        TrendMetric::PassRate => format!("{:.0}%", value * 100.0),
+        TrendMetric::BuildTime => format!("{:.1}s", value),
+    }
+}
+
+fn regression_text(regression: &Regression, lang: Language) -> String {
+    let since = since_text(regression.since, lang);
+    let from = format_value(regression.metric, regression.from);
+    let to = format_value(regression.metric, regression.to);
+    let key = format!("pressure_history.regression.{}", regression.metric.name());
+    // Only the build time message shows the ratio.
+    let ratio = format!("{:.1}", regression.to / regression.from);
+    i18n_args(
+        &key,
+        lang,
+        &[
+            ("since", &since),
+            ("ratio", &ratio),
+            ("from", &from),
+            ("to", &to),
+        ],
+    )
+}
+
+/// `Mon 06-03`, or the weekday alone within the last week.
+fn since_text(since: DateTime<Local>, lang: Language) -> String {
+    let now = Local::now();
+    if since.date_naive() == now.date_naive() {
+        return i18n("pressure_history.earlier_today", lang);
+    }
+    let weekday = weekday_label(since.weekday(), lang);
+    if (now - since).num_days() < 7 {
+        weekday
+    } else {
+        format!("{} {}", weekday, since.format("%m-%d"))
+    }
+}
@@ -0,0 +1,104 @@
+use crate::models::probe_command::ProbeControl;
+use crate::utils::i18n::{i18n, i18n_args};
+use crate::utils::Language;
+use std::io::{self, IsTerminal, Write};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::thread::{self, JoinHandle};
+use std::time::Duration;
+
+const FRAMES: [char; 10] = ['⠋', '⠙', '⠹', '⠸', '⠼', '⠴', '⠦', '⠧', '⠇', '⠏'];
+const FRAME_INTERVAL: Duration = Duration::from_millis(100);
+/// Running probes named on the line before the rest are counted.
+const MAX_NAMED_PROBES: usize = 3;
+
+/// Keeps a progress line on stderr while probes run; the line is cleared when dropped.
+pub struct ProbeSpinner {
+    done: Arc<AtomicBool>,
+    handle: Option<JoinHandle<()>>,
+}
+
+impl Drop for ProbeSpinner {
+    fn drop(&mut self) {
+        self.done.store(true, Ordering::SeqCst);
+        if let Some(handle) = self.handle.take() {
+            let _ = handle.join();
+        }
+    }
+}
+
+/// Shows which probes of `control` are running and for how long.
+///
+/// Draws nothing when stderr is not a terminal, so piped and JSON output stay clean.
+pub fn spawn_probe_spinner(control: &ProbeControl, lang: Language) -> ProbeSpinner {
+    let done = Arc::new(AtomicBool::new(false));
+    if !io::stderr().is_terminal() {
+        return ProbeSpinner { done, handle: None };
+    }
+
+    let control = control.clone();
+    let finished = done.clone();
+    let handle = thread::spawn(move || {
+        let mut frame = 0;
+        let mut drawn = false;
+        while !finished.load(Ordering::SeqCst) {
+            let running = control.running();
+            let mut stderr = io::stderr().lock();
+            if running.is_empty() {
+                if drawn {
+                    let _ = write!(stderr, "\r\x1b[2K");
+                    drawn = false;
+                }
+            } else {
+                let _ = write!(
+                    stderr,
+                    "\r\x1b[2K{} {}",
+                    FRAMES[frame % FRAMES.len()],
+                    progress_text(&running, control.is_cancelled(), lang)
+                );
+                drawn = true;
+                frame += 1;
+            }
+            let _ = stderr.flush();
+            drop(stderr);
+            thread::sleep(FRAME_INTERVAL);
+        }
+        if drawn {
+            let _ = write!(io::stderr(), "\r\x1b[2K");
+        }
+    });
+    ProbeSpinner {
+        done,
+        handle: Some(handle),
+    }
+}
+
+fn progress_text(running: &[(String, Duration)], cancelled: bool, lang: Language) -> String {
+    if cancelled {
+        return i18n("pressure.progress.cancelling", lang);
+    }
+    let mut names: Vec<String> = running
+        .iter()
+        .take(MAX_NAMED_PROBES)
+        .map(|(label, _)| label.clone())
+        .collect();
+    let more = running.len().saturating_sub(MAX_NAMED_PROBES);
+    if more > 0 {
+        names.push(i18n_args(
+            "pressure.progress.more",
+            lang,
+            &[("count", &more)],
+        ));
+    }
+    // The oldest probe is the one worth waiting on.
+    let seconds = running
+        .iter()
+        .map(|(_, elapsed)| elapsed.as_secs())
+        .max()
+        .unwrap_or(0);
+    i18n_args(
+        "pressure.progress.running",
+        lang,
+        &[("probes", &names.join(", ")), ("seconds", &seconds)],
+    )
+}
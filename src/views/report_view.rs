@@ -0,0 +1,254 @@
+use crate::models::build_timings::BuildTimings;
+use crate::models::dev_pressure::{DevPressure, PressureLevel, ProbeKind};
+use crate::models::git_repo::{GitChurn, RepoSignals};
+use crate::models::history::HistoryStats;
+use crate::models::pressure_error::PressureError;
+use crate::models::pressure_history::{PressureSnapshot, Regression};
+use crate::models::pressure_score::ScoreContribution;
+use crate::models::repo_dashboard::RepoPressure;
+use crate::models::team::TeamFortune;
+use crate::models::test_results::TestRunSummary;
+use crate::models::Fortune;
+use crate::utils::LocalizedError;
+use serde::Serialize;
+use std::fmt;
+use std::path::Path;
+use std::str::FromStr;
+
+/// Bumped whenever a field is renamed or removed from [`FortuneReport`].
+pub const REPORT_SCHEMA_VERSION: u32 = 1;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum OutputFormat {
+    #[default]
+    Text,
+    Json,
+    Yaml,
+    Toml,
+}
+
+impl FromStr for OutputFormat {
+    type Err = LocalizedError;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value.to_lowercase().as_str() {
+            "text" => Ok(OutputFormat::Text),
+            "json" => Ok(OutputFormat::Json),
+            "yaml" | "yml" => Ok(OutputFormat::Yaml),
+            "toml" => Ok(OutputFormat::Toml),
+            _ => Err(LocalizedError::new("report.error.unknown_format").with("format", value)),
+        }
+    }
+}
+
+impl fmt::Display for OutputFormat {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            OutputFormat::Text => "text",
+            OutputFormat::Json => "json",
+            OutputFormat::Yaml => "yaml",
+            OutputFormat::Toml => "toml",
+        };
+        f.write_str(name)
+    }
+}
+
+/// The document printed by `--format json|yaml|toml`.
+#[derive(Debug, Serialize)]
+pub struct FortuneReport<'a> {
+    pub schema_version: u32,
+    pub fortune: &'a Fortune,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub pressure: Option<PressureReport<'a>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub pressure_error: Option<&'a PressureError>,
+}
+
+impl<'a> FortuneReport<'a> {
+    pub fn new(fortune: &'a Fortune) -> Self {
+        FortuneReport {
+            schema_version: REPORT_SCHEMA_VERSION,
+            fortune,
+            pressure: None,
+            pressure_error: None,
+        }
+    }
+}
+
+/// The pressure part of a report: the documented fields of a [`DevPressure`], without
+/// the raw probe results, scoring model and cache bookkeeping it carries.
+#[derive(Debug, Serialize)]
+pub struct PressureReport<'a> {
+    pub commit: Option<&'a str>,
+    pub git_diff_lines: i32,
+    pub git_churn: &'a GitChurn,
+    pub repo: Option<&'a RepoSignals>,
+    pub test_success_rate: f32,
+    pub has_tests: bool,
+    pub tests: Option<&'a TestRunSummary>,
+    pub build_time_seconds: u32,
+    pub builds: Option<&'a BuildTimings>,
+    pub probes: &'a [ProbeKind],
+    pub pressure_level: PressureLevel,
+    pub pressure_score: u32,
+    pub score_breakdown: &'a [ScoreContribution],
+    pub scoring_file: Option<&'a Path>,
+    pub risk_threshold: &'a str,
+    pub patience_advice: &'a str,
+    pub errors: &'a [PressureError],
+    pub failed_probes: &'a [ProbeKind],
+    pub timed_out_probes: &'a [ProbeKind],
+}
+
+impl<'a> PressureReport<'a> {
+    pub fn new(pressure: &'a DevPressure) -> Self {
+        PressureReport {
+            commit: pressure.commit.as_deref(),
+            git_diff_lines: pressure.git_diff_lines,
+            git_churn: &pressure.git_churn,
+            repo: pressure.repo.as_ref(),
+            test_success_rate: pressure.test_success_rate,
+            has_tests: pressure.has_tests,
+            tests: pressure.tests.as_ref(),
+            build_time_seconds: pressure.build_time_seconds,
+            builds: pressure.builds.as_ref(),
+            probes: &pressure.probes,
+            pressure_level: pressure.pressure_level,
+            pressure_score: pressure.pressure_score,
+            score_breakdown: &pressure.score_breakdown,
+            scoring_file: pressure.scoring_file.as_deref(),
+            risk_threshold: &pressure.risk_threshold,
+            patience_advice: &pressure.patience_advice,
+            errors: &pressure.errors,
+            failed_probes: &pressure.failed_probes,
+            timed_out_probes: &pressure.timed_out_probes,
+        }
+    }
+}
+
+/// The document printed for `--from/--to` ranges in a structured format.
+#[derive(Debug, Serialize)]
+pub struct FortuneRangeReport<'a> {
+    pub schema_version: u32,
+    pub fortunes: &'a [Fortune],
+}
+
+impl<'a> FortuneRangeReport<'a> {
+    pub fn new(fortunes: &'a [Fortune]) -> Self {
+        FortuneRangeReport {
+            schema_version: REPORT_SCHEMA_VERSION,
+            fortunes,
+        }
+    }
+}
+
+/// The document printed by `findme team` in a structured format.
+#[derive(Debug, Serialize)]
+pub struct TeamReport<'a> {
+    pub schema_version: u32,
+    pub team: &'a TeamFortune,
+}
+
+impl<'a> TeamReport<'a> {
+    pub fn new(team: &'a TeamFortune) -> Self {
+        TeamReport {
+            schema_version: REPORT_SCHEMA_VERSION,
+            team,
+        }
+    }
+}
+
+/// The document printed by `findme history` in a structured format.
+#[derive(Debug, Serialize)]
+pub struct HistoryReport<'a> {
+    pub schema_version: u32,
+    pub stats: &'a HistoryStats,
+    pub entries: &'a [Fortune],
+}
+
+impl<'a> HistoryReport<'a> {
+    pub fn new(stats: &'a HistoryStats, entries: &'a [Fortune]) -> Self {
+        HistoryReport {
+            schema_version: REPORT_SCHEMA_VERSION,
+            stats,
+            entries,
+        }
+    }
+}
+
+/// The document printed by `findme pressure history` in a structured format.
+#[derive(Debug, Serialize)]
+pub struct PressureHistoryReport<'a> {
+    pub schema_version: u32,
+    pub repo: &'a Path,
+    pub snapshots: &'a [PressureSnapshot],
+    pub regressions: &'a [Regression],
+}
+
+impl<'a> PressureHistoryReport<'a> {
+    pub fn new(
+        repo: &'a Path,
+        snapshots: &'a [PressureSnapshot],
+        regressions: &'a [Regression],
+    ) -> Self {
+        PressureHistoryReport {
+            schema_version: REPORT_SCHEMA_VERSION,
+            repo,
+            snapshots,
+            regressions,
+        }
+    }
+}
+
+/// The document printed by `findme pressure` for several repositories in a structured format.
+#[derive(Debug, Serialize)]
+pub struct DashboardReport<'a> {
+    pub schema_version: u32,
+    pub repos: Vec<DashboardEntry<'a>>,
+}
+
+/// One repository of a [`DashboardReport`]; exactly one of `pressure` and `error` is set.
+#[derive(Debug, Serialize)]
+pub struct DashboardEntry<'a> {
+    pub repo: &'a Path,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub pressure: Option<PressureReport<'a>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<&'a PressureError>,
+}
+
+impl<'a> DashboardReport<'a> {
+    pub fn new(repos: &'a [RepoPressure]) -> Self {
+        DashboardReport {
+            schema_version: REPORT_SCHEMA_VERSION,
+            repos: repos
+                .iter()
+                .map(|repo| DashboardEntry {
+                    repo: &repo.repo,
+                    pressure: repo.result.as_ref().ok().map(PressureReport::new),
+                    error: repo.result.as_ref().err(),
+                })
+                .collect(),
+        }
+    }
+}
+
+/// Serializes `report` in a machine-readable format.
+///
+/// [`OutputFormat::Text`] is rendered by the colored views instead.
+pub fn render_report<T: Serialize>(
+    report: &T,
+    format: OutputFormat,
+) -> Result<String, LocalizedError> {
+    let failed = |error: &dyn fmt::Display| {
+        LocalizedError::new("report.error.serialize")
+            .with("format", format)
+            .with("error", error)
+    };
+    match format {
+        OutputFormat::Json => serde_json::to_string_pretty(report).map_err(|e| failed(&e)),
+        OutputFormat::Yaml => serde_norway::to_string(report).map_err(|e| failed(&e)),
+        OutputFormat::Toml => toml::to_string(report).map_err(|e| failed(&e)),
+        OutputFormat::Text => Err(LocalizedError::new("report.error.text")),
+    }
+}
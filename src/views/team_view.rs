@@ -0,0 +1,77 @@
+use crate::models::team::{TeamFortune, TOP_PAIRINGS};
+use crate::utils::{i18n, Language};
+use crate::views::daily_fortune_view::{dimension_label, score_color};
+use colored::*;
+
+pub fn display_team_fortune(team: &TeamFortune, lang: Language) {
+    println!();
+    println!("{}", "=".repeat(60).cyan());
+    let title = match &team.name {
+        Some(name) => format!("{} · {}", i18n("team.title", lang), name),
+        None => i18n("team.title", lang),
+    };
+    println!(
+        "{}",
+        format!("{} · {}", team.date.format("%Y-%m-%d"), title)
+            .bold()
+            .yellow()
+    );
+    println!("{}", "=".repeat(60).cyan());
+    println!();
+
+    println!(
+        "{}: {}",
+        i18n("team.score_label", lang),
+        format!("{}", team.team_score)
+            .color(score_color(team.team_score))
+            .bold()
+    );
+    println!();
+
+    println!("{}", i18n("team.members_label", lang).bold().yellow());
+    let name_width = team
+        .members
+        .iter()
+        .map(|member| member.name.chars().count())
+        .max()
+        .unwrap_or(0);
+    for member in &team.members {
+        let score = member.fortune.overall_score;
+        println!(
+            "  {:width$}  {}  {}",
+            member.name,
+            format!("{:3}", score).color(score_color(score)).bold(),
+            dimension_label(member.fortune.dimensions.primary_dimension(), lang),
+            width = name_width
+        );
+    }
+    println!();
+
+    if !team.pairings.is_empty() {
+        println!("{}", i18n("team.pairings_label", lang).bold().yellow());
+        for pairing in team.pairings.iter().take(TOP_PAIRINGS) {
+            println!(
+                "  {} ({}) + {} ({}) → {}",
+                pairing.first,
+                dimension_label(pairing.first_strength, lang),
+                pairing.second,
+                dimension_label(pairing.second_strength, lang),
+                format!("{}", pairing.synergy).color(score_color(pairing.synergy))
+            );
+        }
+        println!();
+    }
+
+    if let Some((first, second)) = &team.pair_programming {
+        println!(
+            "{}: {} & {}",
+            i18n("team.pair_programming_label", lang),
+            first.green().bold(),
+            second.green().bold()
+        );
+        println!();
+    }
+
+    println!("{}", "=".repeat(60).cyan());
+    println!();
+}
@@ -116,6 +116,186 @@ fn test_invalid_language() -> Result<(), Box<dyn std::error::Error>> {
     Ok(())
 }
 
+#[test]
+fn test_locale_file_in_config_dir_adds_a_language() -> Result<(), Box<dyn std::error::Error>> {
+    let temp_dir = TempDir::new()?;
+    fs::create_dir(temp_dir.path().join("locales"))?;
+    fs::write(
+        temp_dir.path().join("locales").join("ja.json"),
+        r#"{"language.name": "日本語", "app.title": "🎯 今日の開発運勢"}"#,
+    )?;
+
+    let mut cmd = command_with_config(&temp_dir)?;
+    cmd.args(["--language", "ja"]);
+    cmd.assert()
+        .success()
+        .stdout(predicate::str::contains("今日の開発運勢"))
+        // Untranslated messages fall back to English.
+        .stdout(predicate::str::contains("Welcome"));
+
+    let mut cmd = command_with_config(&temp_dir)?;
+    cmd.args(["--language", "zh-TW"]);
+    cmd.assert()
+        .success()
+        .stdout(predicate::str::contains("今日技术运势预测"));
+
+    let mut cmd = command_with_config(&temp_dir)?;
+    cmd.args(["--language", "de"]);
+    cmd.assert()
+        .failure()
+        .stderr(predicate::str::contains("Available languages: en, ja, zh"));
+
+    temp_dir.close()?;
+    Ok(())
+}
+
+#[test]
+fn test_i18n_check_lists_translation_problems() -> Result<(), Box<dyn std::error::Error>> {
+    let temp_dir = TempDir::new()?;
+    let src = temp_dir.path().join("src");
+    fs::create_dir(&src)?;
+    fs::write(src.join("main.rs"), r#"i18n("fortune.excelent", lang);"#)?;
+
+    let mut cmd = command_with_config(&temp_dir)?;
+    cmd.args(["i18n", "check", "--src"]).arg(&src);
+    cmd.assert()
+        .failure()
+        .stdout(predicate::str::contains("fortune.excelent"))
+        .stdout(predicate::str::contains("Keys no code uses"));
+
+    fs::create_dir(temp_dir.path().join("locales"))?;
+    fs::write(
+        temp_dir.path().join("locales").join("zh.json"),
+        r#"{"config.updated": "已更新"}"#,
+    )?;
+    let mut cmd = command_with_config(&temp_dir)?;
+    cmd.current_dir(temp_dir.path().join("locales"))
+        .args(["i18n", "check", "--format", "json"]);
+    let output = cmd.output()?;
+    assert_eq!(output.status.code(), Some(1));
+    let report: serde_json::Value = serde_json::from_slice(&output.stdout)?;
+    assert_eq!(report["placeholder_mismatches"][0]["key"], "config.updated");
+    assert!(report.get("unused").is_none());
+
+    temp_dir.close()?;
+    Ok(())
+}
+
+/// A command without `--language` whose locale comes from `vars` only.
+fn command_with_locale(
+    temp_dir: &TempDir,
+    vars: &[(&str, &str)],
+) -> Result<Command, Box<dyn std::error::Error>> {
+    let mut cmd = command_with_config(temp_dir)?;
+    for key in ["LC_ALL", "LC_MESSAGES", "LANG", "FINDME_NO_INTERACTIVE"] {
+        cmd.env_remove(key);
+    }
+    cmd.envs(vars.iter().copied());
+    Ok(cmd)
+}
+
+#[test]
+fn test_language_comes_from_the_system_locale() -> Result<(), Box<dyn std::error::Error>> {
+    let temp_dir = TempDir::new()?;
+
+    let mut cmd = command_with_locale(&temp_dir, &[("LANG", "zh_CN.UTF-8")])?;
+    cmd.assert()
+        .success()
+        .stdout(predicate::str::contains("今日技术运势预测"));
+    // A detected language is not saved, so it follows the locale.
+    assert!(!temp_dir.path().join("config.toml").exists());
+
+    let mut cmd = command_with_locale(
+        &temp_dir,
+        &[("LANG", "zh_CN.UTF-8"), ("LC_MESSAGES", "en_US.UTF-8")],
+    )?;
+    cmd.assert().success().stdout(predicate::str::contains(
+        "Developer's Daily Decompression Oracle",
+    ));
+
+    // Without a usable locale or a terminal (stdin is empty here), findme uses English
+    // instead of prompting.
+    let mut cmd = command_with_locale(&temp_dir, &[("LC_ALL", "C"), ("LANG", "zh_CN.UTF-8")])?;
+    cmd.assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "Developer's Daily Decompression Oracle",
+        ))
+        .stdout(predicate::str::contains("select language").not());
+
+    let mut cmd = command_with_locale(&temp_dir, &[("LANG", "fr_FR.UTF-8")])?;
+    cmd.assert().success().stdout(predicate::str::contains(
+        "Developer's Daily Decompression Oracle",
+    ));
+
+    // A saved language wins over the locale.
+    let mut cmd = command_with_config(&temp_dir)?;
+    cmd.args(["config", "set", "language", "en"]);
+    cmd.assert().success();
+    let mut cmd = command_with_locale(&temp_dir, &[("LANG", "zh_CN.UTF-8")])?;
+    cmd.assert().success().stdout(predicate::str::contains(
+        "Developer's Daily Decompression Oracle",
+    ));
+
+    temp_dir.close()?;
+    Ok(())
+}
+
+#[test]
+fn test_language_precedence() -> Result<(), Box<dyn std::error::Error>> {
+    let temp_dir = TempDir::new()?;
+    let english = "Developer's Daily Decompression Oracle";
+    let chinese = "今日技术运势预测";
+
+    // Nothing saved: the locale decides.
+    let mut cmd = command_with_locale(&temp_dir, &[("LANG", "zh_CN.UTF-8")])?;
+    cmd.assert()
+        .success()
+        .stdout(predicate::str::contains(chinese));
+
+    // A saved language beats the locale, in both directions.
+    let mut cmd = command_with_config(&temp_dir)?;
+    cmd.args(["config", "set", "language", "zh"]);
+    cmd.assert().success();
+    let mut cmd = command_with_locale(&temp_dir, &[("LANG", "en_US.UTF-8")])?;
+    cmd.assert()
+        .success()
+        .stdout(predicate::str::contains(chinese));
+    let mut cmd = command_with_locale(&temp_dir, &[("LC_ALL", "en_US.UTF-8")])?;
+    cmd.assert()
+        .success()
+        .stdout(predicate::str::contains(chinese));
+
+    // `--language` beats both.
+    let mut cmd = command_with_locale(&temp_dir, &[("LANG", "zh_CN.UTF-8")])?;
+    cmd.args(["--language", "en"]);
+    cmd.assert()
+        .success()
+        .stdout(predicate::str::contains(english));
+
+    temp_dir.close()?;
+    Ok(())
+}
+
+#[test]
+fn test_set_language_needs_a_terminal() -> Result<(), Box<dyn std::error::Error>> {
+    let temp_dir = TempDir::new()?;
+
+    let mut cmd = command_with_locale(&temp_dir, &[("LANG", "en_US.UTF-8")])?;
+    cmd.arg("--set-language");
+    cmd.assert().failure().stderr(predicate::str::contains(
+        "Cannot ask for a language without an interactive terminal",
+    ));
+
+    let mut cmd = command_with_locale(&temp_dir, &[("FINDME_NO_INTERACTIVE", "1")])?;
+    cmd.args(["--set-language", "--no-interactive"]);
+    cmd.assert().failure();
+    assert!(!temp_dir.path().join("config.toml").exists());
+
+    temp_dir.close()?;
+    Ok(())
+}
+
 #[test]
 fn test_help_output() -> Result<(), Box<dyn std::error::Error>> {
     let temp_dir = TempDir::new()?;
@@ -197,8 +377,9 @@ fn test_birthday_is_cached_in_config() -> Result<(), Box<dyn std::error::Error>>
         .arg("en");
     cmd.assert().success();
 
-    let config_contents = fs::read_to_string(temp_dir.path().join("config.txt"))?;
-    assert!(config_contents.contains("birthday=1990-05-15"));
+    let config_contents = fs::read_to_string(temp_dir.path().join("config.toml"))?;
+    assert!(config_contents.contains("birthday = \"1990-05-15\""));
+    assert!(config_contents.contains("schema_version = 1"));
 
     temp_dir.close()?;
     Ok(())
@@ -232,3 +413,1170 @@ fn test_cached_birthday_used_when_not_provided() -> Result<(), Box<dyn std::erro
     temp_dir.close()?;
     Ok(())
 }
+
+fn write_pack(
+    temp_dir: &TempDir,
+    file: &str,
+    content: &str,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let packs_dir = temp_dir.path().join("packs");
+    fs::create_dir_all(&packs_dir)?;
+    fs::write(packs_dir.join(file), content)?;
+    Ok(())
+}
+
+#[test]
+fn test_custom_pack_drives_content() -> Result<(), Box<dyn std::error::Error>> {
+    let temp_dir = TempDir::new()?;
+    write_pack(
+        &temp_dir,
+        "team.json",
+        r#"{
+            "name": "team",
+            "version": "1.0.0",
+            "templates": {
+                "any": {
+                    "condition": "*",
+                    "message": "The borrow checker smiles upon you",
+                    "advice": "Ship the lifetime refactor",
+                    "color": "Rust Orange",
+                    "time": "Right after standup"
+                }
+            }
+        }"#,
+    )?;
+
+    let mut cmd = command_with_config(&temp_dir)?;
+    cmd.arg("--pack").arg("team").arg("--language").arg("en");
+    cmd.assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "The borrow checker smiles upon you",
+        ))
+        .stdout(predicate::str::contains("Ship the lifetime refactor"))
+        .stdout(predicate::str::contains("Rust Orange"))
+        .stdout(predicate::str::contains("Right after standup"));
+
+    temp_dir.close()?;
+    Ok(())
+}
+
+#[test]
+fn test_default_pack_matches_builtin_output() -> Result<(), Box<dyn std::error::Error>> {
+    let temp_dir = TempDir::new()?;
+
+    let builtin = command_with_config(&temp_dir)?
+        .args(["--language", "en", "--birthday", "1990-05-15"])
+        .output()?;
+    let default_pack = command_with_config(&temp_dir)?
+        .args(["--language", "en", "--pack", "default"])
+        .output()?;
+
+    assert!(builtin.status.success());
+    assert_eq!(builtin.stdout, default_pack.stdout);
+
+    temp_dir.close()?;
+    Ok(())
+}
+
+#[test]
+fn test_invalid_pack_is_rejected() -> Result<(), Box<dyn std::error::Error>> {
+    let temp_dir = TempDir::new()?;
+    write_pack(
+        &temp_dir,
+        "broken.json",
+        r#"{
+            "name": "broken",
+            "version": "1.0.0",
+            "templates": {
+                "odd": { "condition": "mood == happy", "message": "hi" }
+            }
+        }"#,
+    )?;
+
+    let mut cmd = command_with_config(&temp_dir)?;
+    cmd.arg("--pack").arg("broken").arg("--language").arg("en");
+    cmd.assert()
+        .failure()
+        .stderr(predicate::str::contains("Could not load fortune pack"))
+        .stderr(predicate::str::contains("template 'odd'"));
+
+    temp_dir.close()?;
+    Ok(())
+}
+
+#[test]
+fn test_missing_pack_is_reported() -> Result<(), Box<dyn std::error::Error>> {
+    let temp_dir = TempDir::new()?;
+    let mut cmd = command_with_config(&temp_dir)?;
+
+    cmd.arg("--pack").arg("nope").arg("--language").arg("en");
+    cmd.assert()
+        .failure()
+        .stderr(predicate::str::contains("no pack named 'nope'"));
+
+    temp_dir.close()?;
+    Ok(())
+}
+
+#[test]
+fn test_list_packs() -> Result<(), Box<dyn std::error::Error>> {
+    let temp_dir = TempDir::new()?;
+    write_pack(
+        &temp_dir,
+        "team.json",
+        r#"{"name": "team", "version": "2.1.0", "templates": {"any": {"message": "hi"}}}"#,
+    )?;
+    write_pack(&temp_dir, "broken.json", "{ not json")?;
+
+    let mut cmd = command_with_config(&temp_dir)?;
+    cmd.arg("--list-packs").arg("--language").arg("en");
+    cmd.assert()
+        .success()
+        .stdout(predicate::str::contains("default (built-in)"))
+        .stdout(predicate::str::contains("team 2.1.0"))
+        .stdout(predicate::str::contains("Invalid pack"));
+
+    temp_dir.close()?;
+    Ok(())
+}
+
+#[test]
+fn test_pack_templates_render_placeholders() -> Result<(), Box<dyn std::error::Error>> {
+    let temp_dir = TempDir::new()?;
+    write_pack(
+        &temp_dir,
+        "team.json",
+        r#"{
+            "name": "team",
+            "version": "1.0.0",
+            "templates": {
+                "any": {
+                    "message": "{user_name} scored {overall_score} on a {scenario}",
+                    "advice": "Focus {dimensions.focus}, strongest: {primary}{{ if pressure }} ({pressure.level}){{ endif }}"
+                }
+            }
+        }"#,
+    )?;
+
+    let mut cmd = command_with_config(&temp_dir)?;
+    cmd.env("FINDME_USER_NAME", "Ferris")
+        .args(["--pack", "team", "--language", "en"]);
+    cmd.assert()
+        .success()
+        .stdout(predicate::str::is_match(
+            r"Ferris scored \d+ on a (workday|weekend)",
+        )?)
+        .stdout(predicate::str::is_match(
+            r"Focus \d+, strongest: (focus|creativity|debugging|collaboration|risk_tolerance)\n",
+        )?);
+
+    temp_dir.close()?;
+    Ok(())
+}
+
+#[test]
+fn test_pack_template_syntax_error_names_key() -> Result<(), Box<dyn std::error::Error>> {
+    let temp_dir = TempDir::new()?;
+    write_pack(
+        &temp_dir,
+        "team.json",
+        r#"{"name": "team", "version": "1.0.0", "templates": {"greeting": {"message": "Hi {user_name"}}}"#,
+    )?;
+
+    let mut cmd = command_with_config(&temp_dir)?;
+    cmd.args(["--pack", "team", "--language", "en"]);
+    cmd.assert()
+        .failure()
+        .stderr(predicate::str::contains("template 'greeting': message"));
+
+    temp_dir.close()?;
+    Ok(())
+}
+
+#[test]
+fn test_pack_template_render_error_names_key() -> Result<(), Box<dyn std::error::Error>> {
+    let temp_dir = TempDir::new()?;
+    write_pack(
+        &temp_dir,
+        "team.json",
+        r#"{"name": "team", "version": "1.0.0", "templates": {"moody": {"advice": "Pressure: {pressure.level}"}}}"#,
+    )?;
+
+    // Pressure values render only with --pressure unless guarded.
+    let mut cmd = command_with_config(&temp_dir)?;
+    cmd.args(["--pack", "team", "--language", "en"]);
+    cmd.assert()
+        .failure()
+        .stderr(predicate::str::contains(
+            "Could not render fortune pack template",
+        ))
+        .stderr(predicate::str::contains("template 'moody': advice"));
+
+    temp_dir.close()?;
+    Ok(())
+}
+
+#[test]
+fn test_pack_unknown_placeholder_fails_at_load() -> Result<(), Box<dyn std::error::Error>> {
+    let temp_dir = TempDir::new()?;
+    for (file, name, advice) in [
+        ("typo.json", "typo", "Mood: {nope}"),
+        ("moody.json", "moody", "Mood: {dimensions.mood}"),
+    ] {
+        write_pack(
+            &temp_dir,
+            file,
+            &format!(
+                r#"{{"name": "{}", "version": "1.0.0", "templates": {{"any": {{"advice": "{}"}}}}}}"#,
+                name, advice
+            ),
+        )?;
+
+        let mut cmd = command_with_config(&temp_dir)?;
+        cmd.args(["--pack", name, "--language", "en"]);
+        cmd.assert()
+            .failure()
+            .stderr(predicate::str::contains("Could not load fortune pack"))
+            .stderr(predicate::str::contains("template 'any': advice"));
+    }
+
+    temp_dir.close()?;
+    Ok(())
+}
+
+#[test]
+fn test_json_output() -> Result<(), Box<dyn std::error::Error>> {
+    let temp_dir = TempDir::new()?;
+    let output = command_with_config(&temp_dir)?
+        .args(["--language", "en", "--format", "json"])
+        .output()?;
+    assert!(output.status.success());
+
+    let document: serde_json::Value = serde_json::from_slice(&output.stdout)?;
+    assert_eq!(document["schema_version"], 1);
+    assert!(document["fortune"]["overall_score"].is_u64());
+    assert!(document["fortune"]["dimensions"]["focus"].is_u64());
+    assert!(document["fortune"]["message"].is_string());
+    assert!(document.get("pressure").is_none());
+
+    temp_dir.close()?;
+    Ok(())
+}
+
+#[test]
+fn test_yaml_and_toml_output() -> Result<(), Box<dyn std::error::Error>> {
+    let temp_dir = TempDir::new()?;
+
+    let yaml = command_with_config(&temp_dir)?
+        .args(["--language", "en", "--format", "yaml"])
+        .output()?;
+    assert!(yaml.status.success());
+    let yaml: serde_norway::Value = serde_norway::from_slice(&yaml.stdout)?;
+    assert_eq!(yaml["schema_version"].as_u64(), Some(1));
+
+    let toml_output = command_with_config(&temp_dir)?
+        .args(["--language", "en", "--format", "toml"])
+        .output()?;
+    assert!(toml_output.status.success());
+    let document: toml::Table = String::from_utf8(toml_output.stdout)?.parse()?;
+    assert_eq!(document["schema_version"].as_integer(), Some(1));
+    assert!(document["fortune"]["dimensions"]["debugging"].is_integer());
+
+    temp_dir.close()?;
+    Ok(())
+}
+
+#[test]
+fn test_invalid_format() -> Result<(), Box<dyn std::error::Error>> {
+    let temp_dir = TempDir::new()?;
+    let mut cmd = command_with_config(&temp_dir)?;
+
+    cmd.args(["--language", "en", "--format", "xml"]);
+    cmd.assert()
+        .failure()
+        .stderr(predicate::str::contains("unknown format 'xml'"));
+
+    temp_dir.close()?;
+    Ok(())
+}
+
+#[test]
+fn test_fortune_for_specific_date() -> Result<(), Box<dyn std::error::Error>> {
+    let temp_dir = TempDir::new()?;
+
+    let first = command_with_config(&temp_dir)?
+        .args(["--language", "en", "--date", "2024-02-29"])
+        .output()?;
+    let second = command_with_config(&temp_dir)?
+        .args(["--language", "en", "--date", "2024-02-29"])
+        .output()?;
+
+    assert!(first.status.success());
+    assert_eq!(first.stdout, second.stdout);
+    assert!(String::from_utf8(first.stdout)?.contains("2024-02-29 ·"));
+
+    temp_dir.close()?;
+    Ok(())
+}
+
+#[test]
+fn test_invalid_date() -> Result<(), Box<dyn std::error::Error>> {
+    let temp_dir = TempDir::new()?;
+    let mut cmd = command_with_config(&temp_dir)?;
+
+    cmd.args(["--language", "en", "--date", "2024-02-30"]);
+    cmd.assert()
+        .failure()
+        .stderr(predicate::str::contains("Invalid date: 2024-02-30"));
+
+    temp_dir.close()?;
+    Ok(())
+}
+
+#[test]
+fn test_date_range_rows() -> Result<(), Box<dyn std::error::Error>> {
+    let temp_dir = TempDir::new()?;
+    let output = command_with_config(&temp_dir)?
+        .args([
+            "--language",
+            "en",
+            "--from",
+            "2024-03-01",
+            "--to",
+            "2024-03-07",
+        ])
+        .output()?;
+    assert!(output.status.success());
+
+    let stdout = String::from_utf8(output.stdout)?;
+    let rows: Vec<&str> = stdout
+        .lines()
+        .filter(|line| line.starts_with("2024-03-"))
+        .collect();
+    assert_eq!(rows.len(), 7);
+    assert!(rows[0].starts_with("2024-03-01 Fri"));
+    assert!(rows[6].starts_with("2024-03-07 Thu"));
+
+    temp_dir.close()?;
+    Ok(())
+}
+
+#[test]
+fn test_date_range_json() -> Result<(), Box<dyn std::error::Error>> {
+    let temp_dir = TempDir::new()?;
+    let output = command_with_config(&temp_dir)?
+        .args([
+            "--language",
+            "en",
+            "--from",
+            "2024-03-01",
+            "--to",
+            "2024-03-03",
+            "--format",
+            "json",
+        ])
+        .output()?;
+    assert!(output.status.success());
+
+    let document: serde_json::Value = serde_json::from_slice(&output.stdout)?;
+    let fortunes = document["fortunes"].as_array().ok_or("missing fortunes")?;
+    assert_eq!(fortunes.len(), 3);
+    assert_eq!(fortunes[2]["date"], "2024-03-03");
+    assert_eq!(fortunes[2]["scenario"], "weekend");
+
+    temp_dir.close()?;
+    Ok(())
+}
+
+#[test]
+fn test_reversed_date_range() -> Result<(), Box<dyn std::error::Error>> {
+    let temp_dir = TempDir::new()?;
+    let mut cmd = command_with_config(&temp_dir)?;
+
+    cmd.args([
+        "--language",
+        "en",
+        "--from",
+        "2024-03-07",
+        "--to",
+        "2024-03-01",
+    ]);
+    cmd.assert()
+        .failure()
+        .stderr(predicate::str::contains("--from must not be after --to"));
+
+    temp_dir.close()?;
+    Ok(())
+}
+
+#[test]
+fn test_calendar_week() -> Result<(), Box<dyn std::error::Error>> {
+    let temp_dir = TempDir::new()?;
+    let mut cmd = command_with_config(&temp_dir)?;
+
+    cmd.args([
+        "--language",
+        "en",
+        "--calendar",
+        "week",
+        "--date",
+        "2024-03-06",
+    ]);
+    cmd.assert()
+        .success()
+        .stdout(predicate::str::contains("2024-03-04 ~ 2024-03-10"))
+        .stdout(predicate::str::contains("Mon       Tue"))
+        .stdout(predicate::str::contains("Best Day per Dimension"));
+
+    temp_dir.close()?;
+    Ok(())
+}
+
+#[test]
+fn test_calendar_month_json() -> Result<(), Box<dyn std::error::Error>> {
+    let temp_dir = TempDir::new()?;
+    let output = command_with_config(&temp_dir)?
+        .args([
+            "--language",
+            "en",
+            "--calendar",
+            "month",
+            "--date",
+            "2024-02-10",
+            "--format",
+            "json",
+        ])
+        .output()?;
+    assert!(output.status.success());
+
+    let document: serde_json::Value = serde_json::from_slice(&output.stdout)?;
+    let fortunes = document["fortunes"].as_array().ok_or("missing fortunes")?;
+    assert_eq!(fortunes.len(), 29);
+    assert_eq!(fortunes[0]["date"], "2024-02-01");
+
+    temp_dir.close()?;
+    Ok(())
+}
+
+const TEAM_TOML: &str = r#"
+name = "Platform"
+
+[[members]]
+name = "Alice"
+birthday = "1990-05-15"
+
+[[members]]
+name = "Bob"
+birthday = "1988-11-02"
+
+[[members]]
+name = "Chen"
+birthday = "1995-02-20"
+"#;
+
+#[test]
+fn test_team_fortune() -> Result<(), Box<dyn std::error::Error>> {
+    let temp_dir = TempDir::new()?;
+    fs::write(temp_dir.path().join("team.toml"), TEAM_TOML)?;
+
+    let mut cmd = command_with_config(&temp_dir)?;
+    cmd.args(["team", "--language", "en"]);
+    cmd.assert()
+        .success()
+        .stdout(predicate::str::contains("Team Fortune · Platform"))
+        .stdout(predicate::str::contains("Team Score"))
+        .stdout(predicate::str::contains("Chen"))
+        .stdout(predicate::str::contains("Pair Programming Pick"));
+
+    temp_dir.close()?;
+    Ok(())
+}
+
+#[test]
+fn test_team_fortune_json_from_file() -> Result<(), Box<dyn std::error::Error>> {
+    let temp_dir = TempDir::new()?;
+    let team_file = temp_dir.path().join("standup.toml");
+    fs::write(&team_file, TEAM_TOML)?;
+
+    let output = command_with_config(&temp_dir)?
+        .args([
+            "team",
+            "--language",
+            "en",
+            "--format",
+            "json",
+            "--date",
+            "2024-03-04",
+        ])
+        .arg("--file")
+        .arg(&team_file)
+        .output()?;
+    assert!(output.status.success());
+
+    let document: serde_json::Value = serde_json::from_slice(&output.stdout)?;
+    assert_eq!(document["team"]["date"], "2024-03-04");
+    assert_eq!(
+        document["team"]["members"].as_array().map(Vec::len),
+        Some(3)
+    );
+    assert_eq!(
+        document["team"]["pairings"].as_array().map(Vec::len),
+        Some(3)
+    );
+
+    temp_dir.close()?;
+    Ok(())
+}
+
+#[test]
+fn test_team_file_errors() -> Result<(), Box<dyn std::error::Error>> {
+    let temp_dir = TempDir::new()?;
+    fs::write(
+        temp_dir.path().join("team.toml"),
+        "[[members]]\nname = \"Alice\"\nbirthday = \"15/05/1990\"\n",
+    )?;
+
+    let mut cmd = command_with_config(&temp_dir)?;
+    cmd.args(["team", "--language", "en"]);
+    cmd.assert()
+        .failure()
+        .stderr(predicate::str::contains("Could not load team file"))
+        .stderr(predicate::str::contains("member 'Alice': invalid birthday"));
+
+    temp_dir.close()?;
+    Ok(())
+}
+
+#[test]
+fn test_legacy_config_is_migrated() -> Result<(), Box<dyn std::error::Error>> {
+    let temp_dir = TempDir::new()?;
+    fs::write(
+        temp_dir.path().join("config.txt"),
+        "language=en\nbirthday=1990-05-15\ntheme=dark\n",
+    )?;
+
+    let mut cmd = command_with_config(&temp_dir)?;
+    cmd.assert()
+        .success()
+        .stdout(predicate::str::contains("Overall Score"));
+
+    let config_contents = fs::read_to_string(temp_dir.path().join("config.toml"))?;
+    assert!(config_contents.contains("language = \"en\""));
+    assert!(config_contents.contains("birthday = \"1990-05-15\""));
+    assert!(config_contents.contains("theme = \"dark\""));
+    assert!(!temp_dir.path().join("config.txt").exists());
+    assert!(temp_dir.path().join("config.txt.bak").exists());
+
+    temp_dir.close()?;
+    Ok(())
+}
+
+#[test]
+fn test_config_subcommand_round_trip() -> Result<(), Box<dyn std::error::Error>> {
+    let temp_dir = TempDir::new()?;
+
+    let mut cmd = command_with_config(&temp_dir)?;
+    cmd.args(["config", "set", "language", "english"]);
+    cmd.assert().success();
+
+    let mut cmd = command_with_config(&temp_dir)?;
+    cmd.args(["config", "set", "birthday", "1990-05-15"]);
+    cmd.assert().success();
+
+    let mut cmd = command_with_config(&temp_dir)?;
+    cmd.args(["config", "get", "language"]);
+    cmd.assert().success().stdout("en\n");
+
+    let mut cmd = command_with_config(&temp_dir)?;
+    cmd.args(["config", "list"]);
+    cmd.assert().success().stdout(predicate::str::contains(
+        "birthday = \"1990-05-15\"\nlanguage = \"en\"",
+    ));
+
+    let mut cmd = command_with_config(&temp_dir)?;
+    cmd.args(["config", "unset", "birthday"]);
+    cmd.assert().success();
+
+    let mut cmd = command_with_config(&temp_dir)?;
+    cmd.args(["config", "get", "birthday"]);
+    cmd.assert()
+        .failure()
+        .stderr(predicate::str::contains("Not set: birthday"));
+
+    let mut cmd = command_with_config(&temp_dir)?;
+    cmd.args(["config", "path"]);
+    cmd.assert()
+        .success()
+        .stdout(predicate::str::contains("config.toml"));
+
+    temp_dir.close()?;
+    Ok(())
+}
+
+#[test]
+fn test_config_set_rejects_invalid_value() -> Result<(), Box<dyn std::error::Error>> {
+    let temp_dir = TempDir::new()?;
+    let mut cmd = command_with_config(&temp_dir)?;
+
+    cmd.args(["config", "set", "birthday", "15/05/1990"]);
+    cmd.assert()
+        .failure()
+        .stderr(predicate::str::contains("key 'birthday'"));
+    assert!(!temp_dir.path().join("config.toml").exists());
+
+    temp_dir.close()?;
+    Ok(())
+}
+
+#[test]
+fn test_invalid_config_names_line_and_key() -> Result<(), Box<dyn std::error::Error>> {
+    let temp_dir = TempDir::new()?;
+    fs::write(
+        temp_dir.path().join("config.toml"),
+        "schema_version = 1\nlanguage = \"en\"\nbirthday = \"May 15\"\n",
+    )?;
+
+    let mut cmd = command_with_config(&temp_dir)?;
+    cmd.arg("--language").arg("en");
+    cmd.assert()
+        .failure()
+        .stderr(predicate::str::contains("line 3: key 'birthday'"));
+
+    temp_dir.close()?;
+    Ok(())
+}
+
+#[test]
+fn test_invalid_custom_probe_is_reported() -> Result<(), Box<dyn std::error::Error>> {
+    let temp_dir = TempDir::new()?;
+    fs::write(
+        temp_dir.path().join("config.toml"),
+        "schema_version = 1\n\n[[pressure.custom_probes]]\nname = \"e2e\"\ncommand = [\"./e2e.sh\"]\npassed = \"[0-9]+ ok\"\n",
+    )?;
+
+    let mut cmd = command_with_config(&temp_dir)?;
+    cmd.arg("--language").arg("en");
+    cmd.assert().failure().stderr(
+        predicate::str::contains("line 3: key 'pressure.custom_probes'")
+            .and(predicate::str::contains("needs a capture group")),
+    );
+
+    temp_dir.close()?;
+    Ok(())
+}
+
+#[test]
+fn test_history_keeps_one_entry_per_day() -> Result<(), Box<dyn std::error::Error>> {
+    let temp_dir = TempDir::new()?;
+
+    for _ in 0..2 {
+        let mut cmd = command_with_config(&temp_dir)?;
+        cmd.arg("--language").arg("en");
+        cmd.assert().success();
+    }
+
+    let mut cmd = command_with_config(&temp_dir)?;
+    cmd.args(["history", "--language", "en", "--format", "json"]);
+    let output = cmd.output()?;
+    assert!(output.status.success());
+    let document: serde_json::Value = serde_json::from_slice(&output.stdout)?;
+    assert_eq!(document["stats"]["days"], 1);
+    assert_eq!(document["entries"].as_array().map(Vec::len), Some(1));
+
+    let mut cmd = command_with_config(&temp_dir)?;
+    cmd.args(["history", "--language", "en"]);
+    cmd.assert()
+        .success()
+        .stdout(predicate::str::contains("Fortune History"))
+        .stdout(predicate::str::contains("Trend"));
+
+    temp_dir.close()?;
+    Ok(())
+}
+
+#[test]
+fn test_history_skips_other_dates() -> Result<(), Box<dyn std::error::Error>> {
+    let temp_dir = TempDir::new()?;
+    let mut cmd = command_with_config(&temp_dir)?;
+
+    cmd.args(["--language", "en", "--date", "2024-06-03"]);
+    cmd.assert().success();
+    assert!(!temp_dir.path().join("history.json").exists());
+
+    temp_dir.close()?;
+    Ok(())
+}
+
+#[test]
+fn test_config_rejects_invalid_exclude_glob() -> Result<(), Box<dyn std::error::Error>> {
+    let temp_dir = TempDir::new()?;
+    let mut cmd = command_with_config(&temp_dir)?;
+
+    cmd.args(["config", "set", "pressure.exclude", "[\"vendor/[\"]"]);
+    cmd.assert()
+        .failure()
+        .stderr(predicate::str::contains("key 'pressure.exclude'"));
+
+    let mut cmd = command_with_config(&temp_dir)?;
+    cmd.args([
+        "config",
+        "set",
+        "pressure.exclude",
+        "[\"vendor/**\", \"gen/**\"]",
+    ]);
+    cmd.assert().success();
+
+    let mut cmd = command_with_config(&temp_dir)?;
+    cmd.args(["config", "get", "pressure.exclude"]);
+    cmd.assert()
+        .success()
+        .stdout(predicate::str::contains("gen/**"));
+
+    temp_dir.close()?;
+    Ok(())
+}
+
+#[test]
+fn test_pressure_runs_only_the_git_probe_by_default() -> Result<(), Box<dyn std::error::Error>> {
+    let temp_dir = TempDir::new()?;
+    let work_dir = TempDir::new()?;
+    let mut cmd = command_with_config(&temp_dir)?;
+
+    cmd.current_dir(work_dir.path())
+        .args(["--pressure", "--language", "en", "--format", "json"]);
+    let output = cmd.output()?;
+    assert!(output.status.success());
+    let document: serde_json::Value = serde_json::from_slice(&output.stdout)?;
+    assert_eq!(document["pressure"]["probes"], serde_json::json!(["git"]));
+    assert!(document["pressure"]["tests"].is_null());
+    // Internal bookkeeping stays out of the report.
+    for field in ["root", "results", "scoring_model", "cached_probes"] {
+        assert!(document["pressure"].get(field).is_none(), "{}", field);
+    }
+
+    let mut cmd = command_with_config(&temp_dir)?;
+    cmd.current_dir(work_dir.path())
+        .args(["--pressure", "--language", "en"]);
+    cmd.assert()
+        .success()
+        .stdout(predicate::str::contains("--probe tests"));
+
+    temp_dir.close()?;
+    work_dir.close()?;
+    Ok(())
+}
+
+#[test]
+fn test_probe_failures_are_reported_with_partial_results() -> Result<(), Box<dyn std::error::Error>>
+{
+    let temp_dir = TempDir::new()?;
+    let work_dir = TempDir::new()?;
+
+    let mut cmd = command_with_config(&temp_dir)?;
+    cmd.current_dir(work_dir.path())
+        .args(["--pressure", "--language", "en", "--format", "json"]);
+    let output = cmd.output()?;
+    assert!(output.status.success());
+    let document: serde_json::Value = serde_json::from_slice(&output.stdout)?;
+    assert_eq!(
+        document["pressure"]["errors"][0]["kind"],
+        "not_a_repository"
+    );
+    assert_eq!(
+        document["pressure"]["failed_probes"],
+        serde_json::json!(["git"])
+    );
+
+    let mut cmd = command_with_config(&temp_dir)?;
+    cmd.current_dir(work_dir.path())
+        .args(["--pressure", "--language", "en"]);
+    cmd.assert()
+        .success()
+        .stdout(predicate::str::contains("Probe Problems"))
+        .stdout(predicate::str::contains("is not inside a git repository"));
+
+    let mut cmd = command_with_config(&temp_dir)?;
+    cmd.current_dir(work_dir.path())
+        .args(["--pressure", "--language", "zh"]);
+    cmd.assert()
+        .success()
+        .stdout(predicate::str::contains("不在 git 仓库中"));
+
+    temp_dir.close()?;
+    work_dir.close()?;
+    Ok(())
+}
+
+#[test]
+fn test_unknown_probe_is_rejected() -> Result<(), Box<dyn std::error::Error>> {
+    let temp_dir = TempDir::new()?;
+    let mut cmd = command_with_config(&temp_dir)?;
+
+    cmd.args(["--probe", "git,lint", "--language", "en"]);
+    cmd.assert()
+        .failure()
+        .stderr(predicate::str::contains("unknown probe 'lint'"));
+
+    temp_dir.close()?;
+    Ok(())
+}
+
+#[test]
+fn test_explain_uses_repository_scoring_model() -> Result<(), Box<dyn std::error::Error>> {
+    let temp_dir = TempDir::new()?;
+    let work_dir = TempDir::new()?;
+    fs::write(
+        work_dir.path().join(".findme.toml"),
+        "[scoring]\nmedium = 10\nhigh = 20\ncritical = 90\n",
+    )?;
+
+    let mut cmd = command_with_config(&temp_dir)?;
+    cmd.current_dir(work_dir.path())
+        .args(["--explain", "--language", "en"]);
+    cmd.assert()
+        .success()
+        .stdout(predicate::str::contains("Score Breakdown"))
+        .stdout(predicate::str::contains(
+            "medium ≥ 10, high ≥ 20, critical ≥ 90",
+        ))
+        .stdout(predicate::str::contains(".findme.toml"));
+
+    fs::write(
+        work_dir.path().join(".findme.toml"),
+        "[scoring.tests]\nweight = 30\nthresholds = [0.5, 0.9]\n",
+    )?;
+    let mut cmd = command_with_config(&temp_dir)?;
+    cmd.current_dir(work_dir.path())
+        .args(["--pressure", "--language", "en"]);
+    cmd.assert().success().stderr(predicate::str::contains(
+        "scoring.tests.thresholds must be descending",
+    ));
+
+    temp_dir.close()?;
+    work_dir.close()?;
+    Ok(())
+}
+
+#[test]
+fn test_pressure_runs_are_recorded_per_repository() -> Result<(), Box<dyn std::error::Error>> {
+    let temp_dir = TempDir::new()?;
+    let work_dir = TempDir::new()?;
+
+    let mut cmd = command_with_config(&temp_dir)?;
+    cmd.current_dir(work_dir.path())
+        .args(["pressure", "history", "--language", "en"]);
+    cmd.assert()
+        .success()
+        .stdout(predicate::str::contains("No pressure runs recorded yet"));
+
+    for _ in 0..2 {
+        let mut cmd = command_with_config(&temp_dir)?;
+        cmd.current_dir(work_dir.path())
+            .args(["--pressure", "--language", "en"]);
+        cmd.assert().success();
+    }
+
+    let mut cmd = command_with_config(&temp_dir)?;
+    cmd.current_dir(work_dir.path())
+        .args(["pressure", "history", "--language", "en"]);
+    cmd.assert()
+        .success()
+        .stdout(predicate::str::contains("2 runs in the last 14 days"))
+        .stdout(predicate::str::contains("Pressure score"))
+        .stdout(predicate::str::contains("Pass rate  (not measured)"))
+        .stdout(predicate::str::contains("No big regressions"));
+
+    let mut cmd = command_with_config(&temp_dir)?;
+    cmd.current_dir(work_dir.path()).args([
+        "pressure",
+        "history",
+        "--language",
+        "en",
+        "--format",
+        "json",
+    ]);
+    let output = cmd.output()?;
+    assert!(output.status.success());
+    let document: serde_json::Value = serde_json::from_slice(&output.stdout)?;
+    assert_eq!(document["snapshots"].as_array().map(Vec::len), Some(2));
+    // Churn is not measured outside a git repository.
+    assert!(document["snapshots"][0]["churn_lines"].is_null());
+    assert!(document["snapshots"][0]["score"].is_u64());
+
+    // Another directory has its own history.
+    let mut cmd = command_with_config(&temp_dir)?;
+    cmd.current_dir(temp_dir.path())
+        .args(["pressure", "history", "--language", "en"]);
+    cmd.assert()
+        .success()
+        .stdout(predicate::str::contains("No pressure runs recorded yet"));
+
+    temp_dir.close()?;
+    work_dir.close()?;
+    Ok(())
+}
+
+#[test]
+fn test_pressure_dashboard_reports_failed_repositories() -> Result<(), Box<dyn std::error::Error>> {
+    let temp_dir = TempDir::new()?;
+    let work_dir = TempDir::new()?;
+    fs::create_dir(work_dir.path().join("api"))?;
+    fs::create_dir(work_dir.path().join("web"))?;
+
+    let mut cmd = command_with_config(&temp_dir)?;
+    cmd.current_dir(work_dir.path()).args([
+        "pressure",
+        "--repos",
+        "api,missing,web",
+        "--language",
+        "en",
+    ]);
+    cmd.assert()
+        .success()
+        .stdout(predicate::str::contains("Repository"))
+        .stdout(predicate::str::contains("missing is not a directory"))
+        .stdout(predicate::str::contains("3 repositories, 1 failed"));
+
+    let mut cmd = command_with_config(&temp_dir)?;
+    cmd.current_dir(work_dir.path()).args([
+        "pressure",
+        "--repos",
+        "missing,web",
+        "--language",
+        "en",
+        "--format",
+        "json",
+    ]);
+    let output = cmd.output()?;
+    assert!(output.status.success());
+    let document: serde_json::Value = serde_json::from_slice(&output.stdout)?;
+    let repos = document["repos"].as_array().unwrap();
+    assert_eq!(repos.len(), 2);
+    // Failed analyses sort last.
+    assert_eq!(repos[0]["pressure"]["pressure_score"], 0);
+    assert!(repos[0]["pressure"].get("scoring_model").is_none());
+    assert_eq!(repos[1]["error"]["kind"], "not_a_directory");
+    assert!(repos[1]["error"]["path"]
+        .as_str()
+        .unwrap()
+        .ends_with("missing"));
+
+    let mut cmd = command_with_config(&temp_dir)?;
+    cmd.current_dir(work_dir.path())
+        .args(["pressure", "--language", "en"]);
+    cmd.assert()
+        .success()
+        .stdout(predicate::str::contains("No repositories to analyze"));
+
+    temp_dir.close()?;
+    work_dir.close()?;
+    Ok(())
+}
+
+/// English phrases of at least two words from the English locale that the Chinese one
+/// does not use itself, plus messages that used to be hard-coded.
+fn english_phrases() -> Result<Vec<String>, Box<dyn std::error::Error>> {
+    let locale = |name: &str| -> Result<serde_json::Value, Box<dyn std::error::Error>> {
+        let path = std::path::Path::new(env!("CARGO_MANIFEST_DIR"))
+            .join("locales")
+            .join(name);
+        Ok(serde_json::from_str(&fs::read_to_string(path)?)?)
+    };
+    let texts = |locale: &serde_json::Value| -> Vec<String> {
+        let mut texts = Vec::new();
+        for message in locale.as_object().into_iter().flat_map(|map| map.values()) {
+            match message {
+                serde_json::Value::String(text) => texts.push(text.clone()),
+                serde_json::Value::Object(forms) => texts.extend(
+                    forms
+                        .values()
+                        .filter_map(|form| form.as_str().map(str::to_string)),
+                ),
+                _ => {}
+            }
+        }
+        texts
+    };
+    let chinese = texts(&locale("zh.json")?).join("\n");
+
+    let mut phrases: Vec<String> = [
+        "Version:",
+        "Author:",
+        "Invalid birthday",
+        "Could not save birthday",
+        "Failed to analyze",
+        "no translation for",
+    ]
+    .map(str::to_string)
+    .to_vec();
+    for text in texts(&locale("en.json")?) {
+        // The literal text between placeholders.
+        for (index, piece) in text.split(['{', '}']).enumerate() {
+            let piece = piece.trim_matches(|c: char| !c.is_ascii_alphanumeric());
+            let words = piece
+                .split(|c: char| !c.is_ascii_alphabetic())
+                .filter(|word| word.len() >= 3)
+                .count();
+            if index % 2 == 0 && words >= 2 && !chinese.contains(piece) {
+                phrases.push(piece.to_string());
+            }
+        }
+    }
+    Ok(phrases)
+}
+
+#[test]
+fn test_chinese_output_has_no_english_messages() -> Result<(), Box<dyn std::error::Error>> {
+    let temp_dir = TempDir::new()?;
+    let work_dir = TempDir::new()?;
+    fs::write(temp_dir.path().join("team.toml"), TEAM_TOML)?;
+    fs::create_dir(work_dir.path().join("repo"))?;
+    git2::Repository::init(work_dir.path().join("repo"))?;
+
+    let phrases = english_phrases()?;
+    assert!(phrases.iter().any(|phrase| phrase == "Today's Work Advice"));
+
+    let runs: &[&[&str]] = &[
+        &[],
+        &["--verbose"],
+        &["--birthday", "1990-05-15"],
+        &["--birthday", "1990-13-45"],
+        &["--date", "2024-02-30"],
+        &["--from", "2024-01-01", "--to", "2024-01-05"],
+        &["--from", "2024-01-05", "--to", "2024-01-01"],
+        &["--from", "2020-01-01", "--to", "2024-01-01"],
+        &["--calendar", "week"],
+        &["--list-packs"],
+        &["--pack", "missing"],
+        &["--pressure", "--explain"],
+        &["--pressure-adjusted"],
+        &["team"],
+        &["team", "--file", "missing.toml"],
+        &["history"],
+        &["pressure", "history"],
+        &["pressure", "--repos", "repo,missing"],
+        &["config", "get", "pressure.window_hours"],
+        &["config", "set", "pressure.window_hours", "none"],
+        &["config", "set", "pressure.window_hours", "48"],
+        &["config", "unset", "pressure.window_hours"],
+        &["--set-language"],
+        &["i18n", "check"],
+    ];
+    for args in runs {
+        for dir in [work_dir.path().to_path_buf(), work_dir.path().join("repo")] {
+            let mut cmd = command_with_config(&temp_dir)?;
+            cmd.current_dir(&dir)
+                .env("FINDME_NO_INTERACTIVE", "1")
+                .args(["--language", "zh"])
+                .args(*args);
+            let output = cmd.output()?;
+            let text = format!(
+                "{}{}",
+                String::from_utf8_lossy(&output.stdout),
+                String::from_utf8_lossy(&output.stderr)
+            );
+            assert!(!text.trim().is_empty(), "no output for {:?}", args);
+            for phrase in &phrases {
+                assert!(
+                    !text.contains(phrase.as_str()),
+                    "`findme {}` printed English {:?}:\n{}",
+                    args.join(" "),
+                    phrase,
+                    text
+                );
+            }
+        }
+    }
+
+    // Errors from loading files are translated too, not only the line they are printed on.
+    let bad_config = TempDir::new()?;
+    fs::write(
+        bad_config.path().join("config.toml"),
+        "[pressure]\nwindow_hours = \"none\"\n",
+    )?;
+    let bad_exclude = TempDir::new()?;
+    fs::write(
+        bad_exclude.path().join("config.toml"),
+        "[pressure]\nexclude = [\"generated/[\"]\n",
+    )?;
+    write_pack(
+        &temp_dir,
+        "needs-pressure.json",
+        r#"{
+            "name": "needs-pressure",
+            "version": "1.0.0",
+            "templates": {
+                "any": { "condition": "*", "message": "Pressure is {pressure.level}" }
+            }
+        }"#,
+    )?;
+    let error_runs: &[(&TempDir, &[&str])] = &[
+        (&temp_dir, &["team", "--file", "missing.toml"]),
+        (
+            &temp_dir,
+            &["config", "set", "pressure.window_hours", "none"],
+        ),
+        (&temp_dir, &["config", "set", "pressure.window_hours", "0"]),
+        (&temp_dir, &["--pack", "missing"]),
+        (&bad_config, &[]),
+        (&bad_exclude, &["--pressure"]),
+        (&temp_dir, &["--pack", "needs-pressure"]),
+    ];
+    for (config, args) in error_runs {
+        let mut cmd = command_with_config(config)?;
+        cmd.current_dir(work_dir.path())
+            .env("FINDME_NO_INTERACTIVE", "1")
+            .args(["--language", "zh"])
+            .args(*args);
+        let output = cmd.output()?;
+        assert!(
+            !output.status.success(),
+            "`findme {}` succeeded",
+            args.join(" ")
+        );
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        let line = stderr.lines().next().unwrap_or_default();
+        let (_, message) = line
+            .split_once(": ")
+            .unwrap_or_else(|| panic!("no error message in {:?}", line));
+        assert!(
+            !message.is_ascii(),
+            "`findme {}` printed an English error: {}",
+            args.join(" "),
+            line
+        );
+    }
+
+    // A broken scoring table is only a warning, but its reason is translated as well.
+    let scored = work_dir.path().join("scored");
+    git2::Repository::init(&scored)?;
+    fs::write(
+        scored.join(".findme.toml"),
+        "[scoring]\nmedium = 70\nhigh = 60\n",
+    )?;
+    let mut cmd = command_with_config(&temp_dir)?;
+    cmd.current_dir(&scored)
+        .env("FINDME_NO_INTERACTIVE", "1")
+        .args(["--language", "zh", "--pressure", "--probe", "git"]);
+    let output = cmd.output()?;
+    let text = format!(
+        "{}{}",
+        String::from_utf8_lossy(&output.stdout),
+        String::from_utf8_lossy(&output.stderr)
+    );
+    let line = text
+        .lines()
+        .find(|line| line.contains(".findme.toml"))
+        .unwrap_or_else(|| panic!("no scoring error in:\n{}", text));
+    let (_, reason) = line.rsplit_once(": ").unwrap_or_default();
+    assert!(
+        !reason.is_ascii(),
+        "the scoring error is in English: {}",
+        line
+    );
+
+    temp_dir.close()?;
+    work_dir.close()?;
+    Ok(())
+}
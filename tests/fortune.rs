@@ -0,0 +1,246 @@
+use chrono::NaiveDate;
+use findme::{
+    generate_fortune_for_date, generate_pressure_adjusted_fortune, generate_team_fortune,
+    history_stats, sparkline, AdjustmentReason, DevPressure, FortunePack, History, Language,
+    PressureLevel, RepoSignals, ScoreInputs, ScoringModel, Team, WorkScenario,
+};
+
+/// Pressure as the analysis would report it for `inputs`, scored with the default model.
+fn pressure_for(inputs: ScoreInputs) -> DevPressure {
+    let score = ScoringModel::default().score(&inputs);
+    DevPressure {
+        pressure_score: score.score,
+        pressure_level: score.level,
+        score_breakdown: score.contributions,
+        ..DevPressure::default()
+    }
+}
+
+#[test]
+fn test_fortune_for_date_is_deterministic() {
+    let date = NaiveDate::from_ymd_opt(2024, 6, 3).unwrap();
+    let pack = FortunePack::builtin(Language::ENGLISH);
+
+    let first = generate_fortune_for_date(date, Some("1990-05-15"), &pack, None, Language::ENGLISH)
+        .unwrap();
+    let second =
+        generate_fortune_for_date(date, Some("1990-05-15"), &pack, None, Language::ENGLISH)
+            .unwrap();
+
+    assert_eq!(first.date, date);
+    assert_eq!(first.overall_score, second.overall_score);
+    assert_eq!(first.message, second.message);
+    assert_eq!(first.lucky_color, second.lucky_color);
+    assert!(matches!(first.scenario, WorkScenario::Workday));
+}
+
+#[test]
+fn test_fortune_scenario_follows_date() {
+    let saturday = NaiveDate::from_ymd_opt(2024, 6, 8).unwrap();
+    let pack = FortunePack::builtin(Language::ENGLISH);
+
+    let fortune =
+        generate_fortune_for_date(saturday, None, &pack, None, Language::ENGLISH).unwrap();
+
+    assert!(matches!(fortune.scenario, WorkScenario::Weekend));
+}
+
+#[test]
+fn test_template_that_fails_to_render_is_a_localized_error() {
+    let pack = FortunePack::from_json(
+        r#"{
+            "name": "needs-pressure",
+            "version": "1.0.0",
+            "templates": {
+                "any": { "condition": "*", "message": "Pressure is {pressure.level}" }
+            }
+        }"#,
+    )
+    .unwrap();
+    let date = NaiveDate::from_ymd_opt(2024, 6, 8).unwrap();
+
+    let error = generate_fortune_for_date(date, None, &pack, None, Language::CHINESE).unwrap_err();
+
+    assert_eq!(error.key(), "pack.error.template_field");
+    assert!(error
+        .message(Language::CHINESE)
+        .starts_with("模板 'any' 的 message: "));
+}
+
+#[test]
+fn test_team_fortune_combines_members() {
+    let team = Team::from_toml(
+        r#"
+        [[members]]
+        name = "Alice"
+        birthday = "1990-05-15"
+
+        [[members]]
+        name = "Bob"
+        birthday = "1988-11-02"
+        "#,
+    )
+    .unwrap();
+    let date = NaiveDate::from_ymd_opt(2024, 6, 3).unwrap();
+    let pack = FortunePack::builtin(Language::ENGLISH);
+
+    let team_fortune = generate_team_fortune(&team, date, &pack, Language::ENGLISH).unwrap();
+
+    let scores: Vec<u8> = team_fortune
+        .members
+        .iter()
+        .map(|member| member.fortune.overall_score)
+        .collect();
+    let expected = ((scores[0] as f32 + scores[1] as f32) / 2.0).round() as u8;
+    assert_eq!(team_fortune.team_score, expected);
+    assert_eq!(team_fortune.pairings.len(), 1);
+    assert_eq!(
+        team_fortune.pair_programming,
+        Some(("Alice".to_string(), "Bob".to_string()))
+    );
+}
+
+#[test]
+fn test_team_rejects_duplicate_members() {
+    let error = Team::from_toml(
+        r#"
+        [[members]]
+        name = "Alice"
+        birthday = "1990-05-15"
+
+        [[members]]
+        name = "Alice"
+        birthday = "1991-05-15"
+        "#,
+    )
+    .unwrap_err();
+
+    assert!(error.to_string().contains("listed twice"));
+}
+
+#[test]
+fn test_history_streaks_and_averages() {
+    let pack = FortunePack::builtin(Language::ENGLISH);
+    let first_day = NaiveDate::from_ymd_opt(2024, 6, 3).unwrap();
+    let scores = [85, 90, 60, 80, 81, 95, 70];
+
+    let mut history = History::default();
+    for (offset, score) in scores.iter().enumerate() {
+        let date = first_day + chrono::Duration::days(offset as i64);
+        let mut fortune =
+            generate_fortune_for_date(date, None, &pack, None, Language::ENGLISH).unwrap();
+        fortune.overall_score = *score;
+        history.record(&fortune);
+        // Recording the same day again replaces the entry.
+        history.record(&fortune);
+    }
+    assert_eq!(history.entries.len(), scores.len());
+
+    let last_day = first_day + chrono::Duration::days(6);
+    let stats = history_stats(history.recent(last_day, 30));
+    assert_eq!(stats.days, 7);
+    assert_eq!(stats.average_score, 80);
+    assert_eq!(stats.best.unwrap().score, 95);
+    assert_eq!(stats.worst.unwrap().score, 60);
+    assert!(stats.current_streak.is_none());
+    let longest = stats.longest_streak.unwrap();
+    assert_eq!(longest.days, 3);
+    assert_eq!(longest.start, first_day + chrono::Duration::days(3));
+
+    let stats = history_stats(history.recent(last_day - chrono::Duration::days(1), 3));
+    assert_eq!(stats.days, 3);
+    assert_eq!(stats.current_streak.unwrap().days, 3);
+}
+
+#[test]
+fn test_sparkline_scales_scores() {
+    assert_eq!(sparkline(&[0, 50, 100]), "▁▅█");
+}
+
+#[test]
+fn test_failing_tests_lower_debugging() {
+    let date = NaiveDate::from_ymd_opt(2024, 6, 3).unwrap();
+    let pack = FortunePack::builtin(Language::ENGLISH);
+    let pressure = pressure_for(ScoreInputs {
+        churn_lines: Some(0.0),
+        test_pass_rate: Some(0.4),
+        ..ScoreInputs::default()
+    });
+
+    let plain = generate_fortune_for_date(date, None, &pack, None, Language::ENGLISH).unwrap();
+    let adjusted =
+        generate_pressure_adjusted_fortune(date, None, &pack, &pressure, Language::ENGLISH)
+            .unwrap();
+    let again = generate_pressure_adjusted_fortune(date, None, &pack, &pressure, Language::ENGLISH)
+        .unwrap();
+
+    // Every test threshold is crossed, so debugging takes the full penalty.
+    assert_eq!(
+        adjusted.dimensions.debugging,
+        plain.dimensions.debugging - 30
+    );
+    assert_eq!(adjusted.dimensions.focus, plain.dimensions.focus);
+    assert!(adjusted.overall_score < plain.overall_score);
+    assert_eq!(adjusted.adjustments.len(), 1);
+    assert_eq!(adjusted.adjustments[0].reason, AdjustmentReason::Tests);
+    assert_eq!(adjusted.lucky_color, plain.lucky_color);
+    assert!(plain.adjustments.is_empty());
+
+    assert_eq!(again.overall_score, adjusted.overall_score);
+    assert_eq!(again.message, adjusted.message);
+}
+
+#[test]
+fn test_churn_and_conflicts_adjust_their_dimensions() {
+    let date = NaiveDate::from_ymd_opt(2024, 6, 8).unwrap();
+    let pack = FortunePack::builtin(Language::ENGLISH);
+    let mut pressure = pressure_for(ScoreInputs {
+        churn_lines: Some(120.0),
+        test_pass_rate: Some(1.0),
+        ..ScoreInputs::default()
+    });
+    pressure.repo = Some(RepoSignals {
+        conflicted_files: 2,
+        ..RepoSignals::default()
+    });
+
+    let plain = generate_fortune_for_date(date, None, &pack, None, Language::ENGLISH).unwrap();
+    let adjusted =
+        generate_pressure_adjusted_fortune(date, None, &pack, &pressure, Language::ENGLISH)
+            .unwrap();
+
+    // Three of four churn thresholds: -23 risk tolerance and -8 focus.
+    assert_eq!(
+        adjusted.dimensions.risk_tolerance,
+        plain.dimensions.risk_tolerance - 23
+    );
+    assert_eq!(adjusted.dimensions.focus, plain.dimensions.focus - 8);
+    // A green suite earns debugging a little confidence.
+    assert_eq!(
+        adjusted.dimensions.debugging,
+        plain.dimensions.debugging + 5
+    );
+    assert_eq!(
+        adjusted.dimensions.collaboration,
+        plain.dimensions.collaboration.saturating_sub(15)
+    );
+}
+
+#[test]
+fn test_enums_are_written_in_snake_case_and_read_old_names(
+) -> Result<(), Box<dyn std::error::Error>> {
+    assert_eq!(
+        serde_json::to_string(&WorkScenario::Weekend)?,
+        r#""weekend""#
+    );
+    assert_eq!(
+        serde_json::to_string(&PressureLevel::Medium)?,
+        r#""medium""#
+    );
+    // History and cache files written before the rename keep loading.
+    let scenario: WorkScenario = serde_json::from_str(r#""Weekend""#)?;
+    assert!(matches!(scenario, WorkScenario::Weekend));
+    let level: PressureLevel = serde_json::from_str(r#""Critical""#)?;
+    assert_eq!(level, PressureLevel::Critical);
+    Ok(())
+}
@@ -0,0 +1,260 @@
+use assert_fs::TempDir;
+use findme::utils::i18n::{plural_category, I18n};
+use findme::utils::i18n_check::{check_translations, read_sources, PlaceholderMismatch, PluralGap};
+use findme::utils::language_from_locale;
+use findme::{Language, LocalizedError};
+use std::fs;
+use std::path::Path;
+
+#[test]
+fn test_language_tags_are_normalized() {
+    let tag = |code: &str| Language::from_code(code).map(|lang| lang.code());
+
+    assert_eq!(tag("zh"), Some("zh"));
+    assert_eq!(tag(" English "), Some("en"));
+    assert_eq!(tag("zh_tw"), Some("zh-TW"));
+    assert_eq!(tag("ZH-hant-tw"), Some("zh-Hant-TW"));
+    assert_eq!(tag("es-419"), Some("es-419"));
+    assert_eq!(tag("invalid"), None);
+    assert_eq!(tag("de-"), None);
+    assert_eq!(Language::from_code("zh"), Some(Language::CHINESE));
+}
+
+#[test]
+fn test_fallback_chain_ends_in_english() {
+    let chain = |code: &str| -> Vec<&str> {
+        Language::from_code(code)
+            .unwrap()
+            .fallback_chain()
+            .iter()
+            .map(|lang| lang.code())
+            .collect()
+    };
+
+    assert_eq!(chain("zh-Hant-TW"), ["zh-Hant-TW", "zh-Hant", "zh", "en"]);
+    assert_eq!(chain("de"), ["de", "en"]);
+    assert_eq!(chain("en-GB"), ["en-GB", "en"]);
+}
+
+#[test]
+fn test_embedded_locales_have_the_same_keys() {
+    let i18n = I18n::new();
+    let english = i18n.messages(Language::ENGLISH).unwrap();
+    let chinese = i18n.messages(Language::CHINESE).unwrap();
+
+    assert!(english.contains_key("app.title"));
+    let mut missing: Vec<&String> = english
+        .keys()
+        .filter(|key| !chinese.contains_key(*key))
+        .chain(chinese.keys().filter(|key| !english.contains_key(*key)))
+        .collect();
+    missing.sort();
+    assert!(missing.is_empty(), "keys in only one locale: {:?}", missing);
+}
+
+#[test]
+fn test_locale_files_override_and_add_languages() -> Result<(), Box<dyn std::error::Error>> {
+    let dir = TempDir::new()?;
+    fs::write(
+        dir.path().join("ja.json"),
+        r#"{"language.name": "日本語", "app.title": "今日の運勢"}"#,
+    )?;
+    fs::write(dir.path().join("zh.json"), r#"{"app.title": "自定义标题"}"#)?;
+    fs::write(dir.path().join("de.json"), "{ not json")?;
+
+    let i18n = I18n::with_overrides(dir.path());
+    let ja = Language::from_code("ja").unwrap();
+    let de = Language::from_code("de").unwrap();
+    let zh_tw = Language::from_code("zh-TW").unwrap();
+
+    assert_eq!(i18n.t("app.title", ja), "今日の運勢");
+    // Keys a locale leaves out come from English.
+    assert_eq!(
+        i18n.t("color.blue", ja),
+        i18n.t("color.blue", Language::ENGLISH)
+    );
+    assert_eq!(i18n.t("app.title", Language::CHINESE), "自定义标题");
+    assert_eq!(i18n.t("app.title", zh_tw), "自定义标题");
+    assert_eq!(i18n.t("no.such.key", ja), "no.such.key");
+
+    assert_eq!(i18n.languages(), [Language::ENGLISH, ja, Language::CHINESE]);
+    assert!(i18n.supports(ja) && i18n.supports(zh_tw));
+    assert!(!i18n.supports(de));
+    assert!(i18n.supports(Language::from_code("en-GB").unwrap()));
+    assert_eq!(i18n.problems().len(), 1);
+    assert!(i18n.problems()[0].to_string().contains("de.json"));
+
+    dir.close()?;
+    Ok(())
+}
+
+#[test]
+fn test_named_arguments_and_plural_forms() -> Result<(), Box<dyn std::error::Error>> {
+    let dir = TempDir::new()?;
+    fs::write(
+        dir.path().join("ru.json"),
+        r#"{"pressure.lines": {"one": "{count} строка", "few": "{count} строки",
+            "many": "{count} строк", "other": "{count} строки"}}"#,
+    )?;
+    let i18n = I18n::with_overrides(dir.path());
+    let ru = Language::from_code("ru").unwrap();
+    let lines =
+        |lang: Language, count: u64| i18n.format("pressure.lines", lang, &[("count", &count)]);
+
+    assert_eq!(lines(Language::ENGLISH, 1), "1 line");
+    assert_eq!(lines(Language::ENGLISH, 180), "180 lines");
+    assert_eq!(lines(Language::CHINESE, 1), "1 行");
+    assert_eq!(lines(ru, 21), "21 строка");
+    assert_eq!(lines(ru, 3), "3 строки");
+    assert_eq!(lines(ru, 12), "12 строк");
+    assert_eq!(
+        i18n.format(
+            "dashboard.summary",
+            Language::ENGLISH,
+            &[("failed", &0), ("count", &2)]
+        ),
+        "2 repositories, 0 failed"
+    );
+    // Values are not searched for placeholders themselves.
+    assert_eq!(
+        i18n.format(
+            "file.error.read",
+            Language::ENGLISH,
+            &[("path", &"/tmp/{error}"), ("error", &"denied")]
+        ),
+        "cannot read /tmp/{error}: denied"
+    );
+    // Placeholders without a value stay visible.
+    assert_eq!(i18n.t("app.welcome", Language::ENGLISH), "Welcome {name}!");
+    // Without a count a plural message uses its `other` form.
+    assert_eq!(i18n.t("pressure.lines", Language::ENGLISH), "{count} lines");
+
+    dir.close()?;
+    Ok(())
+}
+
+#[test]
+fn test_plural_categories_follow_the_language() {
+    let category =
+        |code: &str, count: &str| plural_category(Language::from_code(code).unwrap(), count);
+
+    assert_eq!(category("en", "1"), "one");
+    assert_eq!(category("en", "0"), "other");
+    assert_eq!(category("en", "1.5"), "other");
+    assert_eq!(category("zh", "1"), "other");
+    assert_eq!(category("fr", "0"), "one");
+    assert_eq!(category("pt-BR", "1"), "one");
+    assert_eq!(category("ru", "1"), "one");
+    assert_eq!(category("ru", "11"), "many");
+    assert_eq!(category("ru", "24"), "few");
+    assert_eq!(category("pl", "21"), "many");
+    assert_eq!(category("cs", "3"), "few");
+}
+
+#[test]
+fn test_translation_check_finds_missing_plural_forms() -> Result<(), Box<dyn std::error::Error>> {
+    let dir = TempDir::new()?;
+    fs::write(
+        dir.path().join("ru.json"),
+        r#"{"pressure.lines": {"one": "{count} строка", "other": "{count} строки"}}"#,
+    )?;
+    fs::write(
+        dir.path().join("de.json"),
+        r#"{"pressure.lines": {"one": "{count} Zeile"}}"#,
+    )?;
+
+    let i18n = I18n::with_overrides(dir.path());
+    let report = check_translations(&i18n, None);
+
+    assert_eq!(
+        report.plural_gaps,
+        [PluralGap {
+            key: "pressure.lines".to_string(),
+            language: "ru".to_string(),
+            missing: vec!["few".to_string(), "many".to_string()],
+        }]
+    );
+    // A plural message needs an `other` form to be loaded at all.
+    assert!(i18n.problems()[0].to_string().contains("de.json"));
+
+    dir.close()?;
+    Ok(())
+}
+
+#[test]
+fn test_locale_names_map_to_languages() {
+    let code = |locale: &str| language_from_locale(locale).map(|lang| lang.code());
+
+    assert_eq!(code("zh_CN.UTF-8"), Some("zh-CN"));
+    assert_eq!(code("en_US.UTF-8"), Some("en-US"));
+    assert_eq!(code("zh_TW.Big5@stroke"), Some("zh-TW"));
+    assert_eq!(code("C.UTF-8"), None);
+    assert_eq!(code("POSIX"), None);
+    // No translations for French.
+    assert_eq!(code("fr_FR.UTF-8"), None);
+}
+
+#[test]
+fn test_embedded_translations_are_complete() -> Result<(), LocalizedError> {
+    let sources = read_sources(&Path::new(env!("CARGO_MANIFEST_DIR")).join("src"))?;
+    let report = check_translations(&I18n::new(), Some(&sources));
+
+    assert!(report.is_clean(), "{:#?}", report);
+    Ok(())
+}
+
+#[test]
+fn test_translation_check_finds_gaps() -> Result<(), Box<dyn std::error::Error>> {
+    let dir = TempDir::new()?;
+    fs::write(dir.path().join("ja.json"), r#"{"app.title": "今日の運勢"}"#)?;
+    fs::write(
+        dir.path().join("zh.json"),
+        r#"{"config.updated": "已更新", "only.chinese": "仅中文"}"#,
+    )?;
+    let sources = vec![
+        r#"i18n("app.title", lang); i18n("fortune.excelent", lang);"#.to_string(),
+        r#"i18n(&format!("pressure.error.{}", key), lang)"#.to_string(),
+        r#"Err(LocalizedError::new("file.error.raed").with("path", path))"#.to_string(),
+        r#"let fill = |key: &str, args: &MessageArgs| {
+            i18n_args(&format!("pressure.error.{}", key), lang, args)
+        };
+        fill("timeout", &[]);
+        fill("time_out", &[]);"#
+            .to_string(),
+    ];
+
+    let report = check_translations(&I18n::with_overrides(dir.path()), Some(&sources));
+
+    let ja = &report.missing["ja"];
+    assert!(ja.contains(&"color.blue".to_string()));
+    assert!(!ja.contains(&"app.title".to_string()));
+    assert_eq!(report.missing["en"], ["only.chinese"]);
+    assert_eq!(
+        report.undefined,
+        [
+            "file.error.raed",
+            "fortune.excelent",
+            "pressure.error.time_out"
+        ]
+    );
+    let unused = report.unused.as_ref().unwrap();
+    assert!(unused.contains(&"color.blue".to_string()));
+    assert!(!unused.contains(&"pressure.error.timeout".to_string()));
+    assert_eq!(
+        report.placeholder_mismatches,
+        [PlaceholderMismatch {
+            key: "config.updated".to_string(),
+            language: "zh".to_string(),
+            expected: vec!["key".to_string()],
+            found: vec![],
+        }]
+    );
+    assert!(!report.is_clean());
+
+    // Without sources only the locales are compared.
+    let report = check_translations(&I18n::with_overrides(dir.path()), None);
+    assert!(report.unused.is_none() && report.undefined.is_empty());
+
+    dir.close()?;
+    Ok(())
+}
@@ -0,0 +1,1147 @@
+use assert_fs::TempDir;
+use chrono::{DateTime, Local, NaiveDate, TimeZone};
+use findme::{
+    analyze_dev_pressure_in, analyze_repos, bar_chart, daily_values, detect_regressions,
+    expand_repo_patterns, inspect_repository, measure_git_churn, parse_build_messages,
+    parse_cargo_test_output, parse_go_test_json, parse_pytest_output, parse_test_counts,
+    parse_timing_report, run_command, select_probes, summarize_builds, BuildKind, BuildRecord,
+    CustomProbe, Language, PatternProblem, PressureCache, PressureError, PressureHistory,
+    PressureLevel, PressureOptions, PressureSnapshot, Probe, ProbeControl, ProbeKind, ProbeResult,
+    ScoreInputs, ScoreMetric, ScoringModel, ScoringProblem, TrendMetric, BUILD_FAILED,
+    BUILD_SECONDS, TESTS_FAILED, TESTS_PASSED,
+};
+use git2::{BranchType, Commit, Oid, Repository, Signature};
+use std::fs;
+use std::path::Path;
+use std::thread;
+use std::time::{Duration, Instant};
+
+/// A repository in a temp dir, driven through git2 so tests need no `git` binary.
+struct Fixture {
+    dir: TempDir,
+    repo: Repository,
+}
+
+impl Fixture {
+    fn new() -> Result<Fixture, Box<dyn std::error::Error>> {
+        let dir = TempDir::new()?;
+        let repo = Repository::init(dir.path())?;
+        Ok(Fixture { dir, repo })
+    }
+
+    fn path(&self) -> &Path {
+        self.dir.path()
+    }
+
+    fn write(&self, path: &str, content: &str) -> Result<(), Box<dyn std::error::Error>> {
+        let full_path = self.path().join(path);
+        if let Some(parent) = full_path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write(full_path, content)?;
+        Ok(())
+    }
+
+    fn stage(&self, path: &str) -> Result<(), Box<dyn std::error::Error>> {
+        let mut index = self.repo.index()?;
+        index.add_path(Path::new(path))?;
+        index.write()?;
+        Ok(())
+    }
+
+    fn stage_all(&self) -> Result<(), Box<dyn std::error::Error>> {
+        let mut index = self.repo.index()?;
+        index.add_all(["*"], git2::IndexAddOption::DEFAULT, None)?;
+        index.write()?;
+        Ok(())
+    }
+
+    /// Commits the index on top of HEAD.
+    fn commit(&self, message: &str) -> Result<Oid, Box<dyn std::error::Error>> {
+        let signature = Signature::now("Test", "test@example.com")?;
+        let tree = self.repo.find_tree(self.repo.index()?.write_tree()?)?;
+        let parent = self
+            .repo
+            .head()
+            .ok()
+            .and_then(|head| head.peel_to_commit().ok());
+        let parents: Vec<&Commit> = parent.iter().collect();
+        Ok(self.repo.commit(
+            Some("HEAD"),
+            &signature,
+            &signature,
+            message,
+            &tree,
+            &parents,
+        )?)
+    }
+
+    fn branch(&self) -> String {
+        self.repo.head().unwrap().shorthand().unwrap().to_string()
+    }
+}
+
+fn lines(count: usize) -> String {
+    (0..count).map(|i| format!("line {}\n", i)).collect()
+}
+
+#[test]
+fn test_git_churn_counts_commits_and_uncommitted_changes() -> Result<(), Box<dyn std::error::Error>>
+{
+    let fixture = Fixture::new()?;
+    fixture.write("main.rs", &lines(10))?;
+    fixture.write("vendor/dep.rs", &lines(500))?;
+    fixture.write("Cargo.lock", &lines(200))?;
+    fixture.stage_all()?;
+    fixture.commit("initial")?;
+
+    // 4 lines replaced (4 added, 4 deleted) and 2 appended, left uncommitted.
+    let mut edited: String = (0..10)
+        .map(|i| match i {
+            0..=3 => format!("changed {}\n", i),
+            _ => format!("line {}\n", i),
+        })
+        .collect();
+    edited.push_str("new a\nnew b\n");
+    fixture.write("main.rs", &edited)?;
+    fixture.write("staged.rs", &lines(3))?;
+    fixture.stage("staged.rs")?;
+
+    let churn = measure_git_churn(fixture.path(), &PressureOptions::default())?;
+
+    assert_eq!(churn.added, 10 + 6 + 3);
+    assert_eq!(churn.deleted, 4);
+    assert_eq!(churn.uncommitted, 6 + 4 + 3);
+    assert_eq!(churn.files, 2);
+    assert_eq!(churn.excluded_files, 2);
+    assert_eq!(churn.window_hours, 24);
+
+    Ok(())
+}
+
+#[test]
+fn test_git_churn_respects_custom_excludes() -> Result<(), Box<dyn std::error::Error>> {
+    let fixture = Fixture::new()?;
+    fixture.write("generated/api.rs", &lines(50))?;
+    fixture.write("lib.rs", &lines(5))?;
+    fixture.stage_all()?;
+    fixture.commit("initial")?;
+
+    let options = PressureOptions {
+        window_hours: 48,
+        exclude: vec!["generated/**".to_string()],
+        ..PressureOptions::default()
+    };
+    let churn = measure_git_churn(fixture.path(), &options)?;
+
+    assert_eq!(churn.total_lines(), 5);
+    assert_eq!(churn.files, 1);
+    assert_eq!(churn.excluded_files, 1);
+
+    let options = PressureOptions {
+        exclude: vec!["generated/[".to_string()],
+        ..PressureOptions::default()
+    };
+    let error = measure_git_churn(fixture.path(), &options).unwrap_err();
+    assert!(matches!(
+        &error,
+        PressureError::Pattern { setting, pattern, problem: PatternProblem::Syntax { .. } }
+            if setting == "pressure.exclude" && pattern == "generated/["
+    ));
+    assert!(error
+        .message(Language::CHINESE)
+        .starts_with("pressure.exclude 中的模式 'generated/[' 无效: 无法解析"));
+
+    Ok(())
+}
+
+#[test]
+fn test_git_churn_outside_repository_is_zero() -> Result<(), Box<dyn std::error::Error>> {
+    let dir = TempDir::new()?;
+
+    let churn = measure_git_churn(dir.path(), &PressureOptions::default())?;
+
+    assert_eq!(churn.total_lines(), 0);
+    assert_eq!(churn.files, 0);
+    assert!(inspect_repository(dir.path())?.is_none());
+
+    Ok(())
+}
+
+#[test]
+fn test_repo_signals_for_fresh_repository() -> Result<(), Box<dyn std::error::Error>> {
+    let fixture = Fixture::new()?;
+    fixture.write("notes.txt", "todo\n")?;
+
+    let signals = inspect_repository(fixture.path())?.unwrap();
+
+    assert!(signals.branch.is_some());
+    assert_eq!(signals.ahead, None);
+    assert_eq!(signals.last_commit_age_seconds, None);
+    assert_eq!(signals.untracked_files, 1);
+    assert_eq!(signals.stash_count, 0);
+    assert_eq!(signals.conflicted_files, 0);
+    assert_eq!(signals.in_progress, None);
+
+    Ok(())
+}
+
+#[test]
+fn test_repo_signals_ahead_behind_and_stash() -> Result<(), Box<dyn std::error::Error>> {
+    let mut fixture = Fixture::new()?;
+    fixture.write("lib.rs", &lines(1))?;
+    fixture.stage_all()?;
+    let base = fixture.commit("base")?;
+
+    // The upstream moved on by one commit that is not in the local branch.
+    let signature = Signature::now("Test", "test@example.com")?;
+    let upstream = {
+        let base_commit = fixture.repo.find_commit(base)?;
+        fixture.repo.commit(
+            None,
+            &signature,
+            &signature,
+            "upstream",
+            &base_commit.tree()?,
+            &[&base_commit],
+        )?
+    };
+    fixture
+        .repo
+        .reference("refs/remotes/origin/main", upstream, true, "fixture")?;
+    fixture
+        .repo
+        .remote("origin", "https://example.com/repo.git")?;
+
+    fixture.write("lib.rs", &lines(2))?;
+    fixture.stage_all()?;
+    fixture.commit("local one")?;
+    fixture.write("lib.rs", &lines(3))?;
+    fixture.stage_all()?;
+    fixture.commit("local two")?;
+
+    let branch = fixture.branch();
+    fixture
+        .repo
+        .find_branch(&branch, BranchType::Local)?
+        .set_upstream(Some("origin/main"))?;
+
+    fixture.write("lib.rs", &lines(4))?;
+    fixture.repo.stash_save(&signature, "wip", None)?;
+
+    let signals = inspect_repository(fixture.path())?.unwrap();
+
+    assert_eq!(signals.branch, Some(branch));
+    assert_eq!(signals.ahead, Some(2));
+    assert_eq!(signals.behind, Some(1));
+    assert_eq!(signals.stash_count, 1);
+    assert!(signals.last_commit_age_seconds.unwrap() < 3600);
+
+    Ok(())
+}
+
+#[test]
+fn test_repo_signals_report_merge_conflicts() -> Result<(), Box<dyn std::error::Error>> {
+    let fixture = Fixture::new()?;
+    fixture.write("shared.rs", "original\n")?;
+    fixture.stage_all()?;
+    let base = fixture.commit("base")?;
+
+    let main_branch = fixture.branch();
+    fixture
+        .repo
+        .branch("feature", &fixture.repo.find_commit(base)?, false)?;
+
+    fixture.write("shared.rs", "ours\n")?;
+    fixture.stage_all()?;
+    fixture.commit("ours")?;
+
+    fixture.repo.set_head("refs/heads/feature")?;
+    fixture
+        .repo
+        .checkout_head(Some(git2::build::CheckoutBuilder::new().force()))?;
+    fixture.write("shared.rs", "theirs\n")?;
+    fixture.stage_all()?;
+    let theirs = fixture.commit("theirs")?;
+
+    fixture
+        .repo
+        .set_head(&format!("refs/heads/{}", main_branch))?;
+    fixture
+        .repo
+        .checkout_head(Some(git2::build::CheckoutBuilder::new().force()))?;
+    let annotated = fixture.repo.find_annotated_commit(theirs)?;
+    fixture.repo.merge(&[&annotated], None, None)?;
+
+    let signals = inspect_repository(fixture.path())?.unwrap();
+
+    assert_eq!(signals.conflicted_files, 1);
+    assert_eq!(signals.in_progress.as_deref(), Some("merge"));
+
+    Ok(())
+}
+
+const MULTI_BINARY_STDOUT: &str = "
+running 3 tests
+test bad ... FAILED
+test skipped ... ignored
+test tests::it_works ... ok
+
+failures:
+
+---- bad stdout ----
+assertion `left == right` failed
+
+failures:
+    bad
+
+test result: FAILED. 1 passed; 1 failed; 1 ignored; 0 measured; 0 filtered out; finished in 0.01s
+
+
+running 2 tests
+test fine ... ok
+test also_bad ... FAILED
+
+test result: FAILED. 1 passed; 1 failed; 0 ignored; 0 measured; 3 filtered out; finished in 0.00s
+
+
+running 0 tests
+
+test result: ok. 0 passed; 0 failed; 0 ignored; 0 measured; 0 filtered out; finished in 0.00s
+";
+
+const MULTI_BINARY_STDERR: &str = "   Compiling demo v0.1.0 (/tmp/demo)
+    Finished `test` profile [unoptimized + debuginfo] target(s) in 0.41s
+     Running unittests src/lib.rs (target/debug/deps/demo-17b8969a311cf42f)
+error: test failed, to rerun pass `--lib`
+     Running tests/it.rs (target/debug/deps/it-98aebdcaf9ba814c)
+error: test failed, to rerun pass `--test it`
+   Doc-tests demo
+error: 2 targets failed:
+";
+
+#[test]
+fn test_cargo_test_output_is_summed_per_binary() {
+    let summary = parse_cargo_test_output(MULTI_BINARY_STDOUT, MULTI_BINARY_STDERR);
+
+    let names: Vec<&str> = summary
+        .binaries
+        .iter()
+        .map(|binary| binary.name.as_str())
+        .collect();
+    assert_eq!(
+        names,
+        ["unittests src/lib.rs", "tests/it.rs", "doc-tests demo"]
+    );
+    assert_eq!(summary.passed(), 2);
+    assert_eq!(summary.failed(), 2);
+    assert_eq!(summary.ignored(), 1);
+    assert_eq!(summary.filtered_out(), 3);
+    assert_eq!(summary.success_rate(), Some(0.5));
+    assert!(summary.compiled());
+
+    let failing: Vec<(&str, &str)> = summary.failing_tests().collect();
+    assert_eq!(
+        failing,
+        [("unittests src/lib.rs", "bad"), ("tests/it.rs", "also_bad")]
+    );
+}
+
+#[test]
+fn test_cargo_test_compile_errors_are_separated() {
+    let stderr = "   Compiling demo v0.1.0 (/tmp/demo)
+error[E0308]: mismatched types
+ --> tests/it.rs:2:21
+  |
+2 | fn broken() -> u8 { \"x\" }
+  |                     ^^^ expected `u8`, found `&str`
+
+For more information about this error, try `rustc --explain E0308`.
+error: could not compile `demo` (test \"it\") due to 1 previous error
+";
+
+    let summary = parse_cargo_test_output("", stderr);
+
+    assert!(summary.binaries.is_empty());
+    assert!(!summary.compiled());
+    assert_eq!(
+        summary.compile_errors,
+        ["error[E0308]: mismatched types (tests/it.rs:2:21)"]
+    );
+    assert_eq!(summary.success_rate(), Some(0.0));
+}
+
+#[test]
+fn test_cargo_test_without_tests_has_no_rate() {
+    let summary = parse_cargo_test_output(
+        "\nrunning 0 tests\n\ntest result: ok. 0 passed; 0 failed; 0 ignored; 0 measured; 0 filtered out; finished in 0.00s\n",
+        "     Running unittests src/main.rs (target/debug/deps/demo-1)\n",
+    );
+
+    assert_eq!(summary.binaries.len(), 1);
+    assert_eq!(summary.success_rate(), None);
+}
+
+#[test]
+fn test_pressure_cache_follows_working_tree() -> Result<(), Box<dyn std::error::Error>> {
+    let fixture = Fixture::new()?;
+    let cache_dir = TempDir::new()?;
+    fixture.write("lib.rs", &lines(3))?;
+    fixture.stage_all()?;
+    fixture.commit("initial")?;
+
+    let results = vec![ProbeResult::from_build("make", 42.0, true)];
+    let mut cache = PressureCache::open(cache_dir.path(), fixture.path(), 60)?;
+    assert_eq!(cache.results(ProbeKind::Build), None);
+    cache.set_results(ProbeKind::Build, &results);
+    cache.save()?;
+
+    let cache = PressureCache::open(cache_dir.path(), fixture.path(), 60)?;
+    assert_eq!(cache.results(ProbeKind::Build), Some(results));
+    assert_eq!(cache.results(ProbeKind::Tests), None);
+
+    let expired = PressureCache::open(cache_dir.path(), fixture.path(), 0)?;
+    assert_eq!(expired.results(ProbeKind::Build), None);
+
+    fixture.write("lib.rs", &lines(4))?;
+    let changed = PressureCache::open(cache_dir.path(), fixture.path(), 60)?;
+    assert_eq!(changed.results(ProbeKind::Build), None);
+
+    cache_dir.close()?;
+    Ok(())
+}
+
+fn build(id: &str, finished_at: i64, seconds: f32, kind: BuildKind) -> BuildRecord {
+    BuildRecord {
+        id: id.to_string(),
+        finished_at,
+        seconds,
+        kind,
+        dirty_units: 10,
+        total_units: if kind == BuildKind::Clean { 10 } else { 40 },
+    }
+}
+
+fn timing_report(dirty: u32, total: u32, time: &str) -> String {
+    format!(
+        "<table class=\"my-table summary-table\">
+<tr><td>Targets:</td><td>demo 0.1.0 (lib, bin \"demo\")</td></tr>
+<tr><td>Fresh units:</td><td>{}</td></tr>
+<tr><td>Dirty units:</td><td>{}</td></tr>
+<tr><td>Total units:</td><td>{}</td></tr>
+<tr><td>Total time:</td><td>{}</td></tr>
+</table>",
+        total - dirty,
+        dirty,
+        total,
+        time
+    )
+}
+
+#[test]
+fn test_timing_report_is_parsed() {
+    let name = "cargo-timing-20240603T101530123Z-fe6b82a455136481.html";
+
+    let record = parse_timing_report(name, &timing_report(3, 40, "75.3s (1m 15.3s)")).unwrap();
+    assert_eq!(record.id, name);
+    assert_eq!(record.finished_at, 1_717_409_730);
+    assert_eq!(record.seconds, 75.3);
+    assert_eq!(record.kind, BuildKind::Incremental);
+    assert_eq!((record.dirty_units, record.total_units), (3, 40));
+
+    let clean = parse_timing_report(name, &timing_report(40, 40, "12.0s")).unwrap();
+    assert_eq!(clean.kind, BuildKind::Clean);
+
+    // No-op builds and the `cargo-timing.html` copy are not builds of their own.
+    assert_eq!(
+        parse_timing_report(name, &timing_report(0, 40, "0.1s")),
+        None
+    );
+    assert_eq!(
+        parse_timing_report("cargo-timing.html", &timing_report(3, 40, "2.0s")),
+        None
+    );
+}
+
+#[test]
+fn test_build_messages_count_dirty_units() {
+    let stdout = r#"{"reason":"compiler-artifact","package_id":"a","fresh":true}
+{"reason":"compiler-artifact","package_id":"b","fresh":false}
+{"reason":"compiler-message","package_id":"b","message":{}}
+{"reason":"compiler-artifact","package_id":"c","fresh":false}
+{"reason":"build-finished","success":true}
+"#;
+
+    assert_eq!(parse_build_messages(stdout), (2, 3, true));
+    assert_eq!(
+        parse_build_messages(r#"{"reason":"build-finished","success":false}"#),
+        (0, 0, false)
+    );
+}
+
+#[test]
+fn test_build_medians_are_split_by_kind() {
+    let builds = [
+        build("a", 1, 40.0, BuildKind::Clean),
+        build("b", 2, 4.0, BuildKind::Incremental),
+        build("c", 3, 50.0, BuildKind::Clean),
+        build("d", 4, 6.0, BuildKind::Incremental),
+        build("e", 5, 8.0, BuildKind::Incremental),
+    ];
+
+    let timings = summarize_builds(&builds).unwrap();
+    assert_eq!(timings.latest.id, "e");
+    assert_eq!(timings.median_seconds, 8.0);
+    assert_eq!(timings.clean_median_seconds, Some(45.0));
+    assert_eq!(timings.incremental_median_seconds, Some(6.0));
+    assert_eq!(timings.recorded_builds, 5);
+
+    assert_eq!(summarize_builds(&[]), None);
+}
+
+#[test]
+fn test_failed_cargo_build_is_reported() -> Result<(), Box<dyn std::error::Error>> {
+    let dir = TempDir::new()?;
+    fs::write(
+        dir.path().join("Cargo.toml"),
+        "[package]\nname = \"broken\"\nversion = \"0.1.0\"\nedition = \"2021\"\n",
+    )?;
+    fs::create_dir(dir.path().join("src"))?;
+    fs::write(
+        dir.path().join("src").join("main.rs"),
+        "fn main() { nope }\n",
+    )?;
+    let options = PressureOptions {
+        history_dir: Some(dir.path().join("history")),
+        ..PressureOptions::default()
+    };
+    let build = |options: &PressureOptions| {
+        let probes = select_probes(dir.path(), ProbeKind::Build, &[]);
+        assert_eq!(probes[0].name(), "cargo");
+        probes[0].run(dir.path(), options)
+    };
+
+    let result = build(&options)?;
+    assert_eq!(result.metric(BUILD_FAILED), Some(1.0));
+    assert!(result.metric(BUILD_SECONDS).is_some());
+    assert_eq!(result.builds, None);
+
+    // Once fixed, the build is recorded and the failure clears.
+    fs::write(dir.path().join("src").join("main.rs"), "fn main() {}\n")?;
+    let result = build(&options)?;
+    assert_eq!(result.metric(BUILD_FAILED), Some(0.0));
+    assert_eq!(
+        result.builds.as_ref().map(|builds| builds.recorded_builds),
+        Some(1)
+    );
+
+    // The recorded build answers until a refresh builds again.
+    fs::write(
+        dir.path().join("src").join("main.rs"),
+        "fn main() { nope }\n",
+    )?;
+    let recorded = build(&options)?;
+    assert_eq!(recorded.metric(BUILD_FAILED), Some(0.0));
+    assert_eq!(recorded.builds, result.builds);
+    let refreshed = build(&PressureOptions {
+        refresh: true,
+        ..options.clone()
+    })?;
+    assert_eq!(refreshed.metric(BUILD_FAILED), Some(1.0));
+
+    dir.close()?;
+    Ok(())
+}
+
+#[test]
+fn test_timing_reports_are_used_without_building() -> Result<(), Box<dyn std::error::Error>> {
+    let dir = TempDir::new()?;
+    fs::write(
+        dir.path().join("Cargo.toml"),
+        "[package]\nname = \"timed\"\nversion = \"0.1.0\"\nedition = \"2021\"\n",
+    )?;
+    fs::create_dir(dir.path().join("src"))?;
+    fs::write(
+        dir.path().join("src").join("main.rs"),
+        "fn main() { nope }\n",
+    )?;
+    let timings = dir.path().join("target").join("cargo-timings");
+    fs::create_dir_all(&timings)?;
+    fs::write(
+        timings.join("cargo-timing-20240603T101530123Z-fe6b82a455136481.html"),
+        timing_report(3, 40, "12.0s"),
+    )?;
+
+    let probes = select_probes(dir.path(), ProbeKind::Build, &[]);
+    let result = probes[0].run(dir.path(), &PressureOptions::default())?;
+
+    assert_eq!(result.metric(BUILD_FAILED), Some(0.0));
+    assert_eq!(result.metric(BUILD_SECONDS), Some(12.0));
+    assert_eq!(result.builds.map(|builds| builds.recorded_builds), Some(1));
+    assert!(!dir.path().join("target").join("debug").exists());
+
+    dir.close()?;
+    Ok(())
+}
+
+#[test]
+fn test_runner_counts_are_read_from_common_formats() {
+    let jest = "Test Suites: 1 failed, 2 passed, 3 total
+Tests:       2 failed, 1 skipped, 9 passed, 12 total
+Snapshots:   0 total";
+    let counts = parse_test_counts("npm", jest).unwrap();
+    assert_eq!((counts.passed, counts.failed, counts.ignored), (9, 2, 1));
+
+    let mocha = "  14 passing (20ms)\n  2 pending\n  1 failing\n";
+    let counts = parse_test_counts("npm", mocha).unwrap();
+    assert_eq!((counts.passed, counts.failed, counts.ignored), (14, 1, 2));
+
+    let node = "# tests 5\n# suites 0\n# pass 4\n# fail 1\n# cancelled 0\n";
+    let counts = parse_test_counts("npm", node).unwrap();
+    assert_eq!((counts.passed, counts.failed), (4, 1));
+
+    assert_eq!(parse_test_counts("npm", "Build succeeded"), None);
+}
+
+#[test]
+fn test_pytest_output_is_parsed() {
+    let output = "..F.s
+=========================== short test summary info ============================
+FAILED tests/test_math.py::test_add - assert 3 == 4
+========================= 1 failed, 3 passed, 1 skipped in 0.12s =========================
+";
+    let summary = parse_pytest_output(output);
+    assert_eq!(summary.passed(), 3);
+    assert_eq!(summary.failed(), 1);
+    assert_eq!(summary.ignored(), 1);
+    let failing: Vec<(&str, &str)> = summary.failing_tests().collect();
+    assert_eq!(failing, [("pytest", "tests/test_math.py::test_add")]);
+
+    let broken = "ERROR tests/test_io.py - ModuleNotFoundError: No module named 'yaml'
+!!!!!!!!!!!!!!!!!!!! Interrupted: 1 error during collection !!!!!!!!!!!!!!!!!!!!
+=============================== 1 error in 0.05s ===============================
+";
+    let summary = parse_pytest_output(broken);
+    assert!(!summary.compiled());
+    assert_eq!(summary.success_rate(), Some(0.0));
+}
+
+#[test]
+fn test_go_test_json_is_parsed_per_package() {
+    let stdout = r#"{"Action":"run","Package":"example.com/app","Test":"TestA"}
+{"Action":"pass","Package":"example.com/app","Test":"TestA","Elapsed":0}
+{"Action":"run","Package":"example.com/app","Test":"TestB"}
+{"Action":"fail","Package":"example.com/app","Test":"TestB/sub","Elapsed":0}
+{"Action":"fail","Package":"example.com/app","Test":"TestB","Elapsed":0}
+{"Action":"fail","Package":"example.com/app","Elapsed":0.1}
+{"Action":"skip","Package":"example.com/app/util","Test":"TestSlow","Elapsed":0}
+{"Action":"skip","Package":"example.com/app/empty","Elapsed":0}
+"#;
+    let summary = parse_go_test_json(stdout, "");
+    let names: Vec<&str> = summary
+        .binaries
+        .iter()
+        .map(|binary| binary.name.as_str())
+        .collect();
+    assert_eq!(names, ["example.com/app", "example.com/app/util"]);
+    assert_eq!(
+        (summary.passed(), summary.failed(), summary.ignored()),
+        (1, 1, 1)
+    );
+    assert!(summary.compiled());
+
+    let build_failed = r##"{"ImportPath":"example.com/app [example.com/app.test]","Action":"build-output","Output":"# example.com/app\n"}
+{"ImportPath":"example.com/app [example.com/app.test]","Action":"build-output","Output":"./app_test.go:5:2: undefined: missing\n"}
+{"Action":"fail","Package":"example.com/app","Elapsed":0}
+"##;
+    let summary = parse_go_test_json(build_failed, "");
+    assert_eq!(
+        summary.compile_errors,
+        ["./app_test.go:5:2: undefined: missing"]
+    );
+}
+
+#[test]
+fn test_custom_probe_extracts_counts_with_regex() -> Result<(), Box<dyn std::error::Error>> {
+    let dir = TempDir::new()?;
+    let probe = CustomProbe {
+        name: "suite".to_string(),
+        kind: ProbeKind::Tests,
+        command: vec!["echo".to_string(), "ok=7 bad=2".to_string()],
+        passed: Some(r"ok=(\d+)".to_string()),
+        failed: Some(r"bad=(\d+)".to_string()),
+        timeout_seconds: None,
+    };
+    probe.validate()?;
+
+    let result = probe.run(dir.path(), &PressureOptions::default())?;
+    assert_eq!(result.metric(TESTS_PASSED), Some(7.0));
+    assert_eq!(result.metric(TESTS_FAILED), Some(2.0));
+
+    let unmatched = CustomProbe {
+        passed: Some(r"passed=(\d+)".to_string()),
+        failed: None,
+        ..probe.clone()
+    };
+    assert_eq!(
+        unmatched.run(dir.path(), &PressureOptions::default()),
+        Err(PressureError::NoCountMatch {
+            probe: "suite".to_string(),
+        })
+    );
+
+    let no_group = CustomProbe {
+        passed: Some(r"ok=\d+".to_string()),
+        ..probe
+    };
+    assert!(no_group.validate().is_err());
+    let error = no_group
+        .run(dir.path(), &PressureOptions::default())
+        .unwrap_err();
+    assert_eq!(
+        error,
+        PressureError::CountPattern {
+            probe: "suite".to_string(),
+            pattern: r"ok=\d+".to_string(),
+            problem: PatternProblem::NoCaptureGroup,
+        }
+    );
+    assert_eq!(
+        error.message(Language::CHINESE),
+        r"suite: 计数模式 'ok=\d+' 不可用: 需要一个捕获计数的分组"
+    );
+
+    dir.close()?;
+    Ok(())
+}
+
+#[test]
+fn test_failed_probe_keeps_the_other_results() -> Result<(), Box<dyn std::error::Error>> {
+    let fixture = Fixture::new()?;
+    let probe = |name: &str, command: &[&str]| CustomProbe {
+        name: name.to_string(),
+        kind: ProbeKind::Tests,
+        command: command.iter().map(|part| part.to_string()).collect(),
+        passed: Some(r"ok=(\d+)".to_string()),
+        failed: None,
+        timeout_seconds: None,
+    };
+    let missing = probe("lint", &["definitely-not-a-tool"]);
+    let error = missing
+        .run(fixture.path(), &PressureOptions::default())
+        .unwrap_err();
+    assert_eq!(
+        error,
+        PressureError::ToolMissing {
+            probe: "lint".to_string(),
+            tool: "definitely-not-a-tool".to_string(),
+        }
+    );
+    assert!(error
+        .message(Language::CHINESE)
+        .contains("找不到 definitely-not-a-tool"));
+
+    let options = PressureOptions {
+        probes: vec![ProbeKind::Git, ProbeKind::Tests],
+        custom_probes: vec![probe("suite", &["echo", "ok=4"]), missing],
+        ..PressureOptions::default()
+    };
+    let pressure = analyze_dev_pressure_in(fixture.path(), &options, Language::ENGLISH)?;
+    assert_eq!(pressure.errors, [error]);
+    assert_eq!(pressure.failed_probes, [ProbeKind::Tests]);
+    assert_eq!(pressure.results.len(), 1);
+    assert_eq!(pressure.results[0].metric(TESTS_PASSED), Some(4.0));
+
+    let outside = TempDir::new()?;
+    let pressure = analyze_dev_pressure_in(
+        outside.path(),
+        &PressureOptions::default(),
+        Language::ENGLISH,
+    )?;
+    assert!(matches!(
+        pressure.errors[..],
+        [PressureError::NotARepository { .. }]
+    ));
+    assert_eq!(pressure.failed_probes, [ProbeKind::Git]);
+
+    // A broken repository costs the git probe and the commit, not the other probes.
+    fs::write(outside.path().join(".git"), "garbage")?;
+    let options = PressureOptions {
+        probes: vec![ProbeKind::Tests],
+        custom_probes: vec![probe("suite", &["echo", "ok=4"])],
+        ..PressureOptions::default()
+    };
+    let pressure = analyze_dev_pressure_in(outside.path(), &options, Language::ENGLISH)?;
+    assert_eq!(pressure.commit, None);
+    assert!(matches!(pressure.errors[..], [PressureError::Git { .. }]));
+    assert_eq!(pressure.results[0].metric(TESTS_PASSED), Some(4.0));
+    let pressure = analyze_dev_pressure_in(
+        outside.path(),
+        &PressureOptions::default(),
+        Language::ENGLISH,
+    )?;
+    assert!(matches!(pressure.errors[..], [PressureError::Git { .. }]));
+    assert_eq!(pressure.failed_probes, [ProbeKind::Git]);
+    fs::remove_file(outside.path().join(".git"))?;
+
+    let missing_dir = outside.path().join("missing");
+    assert!(matches!(
+        analyze_dev_pressure_in(&missing_dir, &PressureOptions::default(), Language::ENGLISH),
+        Err(PressureError::NotADirectory { .. })
+    ));
+
+    outside.close()?;
+    Ok(())
+}
+
+#[test]
+fn test_probe_that_runs_too_long_is_stopped() -> Result<(), Box<dyn std::error::Error>> {
+    let dir = TempDir::new()?;
+    let probe = CustomProbe {
+        name: "slow".to_string(),
+        kind: ProbeKind::Tests,
+        // The shell's child must be stopped too, or it would hold the output open.
+        command: ["sh", "-c", "sleep 30; echo 3 passed"]
+            .iter()
+            .map(|part| part.to_string())
+            .collect(),
+        passed: None,
+        failed: None,
+        timeout_seconds: Some(1),
+    };
+    let options = PressureOptions {
+        probes: vec![ProbeKind::Tests],
+        custom_probes: vec![probe],
+        ..PressureOptions::default()
+    };
+
+    let started = Instant::now();
+    let pressure = analyze_dev_pressure_in(dir.path(), &options, Language::ENGLISH)?;
+    assert!(started.elapsed() < Duration::from_secs(10));
+    assert_eq!(
+        pressure.errors,
+        [PressureError::Timeout {
+            probe: "slow".to_string(),
+            seconds: 1,
+        }]
+    );
+    assert_eq!(pressure.timed_out_probes, [ProbeKind::Tests]);
+    assert_eq!(pressure.failed_probes, [ProbeKind::Tests]);
+    assert!(options.control.running().is_empty());
+
+    dir.close()?;
+    Ok(())
+}
+
+#[test]
+fn test_cancelled_command_is_stopped() -> Result<(), Box<dyn std::error::Error>> {
+    let dir = TempDir::new()?;
+    let control = ProbeControl::default();
+    let canceller = control.clone();
+    let handle = thread::spawn(move || {
+        thread::sleep(Duration::from_millis(200));
+        canceller.cancel();
+    });
+
+    let started = Instant::now();
+    let result = run_command(dir.path(), "slow", &["sleep", "30"], 0, &control);
+    handle.join().unwrap();
+    assert!(started.elapsed() < Duration::from_secs(10));
+    assert_eq!(
+        result.unwrap_err(),
+        PressureError::Cancelled {
+            probe: "slow".to_string()
+        }
+    );
+
+    // Nothing starts once the run is cancelled.
+    let result = run_command(dir.path(), "quick", &["echo", "hi"], 0, &control);
+    assert!(matches!(result, Err(PressureError::Cancelled { .. })));
+
+    let output = run_command(
+        dir.path(),
+        "quick",
+        &["echo", "hi"],
+        5,
+        &ProbeControl::default(),
+    )?;
+    assert_eq!(String::from_utf8_lossy(&output.stdout).trim(), "hi");
+
+    dir.close()?;
+    Ok(())
+}
+
+#[test]
+fn test_probes_are_detected_from_project_files() -> Result<(), Box<dyn std::error::Error>> {
+    let dir = TempDir::new()?;
+    let names = |kind| -> Vec<String> {
+        select_probes(dir.path(), kind, &[])
+            .iter()
+            .map(|probe| probe.name().to_string())
+            .collect()
+    };
+    assert!(names(ProbeKind::Tests).is_empty());
+
+    fs::write(dir.path().join("Makefile"), "all:\n\ttrue\n")?;
+    assert_eq!(names(ProbeKind::Build), ["make"]);
+
+    fs::write(
+        dir.path().join("package.json"),
+        r#"{"scripts": {"test": "jest", "build": "tsc"}}"#,
+    )?;
+    fs::write(dir.path().join("pnpm-lock.yaml"), "")?;
+    fs::write(dir.path().join("go.mod"), "module example.com/app\n")?;
+    assert_eq!(names(ProbeKind::Tests), ["pnpm", "go"]);
+    // A Makefile next to another build tool usually wraps it.
+    assert_eq!(names(ProbeKind::Build), ["pnpm"]);
+
+    let custom = CustomProbe {
+        name: "e2e".to_string(),
+        kind: ProbeKind::Tests,
+        command: vec!["./e2e.sh".to_string()],
+        passed: None,
+        failed: None,
+        timeout_seconds: None,
+    };
+    let selected = select_probes(dir.path(), ProbeKind::Tests, &[custom]);
+    assert_eq!(selected.len(), 1);
+    assert_eq!(selected[0].name(), "e2e");
+
+    dir.close()?;
+    Ok(())
+}
+
+#[test]
+fn test_default_scoring_keeps_the_original_thresholds() {
+    let model = ScoringModel::default();
+    let score = model.score(&ScoreInputs {
+        churn_lines: Some(180.0),
+        test_pass_rate: Some(0.6),
+        build_seconds: Some(45.0),
+        build_failed: false,
+    });
+
+    let points: Vec<f64> = score.contributions.iter().map(|c| c.points).collect();
+    assert_eq!(points, [30.0, 20.0, 20.0]);
+    assert_eq!(score.score, 70);
+    assert_eq!(score.level, PressureLevel::High);
+    assert_eq!(score.top_contribution().unwrap().metric, ScoreMetric::Churn);
+}
+
+#[test]
+fn test_score_is_scaled_to_the_whole_model() {
+    let model = ScoringModel::default();
+
+    // Churn keeps its own share: three of four thresholds give 30 of its 40 points.
+    let score = model.score(&ScoreInputs {
+        churn_lines: Some(150.0),
+        ..ScoreInputs::default()
+    });
+    assert_eq!(score.score, 30);
+    assert_eq!(score.contributions[0].max_points, 40.0);
+
+    // The default git-only run cannot saturate the index on churn alone.
+    let score = model.score(&ScoreInputs {
+        churn_lines: Some(5000.0),
+        ..ScoreInputs::default()
+    });
+    assert_eq!((score.score, score.level), (40, PressureLevel::Medium));
+
+    // A failed build crosses every build threshold.
+    let score = model.score(&ScoreInputs {
+        churn_lines: Some(0.0),
+        build_seconds: Some(2.0),
+        build_failed: true,
+        ..ScoreInputs::default()
+    });
+    let top = score.top_contribution().unwrap();
+    assert_eq!((top.metric, top.failed), (ScoreMetric::Build, true));
+
+    let nothing = model.score(&ScoreInputs::default());
+    assert_eq!((nothing.score, nothing.level), (0, PressureLevel::Low));
+    assert_eq!(nothing.top_contribution(), None);
+}
+
+#[test]
+fn test_scoring_model_is_read_from_repo_config() {
+    let model = ScoringModel::parse(
+        "[scoring]\nhigh = 50\n\n[scoring.churn]\nweight = 10\nthresholds = [500]\n",
+    )
+    .unwrap()
+    .unwrap();
+    assert_eq!(model.high, 50.0);
+    assert_eq!(model.churn.thresholds, [500.0]);
+    assert_eq!(model.tests, ScoringModel::default().tests);
+
+    assert_eq!(
+        ScoringModel::parse("name = \"other tool\"\n").unwrap(),
+        None
+    );
+    assert_eq!(
+        ScoringModel::parse("[scoring]\nmedium = 70\nhigh = 60\n"),
+        Err(ScoringProblem::LevelOrder)
+    );
+    assert!(ScoringModel::parse("[scoring.build]\nweight = 30\n").is_err());
+    assert_eq!(
+        ScoringModel::parse("[scoring.churn]\nweight = inf\nthresholds = [100]\n"),
+        Err(ScoringProblem::Weight {
+            metric: ScoreMetric::Churn
+        })
+    );
+    assert_eq!(
+        ScoringModel::parse("[scoring.tests]\nweight = 30\nthresholds = [0.5, 0.9]\n"),
+        Err(ScoringProblem::ThresholdOrder {
+            metric: ScoreMetric::Tests
+        })
+    );
+    assert!(matches!(
+        ScoringModel::parse("[scoring]\nmedum = 20\n"),
+        Err(ScoringProblem::Syntax { .. })
+    ));
+    assert_eq!(
+        ScoringProblem::ThresholdOrder {
+            metric: ScoreMetric::Tests
+        }
+        .message(Language::CHINESE),
+        "scoring.tests.thresholds 必须递减"
+    );
+}
+
+fn at(day: u32, hour: u32) -> DateTime<Local> {
+    Local.with_ymd_and_hms(2024, 6, day, hour, 0, 0).unwrap()
+}
+
+fn snapshot(
+    recorded_at: DateTime<Local>,
+    score: u32,
+    build_seconds: Option<f64>,
+) -> PressureSnapshot {
+    PressureSnapshot {
+        recorded_at,
+        repo: "/work/app".into(),
+        commit: Some("4da4b3b".to_string()),
+        score,
+        level: PressureLevel::Low,
+        churn_lines: Some(30.0),
+        test_pass_rate: Some(1.0),
+        build_seconds,
+        build_failed: false,
+    }
+}
+
+#[test]
+fn test_pressure_history_keeps_snapshots_per_repository() -> Result<(), Box<dyn std::error::Error>>
+{
+    let dir = TempDir::new()?;
+    let repo = TempDir::new()?;
+    let other = TempDir::new()?;
+
+    let mut history = PressureHistory::load(dir.path(), repo.path())?;
+    history.record(snapshot(at(5, 9), 40, None));
+    history.record(snapshot(at(3, 9), 20, None));
+    history.record(snapshot(at(5, 8), 30, None));
+    history.save(dir.path(), repo.path())?;
+
+    let history = PressureHistory::load(dir.path(), repo.path())?;
+    let scores: Vec<u32> = history.snapshots.iter().map(|s| s.score).collect();
+    assert_eq!(scores, [20, 30, 40]);
+    assert!(PressureHistory::load(dir.path(), other.path())?
+        .snapshots
+        .is_empty());
+
+    let today = NaiveDate::from_ymd_opt(2024, 6, 5).unwrap();
+    assert_eq!(history.recent(today, 2).len(), 2);
+    // The last run of each day stands for that day.
+    assert_eq!(
+        daily_values(&history.snapshots, TrendMetric::Score, today, 4),
+        [None, Some(20.0), None, Some(40.0)]
+    );
+    Ok(())
+}
+
+#[test]
+fn test_regressions_compare_latest_with_best_earlier_value() {
+    let snapshots = [
+        snapshot(at(3, 9), 20, Some(14.0)),
+        snapshot(at(4, 9), 25, Some(12.0)),
+        snapshot(at(5, 9), 30, None),
+        snapshot(at(6, 9), 30, Some(31.0)),
+    ];
+    let regressions = detect_regressions(&snapshots);
+    assert_eq!(regressions.len(), 1);
+    let build = &regressions[0];
+    assert_eq!(build.metric, TrendMetric::BuildTime);
+    assert_eq!((build.from, build.to, build.since), (12.0, 31.0, at(4, 9)));
+
+    let mut worse = snapshot(at(7, 9), 60, Some(13.0));
+    worse.test_pass_rate = Some(0.85);
+    let regressions = detect_regressions(&[snapshots[0].clone(), worse]);
+    let metrics: Vec<TrendMetric> = regressions.iter().map(|r| r.metric).collect();
+    assert_eq!(metrics, [TrendMetric::Score, TrendMetric::PassRate]);
+
+    assert!(detect_regressions(&snapshots[..1]).is_empty());
+}
+
+#[test]
+fn test_bar_chart_scales_columns_to_rows() {
+    let rows = bar_chart(&[Some(0.0), Some(50.0), None, Some(100.0)], 100.0, 2);
+    assert_eq!(rows, ["   █", "▁█·█"]);
+}
+
+#[test]
+fn test_repo_patterns_expand_to_git_repositories() -> Result<(), Box<dyn std::error::Error>> {
+    let dir = TempDir::new()?;
+    for repo in ["code/api", "code/web", "code/nested/deep"] {
+        Repository::init(dir.path().join(repo))?;
+    }
+    fs::create_dir_all(dir.path().join("code/notes"))?;
+
+    let patterns = |list: &[&str]| list.iter().map(|p| p.to_string()).collect::<Vec<_>>();
+    let repos = expand_repo_patterns(&patterns(&["code/*"]), dir.path())?;
+    assert_eq!(
+        repos,
+        [dir.path().join("code/api"), dir.path().join("code/web")]
+    );
+
+    let repos = expand_repo_patterns(
+        &patterns(&["code/**/deep", "code/api", "missing"]),
+        dir.path(),
+    )?;
+    assert_eq!(
+        repos,
+        [
+            dir.path().join("code/nested/deep"),
+            dir.path().join("code/api"),
+            dir.path().join("missing"),
+        ]
+    );
+
+    // A repository listed twice is analyzed once.
+    let repos = expand_repo_patterns(&patterns(&["code/api", "code/a*"]), dir.path())?;
+    assert_eq!(repos, [dir.path().join("code/api")]);
+
+    assert!(matches!(
+        expand_repo_patterns(&patterns(&["code/["]), dir.path()),
+        Err(PressureError::Pattern { setting, pattern, .. })
+            if setting == "pressure.repos" && pattern == "code/["
+    ));
+    Ok(())
+}
+
+#[test]
+fn test_every_repository_gets_a_row_beyond_the_worker_count(
+) -> Result<(), Box<dyn std::error::Error>> {
+    let dir = TempDir::new()?;
+    let workers = thread::available_parallelism().map_or(1, |count| count.get());
+    let repos: Vec<_> = (0..workers + 3)
+        .map(|index| dir.path().join(format!("missing-{:02}", index)))
+        .collect();
+
+    let rows = analyze_repos(&repos, &PressureOptions::default(), Language::ENGLISH);
+    let reported: Vec<_> = rows.iter().map(|row| row.repo.clone()).collect();
+    assert_eq!(reported, repos);
+    assert!(rows
+        .iter()
+        .all(|row| matches!(row.result, Err(PressureError::NotADirectory { .. }))));
+    Ok(())
+}